@@ -1,11 +1,93 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn, ReturnType, Type};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Error, FnArg, ItemFn, Meta, Pat, ReturnType, Token, Type};
+
+/// Signature `#[embassy_agb::main]` expects, quoted in error messages
+const MAIN_SIGNATURE: &str = "async fn main(spawner: Spawner) -> !";
+
+/// Parse attribute arguments as a comma-separated list like `pool_size = 4, name`
+fn parse_args(args: proc_macro2::TokenStream) -> Result<Punctuated<Meta, Token![,]>, Error> {
+    Punctuated::<Meta, Token![,]>::parse_terminated.parse2(args)
+}
+
+/// Check that `f` looks like `async fn main(spawner: Spawner) -> !` (or returns `()`)
+///
+/// Errors point at the offending part of the user's signature, instead of surfacing
+/// deep inside the generated executor code.
+fn validate_main(f: &ItemFn) -> Result<(), Error> {
+    let sig = &f.sig;
+    let error = |spanned: &dyn quote::ToTokens, problem: &str| {
+        Err(Error::new_spanned(
+            spanned,
+            format!("#[embassy_agb::main] {problem}, expected `{MAIN_SIGNATURE}`"),
+        ))
+    };
+
+    if sig.asyncness.is_none() {
+        return error(&sig.fn_token, "function must be `async`");
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return error(&sig.generics, "function can't be generic");
+    }
+
+    let mut inputs = sig.inputs.iter();
+    let (Some(input), None) = (inputs.next(), inputs.next()) else {
+        return Err(Error::new(
+            sig.paren_token.span.join(),
+            format!(
+                "#[embassy_agb::main] function must take exactly one parameter, the \
+                 `Spawner`, expected `{MAIN_SIGNATURE}`"
+            ),
+        ));
+    };
+    let FnArg::Typed(input) = input else {
+        return error(input, "function can't take `self`");
+    };
+    if !matches!(*input.pat, Pat::Ident(_)) {
+        return error(&input.pat, "parameter must be a plain name");
+    }
+    let is_spawner = match &*input.ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Spawner"),
+        _ => false,
+    };
+    if !is_spawner {
+        return error(&input.ty, "parameter must be an `embassy_agb::Spawner`");
+    }
+
+    match &sig.output {
+        ReturnType::Default => Ok(()),
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Never(_) => Ok(()),
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(()),
+            _ => error(ty, "function must return `!` (or nothing)"),
+        },
+    }
+}
 
 /// Main entry point for embassy-agb async applications
 ///
 /// This macro creates an async main function that runs on the embassy executor.
-/// The function must take a single `Spawner` parameter and can be async.
+/// The function must be `async`, take a single `Spawner` parameter and return `!` or
+/// nothing; anything else is a compile error pointing at the signature.
+///
+/// ## Task memory
+///
+/// There is no task arena to configure. embassy-executor stores each task's state
+/// (its future, including everything held across `.await`s) in a static sized at compile
+/// time, with room for `pool_size` instances. agb places these statics in the 256KiB of
+/// EWRAM, next to the heap, so a task that is too large fails to link rather than
+/// failing at runtime. The stack lives in the 32KiB of IWRAM, so keep large buffers in
+/// task state or statics rather than in non-async functions' locals.
+///
+/// `#[embassy_agb::main(arena_size = N)]` is therefore not supported: the argument is
+/// rejected with a compile error, since there is no arena for it to size and agb's
+/// startup code, not the executor, places the stack.
 ///
 /// # Example
 ///
@@ -14,7 +96,7 @@ use syn::{parse_macro_input, ItemFn, ReturnType, Type};
 /// #![no_main]
 ///
 /// use embassy_agb::time::Timer;
-/// use embassy_executor::Spawner;
+/// use embassy_agb::Spawner;
 ///
 /// #[embassy_agb::main]
 /// async fn main(spawner: Spawner) {
@@ -27,16 +109,31 @@ use syn::{parse_macro_input, ItemFn, ReturnType, Type};
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn main(_args: TokenStream, input: TokenStream) -> TokenStream {
-    let f = parse_macro_input!(input as ItemFn);
-
-    // Validate function signature
-    if f.sig.inputs.len() != 1 {
-        return quote! {
-            compile_error!("embassy_agb::main function must take exactly one parameter: Spawner");
-        }
+pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
+    let metas = match parse_args(proc_macro2::TokenStream::from(args)) {
+        Ok(metas) => metas,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    if let Some(arena_size) = metas.iter().find(|meta| meta.path().is_ident("arena_size")) {
+        return Error::new_spanned(
+            arena_size,
+            "#[embassy_agb::main] has no `arena_size`: embassy-executor gives every task its \
+             own static, sized by the compiler, so there is no shared arena to size. Use \
+             `#[embassy_agb::task(pool_size = N)]` to reserve room for N instances of a task",
+        )
+        .to_compile_error()
         .into();
     }
+    if let Some(meta) = metas.first() {
+        return Error::new_spanned(meta, "#[embassy_agb::main] takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let f = parse_macro_input!(input as ItemFn);
+    if let Err(error) = validate_main(&f) {
+        return error.to_compile_error().into();
+    }
 
     // Check return type
     let returns_never =
@@ -47,13 +144,13 @@ pub fn main(_args: TokenStream, input: TokenStream) -> TokenStream {
     let fn_body = &f.block;
     let fn_attrs = &f.attrs;
 
-    // Extract the spawner parameter name
+    // Extract the spawner parameter name, already checked by `validate_main`
     let spawner_param = match f.sig.inputs.first() {
-        Some(syn::FnArg::Typed(pat_type)) => match &*pat_type.pat {
-            syn::Pat::Ident(ident) => &ident.ident,
-            _ => panic!("Expected spawner parameter to be a simple identifier"),
+        Some(FnArg::Typed(pat_type)) => match &*pat_type.pat {
+            Pat::Ident(ident) => &ident.ident,
+            _ => unreachable!(),
         },
-        _ => panic!("Expected spawner parameter"),
+        _ => unreachable!(),
     };
 
     let result = if returns_never {
@@ -77,7 +174,9 @@ pub fn main(_args: TokenStream, input: TokenStream) -> TokenStream {
                 });
             }
 
-            #[::embassy_executor::task]
+            #[::embassy_agb::_internal::embassy_executor::task(
+                embassy_executor = ::embassy_agb::_internal::embassy_executor
+            )]
             async fn main_task(#fn_args) -> ! {
                 #(#fn_attrs)*
                 async fn #fn_name(#fn_args) -> ! #fn_body
@@ -105,7 +204,9 @@ pub fn main(_args: TokenStream, input: TokenStream) -> TokenStream {
                 });
             }
 
-            #[::embassy_executor::task]
+            #[::embassy_agb::_internal::embassy_executor::task(
+                embassy_executor = ::embassy_agb::_internal::embassy_executor
+            )]
             async fn main_task(#fn_args) {
                 #(#fn_attrs)*
                 async fn #fn_name(#fn_args) #fn_body
@@ -124,15 +225,48 @@ pub fn main(_args: TokenStream, input: TokenStream) -> TokenStream {
 
 /// Task macro for embassy-agb
 ///
-/// This is a re-export of the embassy_executor::task macro for convenience.
+/// Wraps `embassy_executor::task`, using the executor re-exported by embassy-agb so the
+/// game doesn't need its own `embassy-executor` dependency. All of its arguments are
+/// passed through.
+///
+/// ## Task pools
+///
+/// By default only one instance of a task can run at a time. `pool_size = N` statically
+/// reserves RAM for `N` instances, so it must be a compile-time constant. Spawn the
+/// instances one by one or with `embassy_agb::spawn_n`:
+///
+/// ```rust,no_run
+/// #[embassy_agb::task(pool_size = 4)]
+/// async fn enemy_ai(index: usize) {
+///     // One instance per enemy
+/// }
+///
+/// # fn example(spawner: embassy_agb::Spawner) {
+/// embassy_agb::spawn_n(&spawner, 4, enemy_ai);
+/// # }
+/// ```
 #[proc_macro_attribute]
 pub fn task(args: TokenStream, input: TokenStream) -> TokenStream {
-    // Just pass through to embassy_executor::task with fully qualified path
     let args = proc_macro2::TokenStream::from(args);
     let input = proc_macro2::TokenStream::from(input);
 
+    let mut metas = match parse_args(args) {
+        Ok(metas) => metas,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    // Point the executor macro at embassy-agb's re-export unless the caller already did
+    if !metas
+        .iter()
+        .any(|meta| meta.path().is_ident("embassy_executor"))
+    {
+        metas.push(syn::parse_quote!(
+            embassy_executor = ::embassy_agb::_internal::embassy_executor
+        ));
+    }
+
     quote! {
-        #[::embassy_executor::task(#args)]
+        #[::embassy_agb::_internal::embassy_executor::task(#metas)]
         #input
     }
     .into()
@@ -1,5 +1,9 @@
 use core::cell::UnsafeCell;
+
 use critical_section::Mutex;
+/// Executor crate used by the `task` and `main` macros, so users don't need to depend on it
+#[cfg(feature = "executor")]
+pub use embassy_executor;
 
 /// Internal storage for the agb::Gba instance
 /// This is used by the macro system to store the Gba instance globally
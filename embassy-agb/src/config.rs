@@ -8,29 +8,45 @@ pub struct Config {
 /// Timer configuration for embassy time driver
 ///
 /// GBA has four 16-bit timers (0-3). Embassy uses one with Divider256 (65.536kHz).
-/// Timers 0-1 often used by sound system, so Timer 2 is default.
+/// Timers 0-1 are used by the sound mixer, so Timer 2 is default.
+///
+/// The timer is chosen at runtime when [`init()`](crate::init) is called, so the
+/// `time-driver-timerN` features only change the default. Creating a sound mixer panics
+/// if the time driver is running on Timer 0 or 1.
+///
+/// Ignored with the `time-driver-vblank` feature, which uses no timer.
 #[derive(Debug, Clone)]
 pub struct TimerConfig {
-    /// Which timer to use (default: Timer2)
+    /// Which timer to use (default: Timer2, or the one picked by a `time-driver-timerN` feature)
     pub timer_number: TimerNumber,
 
     /// Timer overflow amount - lower = better precision, more CPU overhead
     ///
     /// At 65.536kHz: 4=~61μs, 16=~244μs, 64=~1ms (default), 256=~4ms, 1024=~16ms
     pub overflow_amount: u16,
+
+    /// Cascade `timer_number` into the next timer to form a 32-bit counter (default: false)
+    ///
+    /// Cuts the timer interrupts from ~1000/sec to one every ~18 hours while keeping
+    /// full precision for `Instant::now()`, at the cost of a second timer (so Timer3
+    /// can't be used) and frame-granularity (~16.7ms) wakeups for `Timer`s and
+    /// timeouts, which are then serviced from the VBlank interrupt. `overflow_amount`
+    /// is ignored.
+    pub cascade: bool,
 }
 
 impl Default for TimerConfig {
     fn default() -> Self {
         Self {
-            timer_number: TimerNumber::Timer2,
+            timer_number: TimerNumber::from_features(),
             overflow_amount: 64, // ~1ms
+            cascade: false,
         }
     }
 }
 
 /// GBA timer selection (Timer 0-1 often used by sound)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimerNumber {
     /// Timer 0 (0x4000100) - IE/IF bit 3, often used by sound system
     Timer0,
@@ -41,3 +57,19 @@ pub enum TimerNumber {
     /// Timer 3 (0x400010C) - IE/IF bit 6
     Timer3,
 }
+
+impl TimerNumber {
+    /// Default timer as selected by the `time-driver-timerN` features, Timer2 if none is
+    /// enabled
+    fn from_features() -> Self {
+        if cfg!(feature = "time-driver-timer0") {
+            TimerNumber::Timer0
+        } else if cfg!(feature = "time-driver-timer1") {
+            TimerNumber::Timer1
+        } else if cfg!(feature = "time-driver-timer3") {
+            TimerNumber::Timer3
+        } else {
+            TimerNumber::Timer2
+        }
+    }
+}
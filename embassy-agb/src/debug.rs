@@ -0,0 +1,329 @@
+//! Debug logging to the mGBA and no$gba emulator logs
+//!
+//! mGBA exposes a small debug interface in otherwise unused I/O space: a message buffer
+//! and a flush register that appends the buffer to the emulator's log at a chosen level.
+//! no$gba has a similar character output port. Real hardware has neither, so output is
+//! dropped there and the same build runs everywhere.
+//!
+//! [`debug_print!`](crate::debug_print) and [`DebugWriter`] send plain text to whichever
+//! emulator is detected:
+//!
+//! ```rust,no_run
+//! use core::fmt::Write;
+//! use embassy_agb::debug::DebugWriter;
+//!
+//! # let (x, y) = (0, 0);
+//! embassy_agb::debug_print!("player at {}, {}", x, y);
+//!
+//! let mut out = DebugWriter::new();
+//! writeln!(out, "score: {}", 1234).ok();
+//! ```
+//!
+//! ## defmt
+//! With the `defmt` feature, this module provides the `defmt` global logger, so
+//! `defmt::info!` and friends work from any task. defmt frames are binary, while the mGBA
+//! log only takes text, so each frame is written as hex digits. To read the logs, join
+//! the hex from the log lines (a frame may span several lines) and pipe the decoded
+//! bytes into `defmt-print -e <your elf>`.
+//!
+//! The binary must also be linked with `-C link-arg=-Tdefmt.x` next to agb's linker
+//! script, as for any defmt target.
+//!
+//! ## Registers
+//! - `0x4FFF600-0x4FFF6FF`: message buffer, 256 bytes
+//! - `0x4FFF700`: writing bit 8 plus a level (bits 0-2) flushes the buffer to the log
+//! - `0x4FFF780`: write 0xC0DE to enable, reads back 0x1DEA when running in mGBA
+//! - `0x4FFFA00-0x4FFFA0F`: no$gba ID string, starting with "no$gba"
+//! - `0x4FFFA1C`: no$gba character output
+
+use portable_atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// Message buffer
+const MGBA_BUFFER: *mut u8 = 0x04FF_F600 as *mut u8;
+/// Flush and level register
+const MGBA_FLUSH: *mut u16 = 0x04FF_F700 as *mut u16;
+/// Enable register
+const MGBA_ENABLE: *mut u16 = 0x04FF_F780 as *mut u16;
+
+/// Size of the message buffer
+const MGBA_BUFFER_LEN: usize = 256;
+/// Written to [`MGBA_ENABLE`] to turn on the debug interface
+const MGBA_HANDSHAKE_IN: u16 = 0xC0DE;
+/// Read back from [`MGBA_ENABLE`] once the debug interface is on
+const MGBA_HANDSHAKE_OUT: u16 = 0x1DEA;
+/// Flush bit in [`MGBA_FLUSH`]
+const MGBA_FLUSH_BIT: u16 = 1 << 8;
+/// mGBA log level for info messages
+const MGBA_LEVEL_INFO: u16 = 3;
+
+/// no$gba emulator ID string
+const NOCASH_ID: *const u8 = 0x04FF_FA00 as *const u8;
+/// no$gba character output register
+const NOCASH_CHAR_OUT: *mut u32 = 0x04FF_FA1C as *mut u32;
+
+/// Whether [`DebugWriter`] output is enabled
+static OUTPUT_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Result of the mGBA handshake: 0 = not tried yet, 1 = present, 2 = absent
+static MGBA_STATE: AtomicU8 = AtomicU8::new(0);
+
+/// Whether the game runs in mGBA with its debug log available
+///
+/// The handshake runs on the first call and is remembered afterwards.
+pub fn is_mgba() -> bool {
+    match MGBA_STATE.load(Ordering::Relaxed) {
+        0 => {
+            let present = unsafe {
+                MGBA_ENABLE.write_volatile(MGBA_HANDSHAKE_IN);
+                MGBA_ENABLE.read_volatile() == MGBA_HANDSHAKE_OUT
+            };
+            MGBA_STATE.store(if present { 1 } else { 2 }, Ordering::Relaxed);
+            present
+        }
+        state => state == 1,
+    }
+}
+
+/// Whether the game runs in no$gba with its debug output available
+pub fn is_nocash() -> bool {
+    const ID: &[u8] = b"no$gba";
+    ID.iter()
+        .enumerate()
+        .all(|(i, &byte)| unsafe { NOCASH_ID.add(i).read_volatile() } == byte)
+}
+
+/// Turn [`DebugWriter`] and [`debug_print!`](crate::debug_print) output on or off
+///
+/// Output is on by default. Turning it off skips formatting entirely, e.g. to keep noisy
+/// logs out of a release build without removing the calls. Doesn't affect `defmt`.
+pub fn set_output_enabled(enabled: bool) {
+    OUTPUT_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Where [`DebugWriter`] sends its text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Mgba,
+    NoCash,
+    Disabled,
+}
+
+/// [`core::fmt::Write`] sink for the emulator debug log
+///
+/// Each line becomes one log entry in mGBA; in no$gba characters go straight to the
+/// debug window. On real hardware, or after
+/// [`set_output_enabled(false)`](set_output_enabled), writes do nothing. Any unfinished
+/// line is sent when the writer is dropped.
+///
+/// mGBA has a single message buffer, so interleaving two writers (e.g. one in an
+/// interrupt handler) mixes their text. [`debug_print!`](crate::debug_print) avoids that
+/// by writing each message inside a critical section.
+pub struct DebugWriter {
+    backend: Backend,
+    line: MgbaLine,
+}
+
+impl DebugWriter {
+    /// Create a writer for whichever emulator debug output is available
+    pub fn new() -> Self {
+        let backend = if !OUTPUT_ENABLED.load(Ordering::Relaxed) {
+            Backend::Disabled
+        } else if is_mgba() {
+            Backend::Mgba
+        } else if is_nocash() {
+            Backend::NoCash
+        } else {
+            Backend::Disabled
+        };
+
+        Self {
+            backend,
+            line: MgbaLine::new(),
+        }
+    }
+
+    /// Whether written text goes anywhere
+    pub fn is_enabled(&self) -> bool {
+        self.backend != Backend::Disabled
+    }
+}
+
+impl Default for DebugWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Write for DebugWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        match self.backend {
+            Backend::Mgba => {
+                for byte in s.bytes() {
+                    if byte == b'\n' {
+                        self.line.flush(MGBA_LEVEL_INFO);
+                    } else {
+                        self.line.push(byte, MGBA_LEVEL_INFO);
+                    }
+                }
+            }
+            Backend::NoCash => {
+                for byte in s.bytes() {
+                    unsafe { NOCASH_CHAR_OUT.write_volatile(byte as u32) };
+                }
+            }
+            Backend::Disabled => {}
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DebugWriter {
+    fn drop(&mut self) {
+        if self.backend == Backend::Mgba {
+            self.line.flush(MGBA_LEVEL_INFO);
+        }
+    }
+}
+
+/// Print a formatted line to the emulator debug log
+///
+/// Works like `println!`, writing through a [`DebugWriter`](crate::debug::DebugWriter)
+/// inside a critical section so messages from interrupts don't interleave. Does nothing
+/// on real hardware.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # let frame = 0;
+/// embassy_agb::debug_print!("frame {} took too long", frame);
+/// ```
+#[macro_export]
+macro_rules! debug_print {
+    ($($arg:tt)*) => {
+        $crate::debug::_print(format_args!($($arg)*))
+    };
+}
+
+#[doc(hidden)]
+pub fn _print(args: core::fmt::Arguments) {
+    if !OUTPUT_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    critical_section::with(|_| {
+        let mut writer = DebugWriter::new();
+        let _ = core::fmt::Write::write_fmt(&mut writer, args);
+        let _ = core::fmt::Write::write_str(&mut writer, "\n");
+    });
+}
+
+/// Text being assembled in the mGBA message buffer
+struct MgbaLine {
+    len: usize,
+}
+
+impl MgbaLine {
+    const fn new() -> Self {
+        Self { len: 0 }
+    }
+
+    /// Append one byte, flushing first at `level` if the buffer is full
+    fn push(&mut self, byte: u8, level: u16) {
+        if self.len == MGBA_BUFFER_LEN {
+            self.flush(level);
+        }
+        unsafe { MGBA_BUFFER.add(self.len).write_volatile(byte) };
+        self.len += 1;
+    }
+
+    /// Send the buffered text to the log at `level`, if there is any
+    fn flush(&mut self, level: u16) {
+        if self.len == 0 {
+            return;
+        }
+        if self.len < MGBA_BUFFER_LEN {
+            unsafe { MGBA_BUFFER.add(self.len).write_volatile(0) };
+        }
+        unsafe { MGBA_FLUSH.write_volatile(MGBA_FLUSH_BIT | level) };
+        self.len = 0;
+    }
+}
+
+#[cfg(feature = "defmt")]
+mod defmt_logger {
+    use core::cell::UnsafeCell;
+    use portable_atomic::{AtomicBool, Ordering};
+
+    use super::{MgbaLine, MGBA_LEVEL_INFO};
+
+    /// Whether a frame is being written
+    static TAKEN: AtomicBool = AtomicBool::new(false);
+
+    /// Logger state, only touched between `acquire` and `release`
+    struct State {
+        restore: UnsafeCell<critical_section::RestoreState>,
+        encoder: UnsafeCell<defmt::Encoder>,
+        line: UnsafeCell<MgbaLine>,
+    }
+
+    // SAFETY: accessed only inside the critical section taken in `acquire`
+    unsafe impl Sync for State {}
+
+    static STATE: State = State {
+        restore: UnsafeCell::new(critical_section::RestoreState::invalid()),
+        encoder: UnsafeCell::new(defmt::Encoder::new()),
+        line: UnsafeCell::new(MgbaLine::new()),
+    };
+
+    /// Write encoded frame bytes as hex digits
+    fn write_hex(bytes: &[u8]) {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let line = unsafe { &mut *STATE.line.get() };
+        for &byte in bytes {
+            line.push(DIGITS[(byte >> 4) as usize], MGBA_LEVEL_INFO);
+            line.push(DIGITS[(byte & 0x0F) as usize], MGBA_LEVEL_INFO);
+        }
+    }
+
+    #[defmt::global_logger]
+    struct Logger;
+
+    unsafe impl defmt::Logger for Logger {
+        fn acquire() {
+            let restore = unsafe { critical_section::acquire() };
+            if TAKEN.swap(true, Ordering::Relaxed) {
+                panic!("defmt logger taken reentrantly");
+            }
+
+            unsafe {
+                *STATE.restore.get() = restore;
+                if super::is_mgba() {
+                    (*STATE.encoder.get()).start_frame(write_hex);
+                }
+            }
+        }
+
+        unsafe fn flush() {
+            unsafe { (*STATE.line.get()).flush(MGBA_LEVEL_INFO) };
+        }
+
+        unsafe fn release() {
+            unsafe {
+                if super::is_mgba() {
+                    (*STATE.encoder.get()).end_frame(write_hex);
+                    (*STATE.line.get()).flush(MGBA_LEVEL_INFO);
+                }
+
+                TAKEN.store(false, Ordering::Relaxed);
+                critical_section::release(*STATE.restore.get());
+            }
+        }
+
+        unsafe fn write(bytes: &[u8]) {
+            if super::is_mgba() {
+                unsafe { (*STATE.encoder.get()).write(bytes, write_hex) };
+            }
+        }
+    }
+}
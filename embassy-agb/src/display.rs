@@ -4,25 +4,162 @@
 //! for graphics updates without tearing.
 //!
 //! ## Registers
-//! - `DISPSTAT` (0x4000004): bit 3 enables VBlank IRQ
-//! - `IE` (0x4000200): bit 0 for VBlank
+//! - `DISPCNT` (0x4000000): video mode (bits 0-2), displayed bitmap page (bit 4)
+//! - `DISPSTAT` (0x4000004): bit 3 enables VBlank IRQ, bit 4 HBlank IRQ, bit 5 VCount
+//!   IRQ, bits 8-15 select the VCount match line
+//! - `VCOUNT` (0x4000006): scanline currently being drawn (0-227)
+//! - `BLDCNT` (0x4000050): blend targets and effect (brighten/darken for fades)
+//! - `BLDY` (0x4000054): brightness coefficient, 0-16
+//! - `MOSAIC` (0x400004C): mosaic block sizes for backgrounds (bits 0-7) and objects (8-15)
+//! - `BG0CNT`-`BG3CNT` (0x4000008-0x400000E): bit 6 enables mosaic for that background
+//! - `IE` (0x4000200): bit 0 for VBlank, bit 11 for DMA3
 //! - `IF` (0x4000202): bit 0 to acknowledge
+//! - `DMA3SAD`/`DMA3DAD`/`DMA3CNT` (0x40000D4-0x40000DE): DMA3 source, destination, control
 
-use core::cell::Cell;
+use core::cell::{Cell, RefCell};
 use core::future::Future;
 use core::pin::Pin;
-use core::task::{Context, Poll};
+use core::task::{Context, Poll, Waker};
 use portable_atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use agb::display::GraphicsDist;
-use agb::interrupt::{add_interrupt_handler, Interrupt, VBlank};
-use embassy_sync::waitqueue::AtomicWaker;
+use agb::interrupt::{add_interrupt_handler, Interrupt, InterruptHandler, VBlank};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::waitqueue::{AtomicWaker, MultiWakerRegistration};
+
+pub mod affine;
+pub mod animation;
+pub mod blend;
+pub mod scroll;
+pub mod sprites;
+pub mod window;
 
 /// VBlank counter
 static VBLANK_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
-/// VBlank waker  
-static VBLANK_WAKER: AtomicWaker = AtomicWaker::new();
+/// Tasks that can wait for VBlank at the same time before older waiters get woken early
+const VBLANK_WAITERS: usize = 4;
+
+/// VBlank wakers, several because the frame loop, audio helpers and timers in other
+/// tasks may all be waiting at once
+static VBLANK_WAKERS: Mutex<
+    CriticalSectionRawMutex,
+    RefCell<MultiWakerRegistration<VBLANK_WAITERS>>,
+> = Mutex::new(RefCell::new(MultiWakerRegistration::new()));
+
+/// Number of callbacks [`on_vblank()`] can hold at once
+pub const MAX_VBLANK_CALLBACKS: usize = 4;
+
+/// Slots for [`on_vblank()`] callbacks
+type VBlankCallbacks = [Option<fn()>; MAX_VBLANK_CALLBACKS];
+
+/// Callbacks registered with [`on_vblank()`], run in slot order
+static VBLANK_CALLBACKS: Mutex<CriticalSectionRawMutex, Cell<VBlankCallbacks>> =
+    Mutex::new(Cell::new([None; MAX_VBLANK_CALLBACKS]));
+
+/// Time between two VBlanks in microseconds (the GBA refreshes at ~59.73Hz)
+#[cfg(feature = "time")]
+pub(crate) const VBLANK_INTERVAL_US: u64 = 16_743;
+
+/// Display control register (DISPCNT)
+const DISPLAY_CONTROL: *mut u16 = 0x0400_0000 as *mut u16;
+
+/// DISPCNT video mode bits
+const DISPCNT_MODE: u16 = 0b111;
+/// DISPCNT bit selecting bitmap page 1 for display in modes 4 and 5
+const DISPCNT_PAGE: u16 = 1 << 4;
+
+/// Bitmap page 0 in VRAM
+const BITMAP_PAGE_0: *mut u16 = 0x0600_0000 as *mut u16;
+/// Bitmap page 1 in VRAM
+const BITMAP_PAGE_1: *mut u16 = 0x0600_A000 as *mut u16;
+
+/// Display status register (DISPSTAT)
+const DISPLAY_STATUS: *mut u16 = 0x0400_0004 as *mut u16;
+
+/// DISPSTAT bit enabling the VCount match interrupt
+const DISPSTAT_VCOUNT_IRQ: u16 = 1 << 5;
+
+/// Scanline counter register (VCOUNT)
+const VCOUNT: *const u16 = 0x0400_0006 as *const u16;
+
+/// Number of scanlines per frame, including the VBlank lines
+const SCANLINES: u16 = 228;
+
+/// Number of visible scanlines, after which VBlank starts
+const VISIBLE_SCANLINES: u16 = 160;
+
+/// HBlank counter, only advanced while something waits for HBlank
+static HBLANK_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// HBlank waker
+static HBLANK_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// VCount match counter, only advanced while something waits for a scanline
+static VCOUNT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// VCount match waker
+static VCOUNT_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Blend control register (BLDCNT)
+#[cfg(feature = "time")]
+const BLEND_CONTROL: *mut u16 = 0x0400_0050 as *mut u16;
+/// Brightness coefficient register (BLDY)
+#[cfg(feature = "time")]
+const BLEND_BRIGHTNESS: *mut u16 = 0x0400_0054 as *mut u16;
+
+/// BLDCNT first target bits for BG0-3, objects and the backdrop
+#[cfg(feature = "time")]
+const BLEND_ALL_LAYERS: u16 = 0x3F;
+/// BLDCNT effect: brightness increase (fade to white)
+#[cfg(feature = "time")]
+const BLEND_BRIGHTEN: u16 = 0b10 << 6;
+/// BLDCNT effect: brightness decrease (fade to black)
+#[cfg(feature = "time")]
+const BLEND_DARKEN: u16 = 0b11 << 6;
+/// Largest BLDY coefficient, fully faded
+#[cfg(feature = "time")]
+const BRIGHTNESS_MAX: u16 = 16;
+
+/// Mosaic size register (MOSAIC), write-only
+const MOSAIC: *mut u16 = 0x0400_004C as *mut u16;
+/// First background control register (BG0CNT), followed by BG1CNT-BG3CNT
+const BG_CONTROL: *mut u16 = 0x0400_0008 as *mut u16;
+/// BGxCNT bit enabling mosaic for the background
+const BGCNT_MOSAIC: u16 = 1 << 6;
+/// Largest mosaic strength, 16x16 pixel blocks
+const MOSAIC_MAX: u8 = 15;
+
+/// DMA3 source address register
+const DMA3_SOURCE: *mut *const u16 = 0x0400_00D4 as *mut *const u16;
+/// DMA3 destination address register
+const DMA3_DEST: *mut *mut u16 = 0x0400_00D8 as *mut *mut u16;
+/// DMA3 word count register
+const DMA3_COUNT: *mut u16 = 0x0400_00DC as *mut u16;
+/// DMA3 control register
+const DMA3_CONTROL: *mut u16 = 0x0400_00DE as *mut u16;
+
+/// DMA control: enable, IRQ on completion, immediate start, 16-bit, both addresses increment
+const DMA_CONTROL_ASYNC_COPY: u16 = (1 << 15) | (1 << 14);
+
+/// Largest transfer DMA3 can do in one go (a count of 0 means 0x10000)
+const DMA3_MAX_UNITS: usize = 0x1_0000;
+
+/// Number of completed DMA3 transfers started by [`AsyncDisplay::dma_copy()`]
+static DMA_COMPLETIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether a [`AsyncDisplay::dma_copy()`] transfer is in flight
+static DMA_BUSY: AtomicBool = AtomicBool::new(false);
+
+/// DMA completion waker
+static DMA_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Whether the DMA3 handler is initialized
+static DMA_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Whether an [`AsyncDisplay`] exists
+static DISPLAY_TAKEN: AtomicBool = AtomicBool::new(false);
 
 /// Whether the VBlank handler is initialized
 static VBLANK_INITIALIZED: AtomicBool = AtomicBool::new(false);
@@ -36,49 +173,776 @@ fn init_embassy_vblank() {
     let handler = unsafe {
         add_interrupt_handler(Interrupt::VBlank, |_| {
             VBLANK_COUNTER.store(VBLANK_COUNTER.load(Ordering::SeqCst) + 1, Ordering::SeqCst);
-            VBLANK_WAKER.wake();
+            // Copied out so a callback can register or remove callbacks itself
+            for callback in VBLANK_CALLBACKS.lock(Cell::get).into_iter().flatten() {
+                callback();
+            }
+            VBLANK_WAKERS.lock(|wakers| wakers.borrow_mut().wake());
         })
     };
     core::mem::forget(handler);
 }
 
+/// Initialize the DMA3 completion interrupt handler
+fn init_dma_interrupt() {
+    if DMA_INITIALIZED.swap(true, Ordering::SeqCst) {
+        return; // Already initialized
+    }
+
+    let handler = unsafe {
+        add_interrupt_handler(Interrupt::Dma3, |_| {
+            DMA_COMPLETIONS.fetch_add(1, Ordering::SeqCst);
+            DMA_BUSY.store(false, Ordering::SeqCst);
+            DMA_WAKER.wake();
+        })
+    };
+    core::mem::forget(handler);
+}
+
+/// Wake `waker` on the next VBlank
+fn register_vblank_waker(waker: &Waker) {
+    VBLANK_WAKERS.lock(|wakers| wakers.borrow_mut().register(waker));
+}
+
+/// Number of VBlanks seen since the display was first initialized
+pub(crate) fn vblank_count() -> usize {
+    VBLANK_COUNTER.load(Ordering::SeqCst)
+}
+
+/// Wait for the next VBlank without an [`AsyncDisplay`]
+///
+/// Used by other peripherals that need to run in step with the frame rate.
+pub(crate) async fn wait_for_vblank() {
+    init_embassy_vblank();
+    EmbassyVBlankFuture::new().await
+}
+
+/// Run `callback` from the VBlank interrupt on every VBlank
+///
+/// A bridge for synchronous agb code that expects to run at the start of VBlank, such
+/// as an OAM update, while the rest of the game moves to async. Callbacks run one after
+/// another, before any task waiting in
+/// [`wait_for_vblank()`](AsyncDisplay::wait_for_vblank) is woken, so both can be used
+/// together. The VBlank interrupt is enabled if it wasn't already.
+///
+/// ## Interrupt context
+/// The callback runs inside the interrupt handler, with other interrupts held off:
+/// - Keep it short, a few hundred cycles at most. Long callbacks delay the time driver,
+///   the audio timers and the tasks woken afterwards, and eat into VBlank itself.
+/// - It can interrupt any code, including a task halfway through changing data the
+///   callback reads. Share state through atomics or a critical section.
+/// - It is never re-entered, as the next VBlank is a whole frame away, and it may
+///   itself call [`on_vblank()`] or [`VBlankCallback::remove()`].
+///
+/// # Panics
+///
+/// Panics if [`MAX_VBLANK_CALLBACKS`] callbacks are already registered.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use portable_atomic::{AtomicU32, Ordering};
+///
+/// static FRAMES: AtomicU32 = AtomicU32::new(0);
+///
+/// fn count_frame() {
+///     FRAMES.fetch_add(1, Ordering::Relaxed);
+/// }
+///
+/// let counter = embassy_agb::on_vblank(count_frame);
+/// // ...
+/// counter.remove();
+/// ```
+pub fn on_vblank(callback: fn()) -> VBlankCallback {
+    init_embassy_vblank();
+
+    let slot = VBLANK_CALLBACKS.lock(|callbacks| {
+        let mut slots = callbacks.get();
+        let slot = slots.iter().position(Option::is_none);
+        if let Some(slot) = slot {
+            slots[slot] = Some(callback);
+            callbacks.set(slots);
+        }
+        slot
+    });
+
+    VBlankCallback {
+        slot: slot.expect("too many VBlank callbacks registered"),
+    }
+}
+
+/// Registration of an [`on_vblank()`] callback
+///
+/// The callback stays registered until [`remove()`](Self::remove) is called, so this
+/// can be dropped to keep it running for the rest of the game.
+#[derive(Debug)]
+pub struct VBlankCallback {
+    slot: usize,
+}
+
+impl VBlankCallback {
+    /// Stop running the callback, freeing its slot
+    pub fn remove(self) {
+        VBLANK_CALLBACKS.lock(|callbacks| {
+            let mut slots = callbacks.get();
+            slots[self.slot] = None;
+            callbacks.set(slots);
+        });
+    }
+}
+
+/// Destination of an [`AsyncDisplay::dma_copy()`] transfer
+///
+/// Destinations are bounds checked when the transfer starts and panic if the copied
+/// data would not fit, like slice indexing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DmaDest {
+    /// Background palette RAM, starting at the given color index (0-255)
+    BackgroundPalette(usize),
+    /// Object (sprite) palette RAM, starting at the given color index (0-255)
+    ObjectPalette(usize),
+    /// VRAM, starting at the given byte offset from 0x0600_0000 (must be even)
+    Vram(usize),
+}
+
+impl DmaDest {
+    /// Resolve to a hardware address, checking that `len` halfwords fit
+    fn address(self, len: usize) -> *mut u16 {
+        let (base, offset, capacity) = match self {
+            DmaDest::BackgroundPalette(index) => (0x0500_0000, index, 256),
+            DmaDest::ObjectPalette(index) => (0x0500_0200, index, 256),
+            DmaDest::Vram(byte_offset) => {
+                assert!(
+                    byte_offset % 2 == 0,
+                    "VRAM DMA offset must be halfword aligned"
+                );
+                (0x0600_0000, byte_offset / 2, 0x1_8000 / 2)
+            }
+        };
+
+        assert!(
+            offset <= capacity && len <= capacity - offset,
+            "DMA transfer of {len} halfwords at {self:?} is out of bounds"
+        );
+        (base as *mut u16).wrapping_add(offset)
+    }
+}
+
+/// Render headroom reported by [`AsyncDisplay::wait_for_vblank_info()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VblankInfo {
+    /// Scanline the display was on when the wait started (0-227)
+    pub scanline: u16,
+    /// How many lines into VBlank the frame's work ran, 0 if it finished in the visible
+    /// region
+    ///
+    /// A non-zero value means the VBlank that was due had already started, so the wait
+    /// lasted until the following one.
+    pub scanlines_over_budget: u16,
+}
+
 /// Async wrapper for agb display operations
 pub struct AsyncDisplay<'a> {
-    graphics: agb::display::Graphics<'a>,
+    /// `None` once moved out by [`take()`](Self::take)
+    graphics: Option<agb::display::Graphics<'a>>,
     #[allow(dead_code)]
     vblank: VBlank,
+    /// VBlank the last [`wait_for_vblank_count()`](Self::wait_for_vblank_count) resolved on
+    paced_from: Option<usize>,
+    _taken: Option<crate::TakeGuard>,
 }
 
 impl<'a> AsyncDisplay<'a> {
     pub(crate) fn new(graphics_dist: &'a mut GraphicsDist) -> Self {
+        let taken = crate::TakeGuard::take(&DISPLAY_TAKEN, "display");
         init_embassy_vblank();
 
         Self {
-            graphics: graphics_dist.get(),
+            graphics: Some(graphics_dist.get()),
             vblank: VBlank::get(),
+            paced_from: None,
+            _taken: Some(taken),
         }
     }
 
+    /// Move the graphics out into a new display, leaving this one only able to wait
+    ///
+    /// Used by [`GbaPeripherals::take_display()`](crate::GbaPeripherals::take_display).
+    /// Returns `None` if the graphics were already taken.
+    pub(crate) fn take(&mut self) -> Option<Self> {
+        let graphics = self.graphics.take()?;
+        Some(Self {
+            graphics: Some(graphics),
+            vblank: VBlank::get(),
+            paced_from: None,
+            _taken: self._taken.take(),
+        })
+    }
+
+    fn graphics_mut(&mut self) -> &mut agb::display::Graphics<'a> {
+        self.graphics
+            .as_mut()
+            .expect("display graphics were moved out with GbaPeripherals::take_display()")
+    }
+
     /// Wait for the next VBlank (~16.7ms at 60Hz)
     pub async fn wait_for_vblank(&self) {
         EmbassyVBlankFuture::new().await
     }
 
+    /// Scanline the display is drawing right now
+    ///
+    /// Reads VCOUNT: 0-159 are the visible lines and 160-227 are VBlank. Each line takes
+    /// 1232 cycles (~73µs), so the value can be stale by the time it is used. For work
+    /// that must happen on a given line, [`wait_for_vcount()`](Self::wait_for_vcount)
+    /// waits for it with an interrupt instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(display: &embassy_agb::display::AsyncDisplay<'_>) {
+    /// // Only start a large VRAM upload if most of VBlank is still ahead
+    /// if display.current_scanline() < 200 {
+    ///     // Upload
+    /// }
+    /// # }
+    /// ```
+    pub fn current_scanline(&self) -> u16 {
+        unsafe { VCOUNT.read_volatile() }
+    }
+
+    /// Whether the display is in VBlank (scanlines 160-227), when VRAM, OAM and palettes
+    /// can be written without tearing
+    pub fn is_in_vblank(&self) -> bool {
+        self.current_scanline() >= VISIBLE_SCANLINES
+    }
+
+    /// Wait for the next VBlank, reporting how much of the frame the work used
+    ///
+    /// Reads VCOUNT when called, i.e. right after the frame's work finished. Finishing
+    /// before scanline 160 leaves `160 - scanline` lines of headroom; finishing inside
+    /// VBlank shows up in [`VblankInfo::scanlines_over_budget`].
+    ///
+    /// Work that ran past the end of VBlank into the next frame reads as a small
+    /// scanline again and can't be told apart from finishing early; use
+    /// [`FrameEvents::dropped_frames`](crate::FrameEvents::dropped_frames) to catch whole
+    /// dropped frames.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// let info = display.wait_for_vblank_info().await;
+    /// if info.scanlines_over_budget > 0 {
+    ///     // Render less next frame
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_vblank_info(&mut self) -> VblankInfo {
+        let scanline = self.current_scanline();
+        let scanlines_over_budget = if scanline >= VISIBLE_SCANLINES {
+            scanline - VISIBLE_SCANLINES + 1
+        } else {
+            0
+        };
+
+        EmbassyVBlankFuture::new().await;
+        VblankInfo {
+            scanline,
+            scanlines_over_budget,
+        }
+    }
+
+    /// Show the page that was just drawn and return the other one for the next frame
+    ///
+    /// Waits for VBlank, swaps the displayed bitmap page, and returns the page that is now
+    /// hidden as raw VRAM halfwords. The swap happens during VBlank and the returned page
+    /// is never the one being scanned out, so drawing into it can't tear the visible
+    /// frame. The borrow of `self` keeps the page from being used across the next flip.
+    ///
+    /// Supported video modes (as set in DISPCNT):
+    /// - Mode 4: 240x160, 8-bit palette indices, two pixels per halfword (19200 halfwords)
+    /// - Mode 5: 160x128, 15-bit color, one pixel per halfword (20480 halfwords)
+    ///
+    /// Mode 3 fills VRAM with a single 240x160 page and has nothing to flip to; the tile
+    /// modes 0-2 have no bitmap pages at all.
+    ///
+    /// Committing an agb `GraphicsFrame` rewrites DISPCNT, so don't mix this with agb's
+    /// tiled backgrounds and objects.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the display is not in mode 4 or 5.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// // Mode 5 with BG2 enabled
+    /// unsafe { (0x0400_0000 as *mut u16).write_volatile(5 | (1 << 10)) };
+    ///
+    /// loop {
+    ///     let page = display.flip_page().await;
+    ///     page.fill(0); // Clear to black, then draw the next frame
+    /// #   break;
+    /// }
+    /// # }
+    /// ```
+    pub async fn flip_page(&mut self) -> &mut [u16] {
+        let len = match unsafe { DISPLAY_CONTROL.read_volatile() } & DISPCNT_MODE {
+            4 => 240 * 160 / 2,
+            5 => 160 * 128,
+            mode => panic!("flip_page needs bitmap mode 4 or 5, display is in mode {mode}"),
+        };
+
+        EmbassyVBlankFuture::new().await;
+
+        let control = unsafe { DISPLAY_CONTROL.read_volatile() } ^ DISPCNT_PAGE;
+        unsafe { DISPLAY_CONTROL.write_volatile(control) };
+
+        let hidden = if control & DISPCNT_PAGE != 0 {
+            BITMAP_PAGE_0
+        } else {
+            BITMAP_PAGE_1
+        };
+        // SAFETY: the hidden page lies in VRAM, isn't displayed, and is only reachable
+        // through this borrow of `self`
+        unsafe { core::slice::from_raw_parts_mut(hidden, len) }
+    }
+
+    /// Wait until `n` VBlanks have passed since the previous call resolved
+    ///
+    /// Counting from the previous call rather than from now means the time spent on game
+    /// logic counts towards the wait, so `wait_for_vblank_count(2)` in a loop gives a
+    /// steady 30Hz. The first call counts from now.
+    ///
+    /// If the caller is falling behind and `n` VBlanks have already passed, this resolves
+    /// immediately and returns how many VBlanks late it is (0 when on time). Pacing then
+    /// restarts from the current VBlank instead of trying to catch up.
+    ///
+    /// The mixer is not serviced while waiting. When audio is playing,
+    /// [`AsyncMixer::frame()`](crate::sound::AsyncMixer::frame) must still run once per
+    /// VBlank, so wait one VBlank at a time (as
+    /// [`GbaPeripherals::wait_frame()`](crate::GbaPeripherals::wait_frame) does) instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// loop {
+    ///     // Game logic at 30Hz
+    ///     let late = display.wait_for_vblank_count(2).await;
+    ///     if late > 0 {
+    ///         // Skip some rendering to catch up
+    ///     }
+    ///     # break;
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_vblank_count(&mut self, n: u32) -> u32 {
+        let now = VBLANK_COUNTER.load(Ordering::SeqCst);
+        let deadline = self.paced_from.unwrap_or(now).wrapping_add(n as usize);
+
+        let late = now.wrapping_sub(deadline) as isize;
+        if late >= 0 {
+            self.paced_from = Some(now);
+            return late as u32;
+        }
+
+        VBlankCountFuture { target: deadline }.await;
+        self.paced_from = Some(deadline);
+        0
+    }
+
+    /// Wait for the next HBlank
+    ///
+    /// The HBlank interrupt is only enabled while this is pending, since it would
+    /// otherwise fire 228 times per frame. See
+    /// [`wait_for_vcount()`](Self::wait_for_vcount) for notes on latency.
+    pub async fn wait_for_hblank(&mut self) {
+        HBlankFuture {
+            handler: None,
+            start: 0,
+        }
+        .await
+    }
+
+    /// Wait until the display reaches scanline `line`
+    ///
+    /// Lines 0-159 are visible and 160-227 are VBlank. This enables the VCount interrupt
+    /// for the requested line and disables it again once resolved or dropped, so it can
+    /// be used for mid-screen effects such as swapping a palette halfway down.
+    ///
+    /// ## Latency
+    ///
+    /// The interrupt fires at the start of the line, but the future only resolves when
+    /// the executor next polls the waiting task. That is typically a few hundred cycles
+    /// after the interrupt (a scanline is 1232 cycles), and more if other tasks are ready
+    /// to run first. Effects that need cycle-exact timing on every line should use
+    /// agb's HBlank DMA instead.
+    ///
+    /// Panics if `line` is not below 228.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// loop {
+    ///     display.wait_for_vcount(80).await;
+    ///     // Swap to the water palette for the lower half of the screen
+    ///     display.wait_for_vblank().await;
+    ///     // Restore the sky palette for the next frame
+    /// #   break;
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_vcount(&mut self, line: u16) {
+        assert!(line < SCANLINES, "scanline {line} does not exist");
+        VCountFuture {
+            line,
+            handler: None,
+            start: 0,
+        }
+        .await
+    }
+
+    /// Fade the whole screen out to `color` over `over`
+    ///
+    /// Steps the hardware brightness registers once per VBlank and leaves the screen fully
+    /// faded when done, ready for [`fade_in()`](Self::fade_in) once the next scene is
+    /// loaded. The brightness hardware can only fade towards black or white, so `color`
+    /// picks whichever of the two is closer (use `rgb15!(0x000000)` or `rgb15!(0xFFFFFF)`).
+    ///
+    /// Committing an agb `GraphicsFrame` rewrites the blend registers, which cancels the
+    /// fade effect. Load the next scene without committing a frame, then fade back in.
+    ///
+    /// Dropping the future mid-fade jumps straight to the fully faded state.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::Duration;
+    /// # use embassy_agb::rgb15;
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// display.fade_out(rgb15!(0x000000), Duration::from_millis(500)).await;
+    /// // Load the next scene
+    /// display.fade_in(rgb15!(0x000000), Duration::from_millis(500)).await;
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn fade_out(&mut self, color: u16, over: embassy_time::Duration) {
+        self.fade(color, over, true).await
+    }
+
+    /// Fade the whole screen in from `color` over `over`
+    ///
+    /// The counterpart to [`fade_out()`](Self::fade_out): starts fully faded to `color`
+    /// (black or white, whichever is closer) and steps back once per VBlank. When done the
+    /// blend registers are reset to normal, with no blend effect.
+    ///
+    /// Dropping the future mid-fade jumps straight to the normal state.
+    #[cfg(feature = "time")]
+    pub async fn fade_in(&mut self, color: u16, over: embassy_time::Duration) {
+        self.fade(color, over, false).await
+    }
+
+    #[cfg(feature = "time")]
+    async fn fade(&mut self, color: u16, over: embassy_time::Duration, out: bool) {
+        let effect = if is_closer_to_white(color) {
+            BLEND_BRIGHTEN
+        } else {
+            BLEND_DARKEN
+        };
+        let frames = (over.as_micros() / VBLANK_INTERVAL_US).max(1) as u32;
+
+        // Leaves the end state in place even if this future is dropped part way
+        let _guard = FadeGuard { effect, out };
+
+        for step in 0..=frames {
+            let progress = (BRIGHTNESS_MAX as u32 * step / frames) as u16;
+            let brightness = if out {
+                progress
+            } else {
+                BRIGHTNESS_MAX - progress
+            };
+
+            self.wait_for_vblank().await;
+            set_fade(effect, brightness);
+        }
+    }
+
+    /// Set the mosaic (pixelation) strength for backgrounds and objects
+    ///
+    /// Each strength is 0-15 (clamped): 0 turns the effect off, and `n` draws blocks of
+    /// `n + 1` by `n + 1` pixels. A non-zero `bg` strength also turns mosaic on for all
+    /// four backgrounds, and `0` turns it off again. `obj` only affects objects with
+    /// their own mosaic flag set, which agb's `Object` doesn't set.
+    ///
+    /// Like fades, this writes the hardware registers directly, and committing an agb
+    /// `GraphicsFrame` rewrites the background control registers, which turns mosaic
+    /// back off for the backgrounds. Set it again after each commit to keep it.
+    pub fn set_mosaic(&mut self, bg: u8, obj: u8) {
+        set_mosaic(bg, obj);
+    }
+
+    /// Animate background mosaic from strength `from` to `to` over `over`
+    ///
+    /// Steps the strength once per VBlank, using the scale of
+    /// [`set_mosaic()`](Self::set_mosaic), and shows `to` for a full frame. When done, and
+    /// also if the future is dropped part way, mosaic is turned off so the screen is never
+    /// left pixelated. It returns at the start of a VBlank, so when pixelating a scene
+    /// away, replace it right after (commit the next scene's first frame, or darken the
+    /// screen) before the sharp old scene is drawn again. As with `set_mosaic()`,
+    /// committing a frame while it runs turns mosaic off for the backgrounds until the
+    /// next step.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::Duration;
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+    /// let over = Duration::from_millis(400);
+    ///
+    /// display.mosaic_transition(0, 15, over).await;
+    /// // Show the next scene, then sharpen it in
+    /// display.mosaic_transition(15, 0, over).await;
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn mosaic_transition(&mut self, from: u8, to: u8, over: embassy_time::Duration) {
+        let (from, to) = (from.min(MOSAIC_MAX) as i32, to.min(MOSAIC_MAX) as i32);
+        let frames = (over.as_micros() / VBLANK_INTERVAL_US).max(1) as i32;
+
+        // Clears mosaic even if this future is dropped part way
+        let _guard = MosaicGuard;
+
+        for step in 0..=frames {
+            let strength = from + (to - from) * step / frames;
+            self.wait_for_vblank().await;
+            set_mosaic(strength as u8, 0);
+        }
+        self.wait_for_vblank().await;
+    }
+
     /// Get a frame for rendering, waiting for VBlank if needed
     pub async fn frame(&mut self) -> agb::display::GraphicsFrame<'_> {
         self.wait_for_vblank().await;
-        self.graphics.frame()
+        self.graphics_mut().frame()
     }
 
     /// Get a frame for rendering without waiting for VBlank
     /// Use this when you've already called wait_for_vblank() separately
     pub fn frame_no_wait(&mut self) -> agb::display::GraphicsFrame<'_> {
-        self.graphics.frame()
+        self.graphics_mut().frame()
+    }
+
+    /// Copy `src` into palette RAM or VRAM using DMA, resolving when the transfer completes
+    ///
+    /// The copy runs on DMA channel 3 and completion is signalled by the DMA3 interrupt.
+    /// Note that the GBA CPU is paused while a DMA runs, so this doesn't let other code
+    /// run during the copy, but it moves data several times faster than a CPU loop.
+    ///
+    /// If a previous transfer is still in flight (for example because its future was
+    /// dropped before completing), this waits for it to finish before starting. Transfers
+    /// larger than DMA3's 65536-halfword limit are split into several.
+    ///
+    /// Panics if `src` does not fit at `dst`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::display::DmaDest;
+    /// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>, palette: &[u16; 16]) {
+    /// display.wait_for_vblank().await;
+    /// display.dma_copy(palette, DmaDest::BackgroundPalette(0)).await;
+    /// # }
+    /// ```
+    pub async fn dma_copy(&mut self, src: &[u16], dst: DmaDest) {
+        let mut dest = dst.address(src.len());
+        init_dma_interrupt();
+
+        for chunk in src.chunks(DMA3_MAX_UNITS) {
+            DmaIdleFuture.await;
+
+            DMA_BUSY.store(true, Ordering::SeqCst);
+            let started = DMA_COMPLETIONS.load(Ordering::SeqCst);
+            unsafe {
+                DMA3_SOURCE.write_volatile(chunk.as_ptr());
+                DMA3_DEST.write_volatile(dest);
+                // A count of 0 transfers the maximum of 0x10000 halfwords
+                DMA3_COUNT.write_volatile(chunk.len() as u16);
+                DMA3_CONTROL.write_volatile(DMA_CONTROL_ASYNC_COPY);
+            }
+
+            DmaTransferFuture { started }.await;
+            dest = dest.wrapping_add(chunk.len());
+        }
     }
 
     /// Get access to the underlying graphics for synchronous operations
     pub fn graphics(&mut self) -> &mut agb::display::Graphics<'a> {
-        &mut self.graphics
+        self.graphics_mut()
+    }
+}
+
+/// Timing of a frame reported by [`FramePacer::wait_frame_paced()`]
+#[cfg(feature = "time")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacingInfo {
+    /// VBlanks missed because the previous frame's work overran its budget (0 when on time)
+    pub dropped: u32,
+    /// Display time covered by this frame: one VBlank interval plus one per dropped frame
+    pub dt: embassy_time::Duration,
+}
+
+/// Fixed-timestep frame pacing on top of [`AsyncDisplay`]
+///
+/// Each [`wait_frame_paced()`](Self::wait_frame_paced) waits for the next VBlank and
+/// reports how many VBlanks went by since the previous one resolved. A fixed-timestep
+/// game can run one logic step per frame plus one per dropped frame to stay in sync
+/// with real time. `dt` is derived from the VBlank count, so it is always an exact
+/// multiple of the refresh interval.
+///
+/// The pacer borrows the display and derefs to it, so rendering goes through the pacer
+/// while it is alive. As with [`AsyncDisplay::wait_for_vblank_count()`], the mixer is not
+/// serviced, so call [`AsyncMixer::frame()`](crate::sound::AsyncMixer::frame) once per
+/// frame yourself when audio is playing.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(display: &mut embassy_agb::display::AsyncDisplay<'_>) {
+/// use embassy_agb::display::FramePacer;
+///
+/// let mut pacer = FramePacer::new(display);
+/// loop {
+///     let info = pacer.wait_frame_paced().await;
+///     for _ in 0..=info.dropped {
+///         // Run one fixed logic step
+///     }
+///     # break;
+/// }
+/// # }
+/// ```
+#[cfg(feature = "time")]
+pub struct FramePacer<'d, 'a> {
+    display: &'d mut AsyncDisplay<'a>,
+    /// VBlank the previous frame resolved on
+    last: Option<usize>,
+}
+
+#[cfg(feature = "time")]
+impl<'d, 'a> FramePacer<'d, 'a> {
+    /// Create a pacer for `display`, counting from the first frame waited on
+    pub fn new(display: &'d mut AsyncDisplay<'a>) -> Self {
+        Self {
+            display,
+            last: None,
+        }
+    }
+
+    /// Wait for the next VBlank and report how the previous frame kept to its budget
+    ///
+    /// Always waits for a fresh VBlank so rendering starts at the top of the blanking
+    /// period, even when the previous frame overran. The first call reports no dropped
+    /// frames.
+    pub async fn wait_frame_paced(&mut self) -> PacingInfo {
+        self.display.wait_for_vblank().await;
+
+        let now = VBLANK_COUNTER.load(Ordering::SeqCst);
+        let elapsed = match self.last {
+            Some(last) => now.wrapping_sub(last).max(1) as u32,
+            None => 1,
+        };
+        self.last = Some(now);
+
+        PacingInfo {
+            dropped: elapsed - 1,
+            dt: embassy_time::Duration::from_micros(elapsed as u64 * VBLANK_INTERVAL_US),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl<'a> core::ops::Deref for FramePacer<'_, 'a> {
+    type Target = AsyncDisplay<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.display
+    }
+}
+
+#[cfg(feature = "time")]
+impl core::ops::DerefMut for FramePacer<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.display
+    }
+}
+
+/// Whether a 15-bit BGR color is closer to white than to black
+#[cfg(feature = "time")]
+fn is_closer_to_white(color: u16) -> bool {
+    let r = color & 0x1F;
+    let g = (color >> 5) & 0x1F;
+    let b = (color >> 10) & 0x1F;
+    r + g + b > (3 * 31) / 2
+}
+
+/// Apply a brightness fade to every layer, or clear the blend effect if `brightness` is 0
+#[cfg(feature = "time")]
+fn set_fade(effect: u16, brightness: u16) {
+    let control = if brightness == 0 {
+        0
+    } else {
+        BLEND_ALL_LAYERS | effect
+    };
+
+    unsafe {
+        BLEND_CONTROL.write_volatile(control);
+        BLEND_BRIGHTNESS.write_volatile(brightness);
+    }
+}
+
+/// Write the mosaic strengths and turn mosaic on or off for every background
+fn set_mosaic(bg: u8, obj: u8) {
+    let (bg, obj) = (bg.min(MOSAIC_MAX) as u16, obj.min(MOSAIC_MAX) as u16);
+
+    unsafe {
+        MOSAIC.write_volatile(bg | (bg << 4) | (obj << 8) | (obj << 12));
+        for index in 0..4 {
+            let control = BG_CONTROL.add(index);
+            let value = control.read_volatile();
+            control.write_volatile(if bg == 0 {
+                value & !BGCNT_MOSAIC
+            } else {
+                value | BGCNT_MOSAIC
+            });
+        }
+    }
+}
+
+/// Turns mosaic off when dropped
+#[cfg(feature = "time")]
+struct MosaicGuard;
+
+#[cfg(feature = "time")]
+impl Drop for MosaicGuard {
+    fn drop(&mut self) {
+        set_mosaic(0, 0);
+    }
+}
+
+/// Puts the screen in the final state of a fade when dropped
+#[cfg(feature = "time")]
+struct FadeGuard {
+    effect: u16,
+    out: bool,
+}
+
+#[cfg(feature = "time")]
+impl Drop for FadeGuard {
+    fn drop(&mut self) {
+        set_fade(self.effect, if self.out { BRIGHTNESS_MAX } else { 0 });
     }
 }
 
@@ -108,7 +972,7 @@ impl Future for EmbassyVBlankFuture {
             Poll::Ready(())
         } else {
             // Register waker for next VBlank
-            VBLANK_WAKER.register(cx.waker());
+            register_vblank_waker(cx.waker());
 
             // Check again in case VBlank occurred between the first check and waker registration
             let current_count = VBLANK_COUNTER.load(Ordering::SeqCst);
@@ -122,17 +986,173 @@ impl Future for EmbassyVBlankFuture {
     }
 }
 
-/// Future for DMA-based transfers (placeholder for future implementation)
-pub struct DmaTransferFuture {
-    _phantom: core::marker::PhantomData<()>,
+/// Future that completes once the VBlank counter reaches `target`
+pub(crate) struct VBlankCountFuture {
+    target: usize,
+}
+
+impl VBlankCountFuture {
+    /// Complete on the first VBlank after the current one
+    #[cfg(feature = "time")]
+    pub(crate) fn next() -> Self {
+        init_embassy_vblank();
+        Self {
+            target: vblank_count().wrapping_add(1),
+        }
+    }
+
+    fn is_reached(&self) -> bool {
+        VBLANK_COUNTER
+            .load(Ordering::SeqCst)
+            .wrapping_sub(self.target) as isize
+            >= 0
+    }
+}
+
+impl Future for VBlankCountFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.is_reached() {
+            return Poll::Ready(());
+        }
+
+        register_vblank_waker(cx.waker());
+
+        // Check again in case VBlank occurred before the waker was registered
+        if self.is_reached() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Future that completes on the next HBlank, installing the interrupt handler while pending
+struct HBlankFuture {
+    handler: Option<InterruptHandler>,
+    start: usize,
+}
+
+impl Future for HBlankFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        HBLANK_WAKER.register(cx.waker());
+
+        if self.handler.is_none() {
+            self.start = HBLANK_COUNTER.load(Ordering::SeqCst);
+            self.handler = Some(unsafe {
+                add_interrupt_handler(Interrupt::HBlank, |_| {
+                    HBLANK_COUNTER.fetch_add(1, Ordering::SeqCst);
+                    HBLANK_WAKER.wake();
+                })
+            });
+            return Poll::Pending;
+        }
+
+        if HBLANK_COUNTER.load(Ordering::SeqCst) != self.start {
+            // Dropping the handler disables the interrupt again
+            self.handler = None;
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Future that completes when VCOUNT matches `line`
+///
+/// Enables the VCount interrupt on first poll and disables it again when dropped.
+struct VCountFuture {
+    line: u16,
+    handler: Option<InterruptHandler>,
+    start: usize,
+}
+
+impl Future for VCountFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        VCOUNT_WAKER.register(cx.waker());
+
+        if self.handler.is_none() {
+            self.start = VCOUNT_COUNTER.load(Ordering::SeqCst);
+            self.handler = Some(unsafe {
+                add_interrupt_handler(Interrupt::VCounter, |_| {
+                    VCOUNT_COUNTER.fetch_add(1, Ordering::SeqCst);
+                    VCOUNT_WAKER.wake();
+                })
+            });
+
+            critical_section::with(|_| unsafe {
+                let status = DISPLAY_STATUS.read_volatile() & 0x00FF;
+                DISPLAY_STATUS.write_volatile(status | (self.line << 8) | DISPSTAT_VCOUNT_IRQ);
+            });
+            return Poll::Pending;
+        }
+
+        if VCOUNT_COUNTER.load(Ordering::SeqCst) != self.start {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for VCountFuture {
+    fn drop(&mut self) {
+        if self.handler.is_some() {
+            critical_section::with(|_| unsafe {
+                let status = DISPLAY_STATUS.read_volatile();
+                DISPLAY_STATUS.write_volatile(status & !DISPSTAT_VCOUNT_IRQ);
+            });
+        }
+    }
+}
+
+/// Future that completes once no DMA transfer is in flight
+struct DmaIdleFuture;
+
+impl Future for DmaIdleFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !DMA_BUSY.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        DMA_WAKER.register(cx.waker());
+
+        // Check again in case the transfer completed before the waker was registered
+        if DMA_BUSY.load(Ordering::SeqCst) {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+/// Future that completes when the DMA transfer started after `started` completions finishes
+struct DmaTransferFuture {
+    started: usize,
 }
 
 impl Future for DmaTransferFuture {
     type Output = ();
 
-    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // For now, complete immediately
-        // TODO: Implement actual DMA async support
-        Poll::Ready(())
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if DMA_COMPLETIONS.load(Ordering::SeqCst) != self.started {
+            return Poll::Ready(());
+        }
+
+        DMA_WAKER.register(cx.waker());
+
+        // Check again in case the interrupt fired before the waker was registered
+        if DMA_COMPLETIONS.load(Ordering::SeqCst) != self.started {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
     }
 }
@@ -0,0 +1,184 @@
+//! Rotation and scaling for affine backgrounds
+//!
+//! Affine backgrounds (available in video modes 1 and 2) are drawn through a 2x2 matrix
+//! and an offset that map each screen pixel back to a pixel of the background.
+//! [`AffineLayer`] keeps the rotation, zoom and pivot point separately and rebuilds
+//! that matrix with the BIOS `BgAffineSet` call whenever one of them changes, so
+//! spinning or zooming a background is a matter of setting an angle or a factor.
+//!
+//! All values are fixed point: angles in turns as `Num<i32, 16>` (so `1` is a full
+//! turn, agb's convention for its trig functions) and zoom factors as `Num<i32, 8>`.
+//!
+//! ## Perspective floors
+//! A racing-game floor is a different zoom on every scanline. Compute the matrix for
+//! each line with [`AffineLayer::transform()`] after [`set_scale()`](AffineLayer::set_scale)
+//! and hand the results to an HBlank DMA; the matrix for the whole screen only needs
+//! [`show()`](AffineLayer::show).
+
+use agb::display::tiled::{AffineBackground, AffineBackgroundId, AffineMatrixBackground};
+use agb::display::{GraphicsFrame, HEIGHT, WIDTH};
+use agb::fixnum::{Num, Vector2D};
+
+/// Rotation, zoom and pivot of an affine background
+///
+/// The pivot is a point of the background, in background pixels, that is drawn at a
+/// fixed point of the screen. Rotation and zoom happen around it. By default the
+/// background's top-left corner sits at the middle of the screen, unrotated and at
+/// normal size.
+///
+/// # Example
+///
+/// A title logo that spins in while growing to full size:
+///
+/// ```rust,no_run
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     mut logo: agb::display::tiled::AffineBackground,
+/// # ) {
+/// use agb::fixnum::{num, Num};
+/// use embassy_agb::display::affine::AffineLayer;
+///
+/// let mut layer = AffineLayer::new();
+/// // Pivot on the middle of the 128x128 pixel logo
+/// layer.set_center(num!(64), num!(64));
+///
+/// for step in 1..=60 {
+///     peripherals.wait_frame().await;
+///     let progress = Num::<i32, 8>::new(step) / 60;
+///     layer.set_rotation(progress.change_base());
+///     layer.set_scale(progress, progress);
+///
+///     let mut frame = peripherals.display.frame().await;
+///     layer.show(&mut logo, &mut frame);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffineLayer {
+    center: Vector2D<Num<i32, 8>>,
+    screen_pos: Vector2D<i16>,
+    scale: Vector2D<Num<i32, 8>>,
+    rotation: Num<i32, 16>,
+    transform: AffineMatrixBackground,
+}
+
+impl AffineLayer {
+    /// Unrotated, unscaled layer with the background's origin at the middle of the screen
+    pub fn new() -> Self {
+        let mut layer = Self {
+            center: Vector2D::new(Num::new(0), Num::new(0)),
+            screen_pos: Vector2D::new((WIDTH / 2) as i16, (HEIGHT / 2) as i16),
+            scale: Vector2D::new(Num::new(1), Num::new(1)),
+            rotation: Num::new(0),
+            transform: AffineMatrixBackground::default(),
+        };
+        layer.update();
+        layer
+    }
+
+    /// Set the rotation in turns: `1/4` is a quarter turn, `1` a full one
+    ///
+    /// Any angle is accepted and wraps around, so it can simply keep increasing.
+    pub fn set_rotation(&mut self, angle: Num<i32, 16>) {
+        self.rotation = angle;
+        self.update();
+    }
+
+    /// Current rotation in turns, as set
+    pub fn rotation(&self) -> Num<i32, 16> {
+        self.rotation
+    }
+
+    /// Set how large the background is drawn on each axis: `2` doubles its size, `1/2`
+    /// halves it
+    ///
+    /// Negative factors mirror the background along that axis. Factors are limited to at
+    /// least 1/128 either way, the smallest the hardware can show.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either factor is 0.
+    pub fn set_scale(&mut self, sx: Num<i32, 8>, sy: Num<i32, 8>) {
+        assert!(
+            sx != Num::new(0) && sy != Num::new(0),
+            "affine scale must not be zero"
+        );
+        self.scale = Vector2D::new(sx, sy);
+        self.update();
+    }
+
+    /// Current zoom factors, as set
+    pub fn scale(&self) -> Vector2D<Num<i32, 8>> {
+        self.scale
+    }
+
+    /// Set the pivot: the point of the background, in background pixels, that rotation
+    /// and scaling happen around
+    pub fn set_center(&mut self, x: Num<i32, 8>, y: Num<i32, 8>) {
+        self.center = Vector2D::new(x, y);
+        self.update();
+    }
+
+    /// Current pivot in background pixels
+    pub fn center(&self) -> Vector2D<Num<i32, 8>> {
+        self.center
+    }
+
+    /// Set where on the screen the pivot is drawn, the middle of the screen by default
+    ///
+    /// Moving this pans the background without affecting rotation or zoom.
+    pub fn set_screen_pos(&mut self, x: i16, y: i16) {
+        self.screen_pos = Vector2D::new(x, y);
+        self.update();
+    }
+
+    /// Screen position the pivot is drawn at
+    pub fn screen_pos(&self) -> Vector2D<i16> {
+        self.screen_pos
+    }
+
+    /// The matrix for the current rotation, zoom and pivot
+    pub fn transform(&self) -> AffineMatrixBackground {
+        self.transform
+    }
+
+    /// Write the matrix into `background`
+    pub fn apply(&self, background: &mut AffineBackground) {
+        background.set_transform(self.transform);
+    }
+
+    /// Apply the matrix to `background` and show it on `frame`
+    pub fn show(
+        &self,
+        background: &mut AffineBackground,
+        frame: &mut GraphicsFrame,
+    ) -> AffineBackgroundId {
+        self.apply(background);
+        background.show(frame)
+    }
+
+    /// Rebuild the matrix after a change
+    fn update(&mut self) {
+        // The matrix maps screen pixels to background pixels, so it holds the inverse zoom
+        let inverse = Vector2D::new(inverse_scale(self.scale.x), inverse_scale(self.scale.y));
+        self.transform = AffineMatrixBackground::from_scale_rotation_position(
+            self.center,
+            inverse,
+            self.rotation,
+            self.screen_pos,
+        );
+    }
+}
+
+impl Default for AffineLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `1 / scale`, limited to the range of the hardware's 8.8 matrix entries
+fn inverse_scale(scale: Num<i32, 8>) -> Num<i32, 8> {
+    let raw = (Num::<i32, 8>::new(1) / scale).to_raw();
+    Num::from_raw(raw.clamp(i16::MIN as i32, i16::MAX as i32))
+}
@@ -0,0 +1,156 @@
+//! Frame-driven sprite animation
+//!
+//! Picking an animation frame as `frame_count / rate` works until the counter wraps,
+//! when the animation jumps, and it can't play an animation once and stop. [`Animator`]
+//! keeps its own position in one agb [`Tag`], advanced once per frame, and hands back
+//! the sprite to show.
+
+use agb::display::object::{Sprite, Tag};
+
+/// What an [`Animator`] does after the last sprite of its tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Playback {
+    /// Start over, following the tag's direction (forward, backward or ping-pong)
+    #[default]
+    Loop,
+    /// Stop and hold the last sprite
+    ///
+    /// Plays each of the tag's sprites once in its direction; ping-pong tags stop at
+    /// the end of their forward half.
+    Once,
+}
+
+/// Steps through an aseprite tag at a fixed number of frames per sprite
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # agb::include_aseprite!(mod ship_sprites, "gfx/ship.aseprite");
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>, moving: bool) {
+/// use agb::display::object::Object;
+/// use embassy_agb::display::animation::Animator;
+///
+/// let mut ship = Animator::looping(&ship_sprites::IDLE, 15);
+///
+/// loop {
+///     peripherals.wait_frame().await;
+///
+///     // Switching tags restarts the animation; staying on one keeps it going
+///     if moving {
+///         ship.set_animation(&ship_sprites::FLAME, 8);
+///     } else {
+///         ship.set_animation(&ship_sprites::IDLE, 15);
+///     }
+///
+///     let mut frame = peripherals.display.frame().await;
+///     Object::new(ship.tick()).show(&mut frame);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Animator {
+    tag: &'static Tag,
+    frames_per_step: u32,
+    playback: Playback,
+    /// Frames since the animation started, kept below one full cycle of the tag
+    elapsed: u32,
+}
+
+impl Animator {
+    /// Animate `tag`, showing each sprite for `frames_per_step` frames
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames_per_step` is 0.
+    pub fn new(tag: &'static Tag, frames_per_step: u32, playback: Playback) -> Self {
+        assert!(frames_per_step > 0, "an animation step must last a frame");
+        Self {
+            tag,
+            frames_per_step,
+            playback,
+            elapsed: 0,
+        }
+    }
+
+    /// Animation that repeats forever
+    pub fn looping(tag: &'static Tag, frames_per_step: u32) -> Self {
+        Self::new(tag, frames_per_step, Playback::Loop)
+    }
+
+    /// Animation that plays once and holds its last sprite
+    pub fn once(tag: &'static Tag, frames_per_step: u32) -> Self {
+        Self::new(tag, frames_per_step, Playback::Once)
+    }
+
+    /// Switch to another tag and rate, restarting only if the tag changed
+    ///
+    /// Call it every frame with the animation for the current state: the animation
+    /// carries on while the state stays the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames_per_step` is 0.
+    pub fn set_animation(&mut self, tag: &'static Tag, frames_per_step: u32) {
+        assert!(frames_per_step > 0, "an animation step must last a frame");
+        if !core::ptr::eq(self.tag, tag) {
+            self.tag = tag;
+            self.elapsed = 0;
+        }
+        if frames_per_step != self.frames_per_step {
+            // Stay on the current step at the new rate
+            self.elapsed = self.elapsed / self.frames_per_step * frames_per_step;
+            self.frames_per_step = frames_per_step;
+        }
+    }
+
+    /// Go back to the first sprite
+    pub fn reset(&mut self) {
+        self.elapsed = 0;
+    }
+
+    /// Return the sprite to show this frame and advance to the next frame
+    pub fn tick(&mut self) -> &'static Sprite {
+        let sprite = self.sprite();
+        self.advance(1);
+        sprite
+    }
+
+    /// Advance by `frames` frames, e.g. to catch up on dropped frames
+    pub fn advance(&mut self, frames: u32) {
+        let cycle = self.cycle_frames();
+        self.elapsed = match self.playback {
+            Playback::Loop => ((self.elapsed as u64 + frames as u64) % cycle as u64) as u32,
+            Playback::Once => self.elapsed.saturating_add(frames).min(cycle),
+        };
+    }
+
+    /// The sprite for the current frame
+    pub fn sprite(&self) -> &'static Sprite {
+        let step = (self.elapsed / self.frames_per_step) as usize;
+        match self.playback {
+            Playback::Loop => self.tag.animation_sprite(step),
+            Playback::Once => self
+                .tag
+                .animation_sprite(step.min(self.tag.sprites().len() - 1)),
+        }
+    }
+
+    /// Whether a [`Playback::Once`] animation has reached its last sprite
+    ///
+    /// Always false for looping animations.
+    pub fn is_finished(&self) -> bool {
+        self.playback == Playback::Once
+            && self.elapsed / self.frames_per_step >= self.tag.sprites().len() as u32 - 1
+    }
+
+    /// Frames after which the animation is back where it started
+    ///
+    /// A multiple of both the forward/backward cycle (`len` steps) and the ping-pong
+    /// cycle (`2 * (len - 1)` steps), since agb doesn't say which one the tag uses.
+    fn cycle_frames(&self) -> u32 {
+        let len = self.tag.sprites().len() as u32;
+        let steps = len * (2 * (len - 1)).max(1);
+        steps.saturating_mul(self.frames_per_step)
+    }
+}
@@ -0,0 +1,199 @@
+//! Alpha blending between layers
+//!
+//! The GBA can mix two sets of layers pixel by pixel: each pixel of a "top" layer is
+//! weighted by `eva / 16` and added to the pixel of the "bottom" layer right below it,
+//! weighted by `evb / 16`. [`Blend`] describes that effect and applies it to agb's
+//! per-frame blend state when shown, and [`Blend::fade_alpha()`] animates it, e.g. to
+//! make a ghost enemy fade in or to dim the game behind a menu.
+//!
+//! ## Supported combinations
+//! - Any of the four backgrounds, objects and the backdrop color can be top or bottom
+//!   targets, and a layer can be both.
+//! - Pixels only mix when a top target pixel is drawn directly over a bottom target
+//!   pixel. Where a top target lies over a layer that isn't a bottom target, it is drawn
+//!   as normal.
+//! - Objects only blend with their agb `GraphicsMode` set to `AlphaBlending`.
+//! - There is one blend effect per frame for the whole screen, shared with the
+//!   brightness fades of [`AsyncDisplay::fade_out()`](super::AsyncDisplay::fade_out).
+//!   With [windows](super::window), blending only happens in windows that enable it.
+//! - `eva + evb` above 16 brightens the result, saturating at white.
+
+use core::cell::Cell;
+
+use agb::display::tiled::BackgroundId;
+use agb::display::{GraphicsFrame, Layer};
+use agb::fixnum::Num;
+
+/// Largest blend coefficient, for a layer at full strength
+pub const ALPHA_MAX: u8 = 16;
+
+/// Layers on one side of a [`Blend`]
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Targets {
+    backgrounds: heapless::Vec<BackgroundId, 4>,
+    objects: bool,
+    backdrop: bool,
+}
+
+impl Targets {
+    /// No layers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also blend `background`, as returned by the background's `show()`
+    ///
+    /// The GBA has four backgrounds, so adding more than four does nothing.
+    pub fn with_background(mut self, background: impl Into<BackgroundId>) -> Self {
+        let background = background.into();
+        if !self.backgrounds.contains(&background) {
+            let _ = self.backgrounds.push(background);
+        }
+        self
+    }
+
+    /// Also blend objects, see the [module docs](self)
+    pub fn with_objects(mut self) -> Self {
+        self.objects = true;
+        self
+    }
+
+    /// Also blend the backdrop color (palette color 0, shown where nothing else is drawn)
+    pub fn with_backdrop(mut self) -> Self {
+        self.backdrop = true;
+        self
+    }
+}
+
+/// Alpha blend between a top and a bottom set of layers
+///
+/// The coefficients can be changed through a shared reference, so a fade can run in
+/// one future while the render loop keeps showing the blend in another.
+///
+/// # Example
+///
+/// A ghost that fades in over half a second while the game keeps drawing:
+///
+/// ```rust,no_run
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     mut ghost_layer: agb::display::tiled::RegularBackground,
+/// #     mut level: agb::display::tiled::RegularBackground,
+/// # ) {
+/// use embassy_agb::display::blend::{Blend, Targets};
+/// use embassy_agb::futures::join::join;
+/// use embassy_agb::Duration;
+///
+/// # let mut frame = peripherals.display.frame().await;
+/// # let (ghost_id, level_id) = (ghost_layer.show(&mut frame), level.show(&mut frame));
+/// # drop(frame);
+/// let blend = Blend::alpha(
+///     Targets::new().with_background(ghost_id),
+///     Targets::new().with_background(level_id),
+///     0,
+///     16,
+/// );
+///
+/// let fade = blend.fade_alpha(0, 16, Duration::from_millis(500));
+/// let render = async {
+///     loop {
+///         peripherals.wait_frame().await;
+///         let mut frame = peripherals.display.frame().await;
+///         ghost_layer.show(&mut frame);
+///         level.show(&mut frame);
+///         blend.show(&mut frame);
+///         frame.commit();
+///     }
+/// };
+/// join(fade, render).await;
+/// # }
+/// ```
+pub struct Blend {
+    top: Targets,
+    bottom: Targets,
+    eva: Cell<u8>,
+    evb: Cell<u8>,
+}
+
+impl Blend {
+    /// Blend `top` at `eva / 16` strength over `bottom` at `evb / 16`
+    ///
+    /// Coefficients above 16 are treated as 16. `eva + evb = 16` gives plain
+    /// transparency, e.g. `(8, 8)` for half see-through.
+    pub fn alpha(top: Targets, bottom: Targets, eva: u8, evb: u8) -> Self {
+        Self {
+            top,
+            bottom,
+            eva: Cell::new(eva.min(ALPHA_MAX)),
+            evb: Cell::new(evb.min(ALPHA_MAX)),
+        }
+    }
+
+    /// Current `(eva, evb)` coefficients
+    pub fn coefficients(&self) -> (u8, u8) {
+        (self.eva.get(), self.evb.get())
+    }
+
+    /// Change the coefficients, taking effect the next time the blend is shown
+    pub fn set_alpha(&self, eva: u8, evb: u8) {
+        self.eva.set(eva.min(ALPHA_MAX));
+        self.evb.set(evb.min(ALPHA_MAX));
+    }
+
+    /// Use this blend for `frame`, replacing any other blend effect on it
+    pub fn show(&self, frame: &mut GraphicsFrame) {
+        let (eva, evb) = self.coefficients();
+        let mut effect = frame.blend().alpha(Num::from_raw(eva), Num::from_raw(evb));
+
+        for (layer, targets) in [(Layer::Top, &self.top), (Layer::Bottom, &self.bottom)] {
+            for &background in &targets.backgrounds {
+                effect.enable_background(layer, background);
+            }
+            if targets.objects {
+                effect.enable_object(layer);
+            }
+            if targets.backdrop {
+                effect.enable_backdrop(layer);
+            }
+        }
+    }
+
+    /// Fade the top layers' strength from `from` to `to` (0-16) over `over`
+    ///
+    /// Steps once per VBlank, keeping the bottom layers at `16 - eva` so the mix stays
+    /// a plain cross-fade: `fade_alpha(16, 0, ..)` makes the top layers vanish. Only the
+    /// coefficients change, so the blend still has to be [shown](Self::show) each frame.
+    ///
+    /// Dropping the future mid-fade jumps straight to `to`.
+    #[cfg(feature = "time")]
+    pub async fn fade_alpha(&self, from: u8, to: u8, over: embassy_time::Duration) {
+        let (from, to) = (from.min(ALPHA_MAX) as i32, to.min(ALPHA_MAX) as i32);
+        let frames = (over.as_micros() / super::VBLANK_INTERVAL_US).max(1) as i32;
+
+        // Leaves the end state in place even if this future is dropped part way
+        let _guard = AlphaGuard {
+            blend: self,
+            eva: to as u8,
+        };
+
+        for step in 0..=frames {
+            let eva = (from + (to - from) * step / frames) as u8;
+            super::wait_for_vblank().await;
+            self.set_alpha(eva, ALPHA_MAX - eva);
+        }
+    }
+}
+
+/// Sets a blend's final cross-fade coefficients when dropped
+#[cfg(feature = "time")]
+struct AlphaGuard<'a> {
+    blend: &'a Blend,
+    eva: u8,
+}
+
+#[cfg(feature = "time")]
+impl Drop for AlphaGuard<'_> {
+    fn drop(&mut self) {
+        self.blend.set_alpha(self.eva, ALPHA_MAX - self.eva);
+    }
+}
@@ -0,0 +1,335 @@
+//! Camera scrolling for tiled backgrounds
+//!
+//! [`ScrollLayer`] keeps a camera position in world pixels and writes it into a
+//! background's scroll offset each frame. The camera can roam freely, stay inside the
+//! world's bounds, or wrap around for maps that repeat forever. Sprites are placed with
+//! [`ScrollLayer::to_screen()`] so they move with the background.
+//!
+//! [`ParallaxScroller`] moves several backgrounds at different speeds from one camera
+//! position, so distant scenery drifts slower than the foreground.
+
+use agb::display::tiled::{RegularBackground, RegularBackgroundId};
+use agb::display::{GraphicsFrame, HEIGHT, WIDTH};
+use agb::fixnum::{vec2, Num, Vector2D};
+
+/// Number of backgrounds the GBA can show at once
+const MAX_LAYERS: usize = 4;
+
+/// What happens when the camera reaches the edge of the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edges {
+    /// No edges, the camera goes anywhere
+    Free,
+    /// Keep the whole screen inside a world of this size
+    Clamp(Vector2D<i32>),
+    /// Wrap the camera around a world of this size
+    Wrap(Vector2D<i32>),
+}
+
+/// Camera that scrolls a background
+///
+/// # Example
+///
+/// A platformer camera that follows the player, only scrolling once they leave a box
+/// in the middle of the screen:
+///
+/// ```rust,no_run
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     mut level: agb::display::tiled::RegularBackground,
+/// # ) {
+/// use embassy_agb::display::scroll::ScrollLayer;
+///
+/// // 512x256 pixel level
+/// let mut camera = ScrollLayer::with_bounds(512, 256);
+/// # let (player_x, player_y) = (0, 0);
+///
+/// loop {
+///     peripherals.wait_frame().await;
+///     camera.follow(player_x, player_y, (64, 48));
+///
+///     let mut frame = peripherals.display.frame().await;
+///     camera.show(&mut level, &mut frame);
+///     let player_on_screen = camera.to_screen(player_x, player_y);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollLayer {
+    camera: Vector2D<i32>,
+    edges: Edges,
+}
+
+impl ScrollLayer {
+    /// Camera at (0, 0) that can move anywhere
+    pub fn new() -> Self {
+        Self {
+            camera: vec2(0, 0),
+            edges: Edges::Free,
+        }
+    }
+
+    /// Camera that never shows anything outside a `width` x `height` pixel world
+    ///
+    /// In a world smaller than the screen the camera stays at 0 on that axis.
+    pub fn with_bounds(width: i32, height: i32) -> Self {
+        Self {
+            camera: vec2(0, 0),
+            edges: Edges::Clamp(vec2(width, height)),
+        }
+    }
+
+    /// Camera that wraps around a `width` x `height` pixel world, for infinite maps
+    ///
+    /// Use the background's own size (e.g. 256x256 for 32x32 tiles) to repeat it
+    /// forever. The camera always stays within the world, so it never overflows
+    /// however far the player travels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` isn't positive.
+    pub fn with_wrap(width: i32, height: i32) -> Self {
+        assert!(width > 0 && height > 0, "wrapping world must have a size");
+        Self {
+            camera: vec2(0, 0),
+            edges: Edges::Wrap(vec2(width, height)),
+        }
+    }
+
+    /// World position of the screen's top-left corner
+    pub fn camera(&self) -> Vector2D<i32> {
+        self.camera
+    }
+
+    /// Move the screen's top-left corner to (`x`, `y`) in the world
+    pub fn set_camera(&mut self, x: i32, y: i32) {
+        self.camera = self.fit(vec2(x, y));
+    }
+
+    /// Move the camera by (`dx`, `dy`) pixels
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        self.camera = self.fit(self.camera + vec2(dx, dy));
+    }
+
+    /// Follow a target, scrolling only when it leaves a dead zone around the screen center
+    ///
+    /// `deadzone` is the width and height of the box, in pixels, that the target can move
+    /// around in without the camera moving. Use `(0, 0)` to keep the target centered.
+    pub fn follow(&mut self, target_x: i32, target_y: i32, deadzone: impl Into<Vector2D<i32>>) {
+        let deadzone = deadzone.into();
+        let on_screen = self.to_screen(target_x, target_y);
+
+        let left = (WIDTH - deadzone.x) / 2;
+        let top = (HEIGHT - deadzone.y) / 2;
+        let right = left + deadzone.x;
+        let bottom = top + deadzone.y;
+
+        let mut delta = vec2(0, 0);
+        if on_screen.x < left {
+            delta.x = on_screen.x - left;
+        } else if on_screen.x > right {
+            delta.x = on_screen.x - right;
+        }
+        if on_screen.y < top {
+            delta.y = on_screen.y - top;
+        } else if on_screen.y > bottom {
+            delta.y = on_screen.y - bottom;
+        }
+
+        self.camera = self.fit(self.camera + delta);
+    }
+
+    /// Screen position of a world position, e.g. to place a sprite
+    ///
+    /// In a wrapping world this picks the nearest copy of the position, so objects just
+    /// past the wrap point still show up next to the camera.
+    pub fn to_screen(&self, world_x: i32, world_y: i32) -> Vector2D<i32> {
+        let offset = vec2(world_x, world_y) - self.camera;
+        match self.edges {
+            Edges::Wrap(size) => vec2(
+                (offset.x + size.x / 2).rem_euclid(size.x) - size.x / 2,
+                (offset.y + size.y / 2).rem_euclid(size.y) - size.y / 2,
+            ),
+            _ => offset,
+        }
+    }
+
+    /// Write the camera into `background`'s scroll offset
+    pub fn apply(&self, background: &mut RegularBackground) {
+        background.set_scroll_pos(self.camera);
+    }
+
+    /// Scroll `background` to the camera and show it on `frame`
+    pub fn show(
+        &self,
+        background: &mut RegularBackground,
+        frame: &mut GraphicsFrame,
+    ) -> RegularBackgroundId {
+        self.apply(background);
+        background.show(frame)
+    }
+
+    /// Bring a camera position back inside the world's edges
+    fn fit(&self, camera: Vector2D<i32>) -> Vector2D<i32> {
+        match self.edges {
+            Edges::Free => camera,
+            Edges::Clamp(size) => vec2(
+                camera.x.clamp(0, (size.x - WIDTH).max(0)),
+                camera.y.clamp(0, (size.y - HEIGHT).max(0)),
+            ),
+            Edges::Wrap(size) => vec2(camera.x.rem_euclid(size.x), camera.y.rem_euclid(size.y)),
+        }
+    }
+}
+
+impl Default for ScrollLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One background of a [`ParallaxScroller`]
+struct ParallaxLayer {
+    background: RegularBackground,
+    speed: Num<i32, 8>,
+}
+
+/// Backgrounds that scroll horizontally at different speeds from one camera
+///
+/// Each layer moves by its speed times the camera's movement: `1` for the layer the
+/// game takes place on, below `1` for distant scenery, `0` for a fixed sky and above `1`
+/// for foreground objects passing in front. Speeds are fixed point, so a layer at
+/// `0.25` moves a pixel for every four the camera moves rather than in jumps.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     mountains: agb::display::tiled::RegularBackground,
+/// #     level: agb::display::tiled::RegularBackground,
+/// # ) {
+/// use agb::fixnum::num;
+/// use embassy_agb::display::scroll::{ParallaxScroller, ScrollLayer};
+///
+/// let mut camera = ScrollLayer::with_bounds(1024, 160);
+/// let mut parallax = ParallaxScroller::new();
+/// parallax.add_layer(mountains, num!(0.25));
+/// parallax.add_layer(level, num!(1));
+/// # let player_x = 0;
+///
+/// loop {
+///     peripherals.wait_frame().await;
+///     camera.follow(player_x, 80, (64, 160));
+///     parallax.set_camera(camera.camera().x);
+///
+///     let mut frame = peripherals.display.frame().await;
+///     parallax.show(&mut frame);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+pub struct ParallaxScroller {
+    layers: heapless::Vec<ParallaxLayer, MAX_LAYERS>,
+    camera: Vector2D<i32>,
+}
+
+impl ParallaxScroller {
+    /// Scroller with no layers and the camera at (0, 0)
+    pub fn new() -> Self {
+        Self {
+            layers: heapless::Vec::new(),
+            camera: vec2(0, 0),
+        }
+    }
+
+    /// Add a background that scrolls at `speed` times the camera's speed, returning its
+    /// index
+    ///
+    /// Layers are shown in the order they were added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scroller already has four layers, the most the GBA can show.
+    pub fn add_layer(&mut self, background: RegularBackground, speed: Num<i32, 8>) -> usize {
+        if self
+            .layers
+            .push(ParallaxLayer { background, speed })
+            .is_err()
+        {
+            panic!("ParallaxScroller can hold at most {MAX_LAYERS} layers");
+        }
+        self.layers.len() - 1
+    }
+
+    /// Number of layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether there are no layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Background of layer `index`, e.g. to change its tiles
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut RegularBackground> {
+        self.layers
+            .get_mut(index)
+            .map(|layer| &mut layer.background)
+    }
+
+    /// Change the speed of layer `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_speed(&mut self, index: usize, speed: Num<i32, 8>) {
+        self.layers[index].speed = speed;
+    }
+
+    /// Move the camera to `x` pixels across the world
+    pub fn set_camera(&mut self, x: i32) {
+        self.camera.x = x;
+    }
+
+    /// Move the camera to `y` pixels down the world
+    ///
+    /// Vertical scrolling is the same for every layer, whatever its speed.
+    pub fn set_camera_y(&mut self, y: i32) {
+        self.camera.y = y;
+    }
+
+    /// Camera position, as set
+    pub fn camera(&self) -> Vector2D<i32> {
+        self.camera
+    }
+
+    /// Write each layer's offset into its background
+    pub fn apply(&mut self) {
+        let camera = self.camera;
+        for layer in &mut self.layers {
+            let x = (layer.speed * camera.x).floor();
+            layer.background.set_scroll_pos(vec2(x, camera.y));
+        }
+    }
+
+    /// Scroll every layer and show them on `frame`, returning their IDs in layer order
+    pub fn show(
+        &mut self,
+        frame: &mut GraphicsFrame,
+    ) -> heapless::Vec<RegularBackgroundId, MAX_LAYERS> {
+        self.apply();
+        self.layers
+            .iter()
+            .map(|layer| layer.background.show(frame))
+            .collect()
+    }
+}
+
+impl Default for ParallaxScroller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
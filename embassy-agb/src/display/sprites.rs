@@ -0,0 +1,167 @@
+//! Reusable sprite objects
+//!
+//! Creating an [`Object`] looks its sprite up in VRAM (loading it on first use), so
+//! building every object from scratch each frame adds up in scenes with many sprites.
+//! [`SpritePool`] creates a fixed set of objects once and keeps them across frames:
+//! each frame only positions change, and a sprite is only swapped when it differs from
+//! the one already set.
+//!
+//! ## OAM limit
+//! The GBA shows at most [`MAX_OBJECTS`] (128) objects per frame. agb silently drops any
+//! object shown past that limit, so a pool can't be larger than 128, and objects shown
+//! outside the pool in the same frame count towards the same limit.
+
+use alloc::vec::Vec;
+
+use agb::display::object::{Object, Sprite};
+use agb::display::GraphicsFrame;
+use agb::fixnum::Vector2D;
+
+/// Number of objects the GBA can show in one frame
+pub const MAX_OBJECTS: usize = 128;
+
+/// One pooled object
+struct Slot {
+    object: Object,
+    sprite: &'static Sprite,
+    visible: bool,
+}
+
+/// Fixed set of objects reused across frames by index
+///
+/// All objects start hidden. Set up the ones needed this frame, then call
+/// [`show()`](Self::show) to add the visible ones to the frame in index order, which
+/// is also their drawing order among objects of the same priority.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use agb::display::object::Sprite;
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     coin: &'static Sprite,
+/// #     coins: &[(i32, i32)],
+/// # ) {
+/// use embassy_agb::display::sprites::SpritePool;
+///
+/// let mut pool = SpritePool::new(16, coin);
+///
+/// loop {
+///     peripherals.wait_frame().await;
+///
+///     pool.hide_all();
+///     for (index, &(x, y)) in coins.iter().enumerate() {
+///         pool.set_pos(index, (x, y));
+///     }
+///
+///     let mut frame = peripherals.display.frame().await;
+///     pool.show(&mut frame);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+pub struct SpritePool {
+    slots: Vec<Slot>,
+}
+
+impl SpritePool {
+    /// Create `count` hidden objects, all using `sprite`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is more than [`MAX_OBJECTS`].
+    pub fn new(count: usize, sprite: &'static Sprite) -> Self {
+        assert!(
+            count <= MAX_OBJECTS,
+            "SpritePool can hold at most {MAX_OBJECTS} objects, got {count}"
+        );
+
+        let mut slots = Vec::with_capacity(count);
+        slots.resize_with(count, || Slot {
+            object: Object::new(sprite),
+            sprite,
+            visible: false,
+        });
+
+        Self { slots }
+    }
+
+    /// Number of objects in the pool
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Whether the pool has no objects
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Number of objects that will be shown
+    pub fn visible_count(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.visible).count()
+    }
+
+    /// Move object `index` and make it visible
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_pos(&mut self, index: usize, pos: impl Into<Vector2D<i32>>) -> &mut Object {
+        let slot = &mut self.slots[index];
+        slot.visible = true;
+        slot.object.set_pos(pos)
+    }
+
+    /// Change the sprite of object `index` and make it visible
+    ///
+    /// Does nothing to the object if it already uses `sprite`, so calling this every
+    /// frame with the current animation frame is cheap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_sprite(&mut self, index: usize, sprite: &'static Sprite) -> &mut Object {
+        let slot = &mut self.slots[index];
+        slot.visible = true;
+        if !core::ptr::eq(slot.sprite, sprite) {
+            slot.sprite = sprite;
+            slot.object.set_sprite(sprite);
+        }
+        &mut slot.object
+    }
+
+    /// Object `index`, e.g. to flip it or change its priority
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Object> {
+        self.slots.get_mut(index).map(|slot| &mut slot.object)
+    }
+
+    /// Show or hide object `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_visible(&mut self, index: usize, visible: bool) {
+        self.slots[index].visible = visible;
+    }
+
+    /// Whether object `index` will be shown, false if it's out of range
+    pub fn is_visible(&self, index: usize) -> bool {
+        self.slots.get(index).is_some_and(|slot| slot.visible)
+    }
+
+    /// Hide every object, e.g. at the start of a frame before setting up the visible ones
+    pub fn hide_all(&mut self) {
+        for slot in &mut self.slots {
+            slot.visible = false;
+        }
+    }
+
+    /// Add the visible objects to `frame`
+    ///
+    /// Objects past the frame's 128th are dropped by agb, see the [module docs](self).
+    pub fn show(&self, frame: &mut GraphicsFrame) {
+        for slot in self.slots.iter().filter(|slot| slot.visible) {
+            slot.object.show(frame);
+        }
+    }
+}
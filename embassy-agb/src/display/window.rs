@@ -0,0 +1,220 @@
+//! Hardware windows for clipping layers to parts of the screen
+//!
+//! A window is a region of the screen with its own set of visible layers: inside it,
+//! only the backgrounds (and optionally objects and blending) it enables are drawn.
+//! [`Window`] describes one such region, as a rectangle or a circle, and writes it
+//! into agb's per-frame window state when shown, e.g. for a spotlight reveal in a
+//! cutscene or to keep the playfield from drawing under the HUD.
+//!
+//! ## Limits
+//! The GBA has two regular windows, [`WinIn::Win0`] and [`WinIn::Win1`], so at most two
+//! [`Window`]s can be shown per frame. Where they overlap, only `Win0`'s layers apply;
+//! settings are never combined. Outside every window, only the layers given to
+//! [`show_outside()`] are drawn, and if it isn't called nothing but the backdrop
+//! color shows there.
+//!
+//! Circles are drawn by changing the window's edges on every scanline with an HBlank
+//! DMA on channel 0, so only one circular window can be shown per frame and no other
+//! HBlank DMA can run alongside it.
+
+use alloc::vec::Vec;
+
+use agb::display::tiled::BackgroundId;
+use agb::display::{GraphicsFrame, WinIn, HEIGHT, WIDTH};
+use agb::dma::HBlankDma;
+use agb::fixnum::{vec2, Rect, Vector2D};
+
+/// Largest circle radius, small enough that squaring it can't overflow
+const MAX_RADIUS: i32 = 1024;
+
+/// What is drawn inside a window, or outside all of them
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn example(frame: &mut agb::display::GraphicsFrame, level: agb::display::tiled::RegularBackgroundId) {
+/// use embassy_agb::display::window::Layers;
+///
+/// let layers = Layers::new().with_background(level).with_objects();
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Layers {
+    backgrounds: heapless::Vec<BackgroundId, 4>,
+    objects: bool,
+    blending: bool,
+}
+
+impl Layers {
+    /// No layers at all, only the backdrop color
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also draw `background`, as returned by the background's `show()`
+    ///
+    /// The GBA has four backgrounds, so adding more than four does nothing.
+    pub fn with_background(mut self, background: impl Into<BackgroundId>) -> Self {
+        let background = background.into();
+        if !self.backgrounds.contains(&background) {
+            let _ = self.backgrounds.push(background);
+        }
+        self
+    }
+
+    /// Also draw objects (sprites)
+    pub fn with_objects(mut self) -> Self {
+        self.objects = true;
+        self
+    }
+
+    /// Also apply the frame's blend effect, e.g. a fade or transparency
+    pub fn with_blending(mut self) -> Self {
+        self.blending = true;
+        self
+    }
+}
+
+/// Shape of a [`Window`], in screen pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Rect(Rect<i32>),
+    Circle { center: Vector2D<i32>, radius: i32 },
+}
+
+/// A clipping region and the layers drawn inside it
+///
+/// # Example
+///
+/// A spotlight that closes in on the player at the end of a cutscene:
+///
+/// ```rust,no_run
+/// # async fn example(
+/// #     mut peripherals: embassy_agb::GbaPeripherals<'_>,
+/// #     mut scene: agb::display::tiled::RegularBackground,
+/// # ) {
+/// use agb::display::WinIn;
+/// use embassy_agb::display::window::{Layers, Window};
+///
+/// # let (player_x, player_y) = (120, 80);
+/// for radius in (0..=160).rev().step_by(4) {
+///     peripherals.wait_frame().await;
+///
+///     let mut frame = peripherals.display.frame().await;
+///     let scene_id = scene.show(&mut frame);
+///     Window::circle(player_x, player_y, radius)
+///         .enable_inside(Layers::new().with_background(scene_id).with_objects())
+///         .show(&mut frame, WinIn::Win0);
+///     frame.commit();
+/// }
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Window {
+    shape: Shape,
+    inside: Layers,
+}
+
+impl Window {
+    /// Rectangular window with its top-left corner at (`x`, `y`)
+    ///
+    /// Parts off the screen are cut off.
+    pub fn rect(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            shape: Shape::Rect(Rect::new(vec2(x, y), vec2(width, height))),
+            inside: Layers::new(),
+        }
+    }
+
+    /// Circular window centered on (`x`, `y`)
+    ///
+    /// Radii above 1024 are treated as 1024, which covers the screen from anywhere near
+    /// it. See the [module docs](self) for the HBlank DMA this uses.
+    pub fn circle(x: i32, y: i32, radius: i32) -> Self {
+        Self {
+            shape: Shape::Circle {
+                center: vec2(x, y),
+                radius: radius.clamp(0, MAX_RADIUS),
+            },
+            inside: Layers::new(),
+        }
+    }
+
+    /// Set the layers drawn inside the window, replacing any set before
+    ///
+    /// With no layers, the inside only shows the backdrop color, which masks out
+    /// everything behind it.
+    pub fn enable_inside(&mut self, layers: Layers) -> &mut Self {
+        self.inside = layers;
+        self
+    }
+
+    /// Turn on the window in `slot` for this frame
+    pub fn show(&self, frame: &mut GraphicsFrame, slot: WinIn) {
+        let bounds = match self.shape {
+            Shape::Rect(rect) => rect,
+            Shape::Circle { center, radius } => {
+                Rect::new(vec2(0, center.y - radius), vec2(WIDTH, 2 * radius + 1))
+            }
+        };
+        let bounds = clip_to_screen(bounds);
+
+        let window = frame.windows().win_in(slot);
+        window.set_pos(bounds);
+        for &background in &self.inside.backgrounds {
+            window.enable_background(background);
+        }
+        if self.inside.objects {
+            window.enable_objects();
+        }
+        if self.inside.blending {
+            window.enable_blending();
+        }
+
+        if let Shape::Circle { center, radius } = self.shape {
+            let spans = circle_spans(center, radius);
+            HBlankDma::new(window.horizontal_pos_dma(), &spans).show(frame);
+        }
+    }
+}
+
+/// Draw `layers` everywhere outside the shown windows for this frame
+pub fn show_outside(frame: &mut GraphicsFrame, layers: &Layers) {
+    let outside = frame.windows().win_out();
+    for &background in &layers.backgrounds {
+        outside.enable_background(background);
+    }
+    if layers.objects {
+        outside.enable_objects();
+    }
+    if layers.blending {
+        outside.enable_blending();
+    }
+}
+
+/// `rect` with the parts off the screen removed, empty if it is entirely off screen
+fn clip_to_screen(rect: Rect<i32>) -> Rect<i32> {
+    let left = rect.position.x.clamp(0, WIDTH);
+    let top = rect.position.y.clamp(0, HEIGHT);
+    let right = (rect.position.x + rect.size.x).clamp(left, WIDTH);
+    let bottom = (rect.position.y + rect.size.y).clamp(top, HEIGHT);
+    Rect::new(vec2(left, top), vec2(right - left, bottom - top))
+}
+
+/// Left and right edge of a circle on each scanline, in the layout of the window's
+/// horizontal position register: `x` is the right edge (exclusive), `y` the left edge
+fn circle_spans(center: Vector2D<i32>, radius: i32) -> Vec<Vector2D<u8>> {
+    (0..HEIGHT)
+        .map(|line| {
+            let dy = line - center.y;
+            if dy.abs() > radius {
+                return vec2(0, 0);
+            }
+
+            let half_width = (radius * radius - dy * dy).isqrt();
+            let left = (center.x - half_width).clamp(0, WIDTH);
+            let right = (center.x + half_width + 1).clamp(left, WIDTH);
+            vec2(right as u8, left as u8)
+        })
+        .collect()
+}
@@ -3,11 +3,108 @@
 //! Uses `HALTCNT` (0x4000301) to enter Halt mode when idle, waking on interrupts.
 //! - Halt (bit 7=0): CPU pauses until interrupt, hardware continues
 //! - Stop (bit 7=1): Everything pauses (not used by executor)
+//!
+//! [`InterruptExecutor`] runs a second executor from an interrupt handler, so its tasks
+//! preempt the main [`Executor`].
 
+use core::cell::{Cell, UnsafeCell};
 use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 
+use agb::interrupt::{add_interrupt_handler, Interrupt};
+use critical_section::Mutex;
 use embassy_executor::raw;
-pub use embassy_executor::Spawner;
+pub use embassy_executor::{SendSpawner, SpawnError, SpawnToken, Spawner};
+#[cfg(feature = "time")]
+use portable_atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// Length of the window [`cpu_load()`] is measured over, in embassy ticks
+#[cfg(feature = "time")]
+const LOAD_WINDOW_TICKS: u64 = embassy_time::TICK_HZ;
+
+/// Start of the current load window, in embassy ticks
+#[cfg(feature = "time")]
+static LOAD_WINDOW_START: AtomicU64 = AtomicU64::new(0);
+
+/// Ticks spent idle so far in the current load window
+#[cfg(feature = "time")]
+static LOAD_IDLE_TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Busy percentage over the last complete window
+#[cfg(feature = "time")]
+static CPU_LOAD: AtomicU8 = AtomicU8::new(0);
+
+/// Percentage of time the main executor spent busy over the last second
+///
+/// The executor timestamps each idle period (the Halt in [`Executor::run()`] or the
+/// hook in [`Executor::run_with_idle()`]) and everything else counts as busy: polling
+/// tasks and servicing interrupts. Updated once per second, and 0 until the first
+/// second has passed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// if embassy_agb::cpu_load() > 90 {
+///     // Close to dropping frames
+/// }
+/// ```
+#[cfg(feature = "time")]
+pub fn cpu_load() -> u8 {
+    CPU_LOAD.load(Ordering::Relaxed)
+}
+
+/// Spawn up to `count` instances of a task, passing each its index
+///
+/// Meant for tasks declared with `#[embassy_agb::task(pool_size = N)]`, which reserves
+/// static RAM for `N` concurrent instances at compile time. Returns how many instances
+/// were spawned; fewer than `count` means the pool was full.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::Spawner;
+///
+/// const ENEMIES: usize = 4;
+///
+/// #[embassy_agb::task(pool_size = ENEMIES)]
+/// async fn enemy_ai(index: usize) {
+///     // Each instance drives the enemy in slot `index`
+/// }
+///
+/// # fn example(spawner: Spawner) {
+/// let spawned = embassy_agb::spawn_n(&spawner, ENEMIES, enemy_ai);
+/// assert_eq!(spawned, ENEMIES);
+/// # }
+/// ```
+pub fn spawn_n<S>(
+    spawner: &Spawner,
+    count: usize,
+    mut task: impl FnMut(usize) -> SpawnToken<S>,
+) -> usize {
+    for index in 0..count {
+        if spawner.spawn(task(index)).is_err() {
+            return index;
+        }
+    }
+    count
+}
+
+/// Add an idle period to the load window, closing the window once a second has passed
+#[cfg(feature = "time")]
+fn record_idle(start: embassy_time::Instant, end: embassy_time::Instant) {
+    let idle = LOAD_IDLE_TICKS.load(Ordering::Relaxed) + (end - start).as_ticks();
+    let window_start = LOAD_WINDOW_START.load(Ordering::Relaxed);
+    let window = end.as_ticks().saturating_sub(window_start);
+
+    if window >= LOAD_WINDOW_TICKS {
+        let busy = window.saturating_sub(idle);
+        CPU_LOAD.store((busy * 100 / window) as u8, Ordering::Relaxed);
+        LOAD_WINDOW_START.store(end.as_ticks(), Ordering::Relaxed);
+        LOAD_IDLE_TICKS.store(0, Ordering::Relaxed);
+    } else {
+        LOAD_IDLE_TICKS.store(idle, Ordering::Relaxed);
+    }
+}
 
 /// Embassy executor with automatic Halt mode when idle
 pub struct Executor {
@@ -32,8 +129,46 @@ impl Executor {
 
     /// Run the executor (never returns)
     ///
-    /// Polls tasks continuously, entering Halt mode when idle to save power.
+    /// Polls tasks continuously, entering Halt mode with
+    /// [`wait_for_interrupt()`](crate::wait_for_interrupt) when idle to save power.
     pub fn run(&'static mut self, init: impl FnOnce(Spawner)) -> ! {
+        self.run_with_idle(init, crate::wait_for_interrupt)
+    }
+
+    /// Run the executor with a custom idle hook (never returns)
+    ///
+    /// `idle` is called every time the executor has polled all woken tasks, in place
+    /// of the Halt that [`run()`](Self::run) does. Use it to count idle time, drive a
+    /// profiler or enter a deeper sleep.
+    ///
+    /// The hook runs outside any interrupt handler with interrupts enabled, so a VBlank,
+    /// timer or keypad interrupt can still wake tasks while it runs. After it returns
+    /// the executor polls again; a hook that returns without halting turns the loop into
+    /// a busy-wait. Time spent in the hook counts as idle for [`cpu_load()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::Executor;
+    /// # fn example(executor: &'static mut Executor) -> ! {
+    /// static mut IDLE_LOOPS: u32 = 0;
+    ///
+    /// executor.run_with_idle(
+    ///     |spawner| {
+    ///         // Spawn tasks
+    ///     },
+    ///     || {
+    ///         unsafe { IDLE_LOOPS += 1 };
+    ///         embassy_agb::wait_for_interrupt();
+    ///     },
+    /// )
+    /// # }
+    /// ```
+    pub fn run_with_idle(
+        &'static mut self,
+        init: impl FnOnce(Spawner),
+        mut idle: impl FnMut(),
+    ) -> ! {
         // Initialize time driver if enabled
         #[cfg(feature = "_time-driver")]
         crate::time_driver::init();
@@ -47,8 +182,118 @@ impl Executor {
                 self.inner.poll();
             }
 
-            // Halt until interrupt when idle (power saving)
-            agb::halt();
+            // Nothing left to poll until an interrupt wakes a task
+            #[cfg(feature = "time")]
+            let idle_start = embassy_time::Instant::now();
+
+            idle();
+
+            #[cfg(feature = "time")]
+            record_idle(idle_start, embassy_time::Instant::now());
         }
     }
 }
+
+/// Executor that polls its tasks from an interrupt handler
+///
+/// Tasks spawned on it run inside the chosen interrupt, preempting the main
+/// [`Executor`] loop, which suits time-critical work like audio mixing or input
+/// sampling. Each time the interrupt fires, every task that has been woken is polled.
+///
+/// The GBA can't raise interrupts from software, so a task woken by something other
+/// than its own interrupt (a channel send from the main loop, a timer expiring) only
+/// runs the next time the interrupt fires. With `Interrupt::VBlank` that is up to one
+/// frame later. Keep the tasks short: other interrupts are held off while they run.
+///
+/// The interrupt source must be set up to fire. VBlank and HBlank are enabled
+/// automatically; a timer interrupt needs the timer configured and running.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::agb::interrupt::Interrupt;
+/// use embassy_agb::InterruptExecutor;
+///
+/// static HIGH_PRIORITY: InterruptExecutor = InterruptExecutor::new();
+///
+/// #[embassy_agb::task]
+/// async fn sample_input() {
+///     // Runs at the start of every VBlank, ahead of the main loop
+/// }
+///
+/// # fn example() {
+/// let spawner = HIGH_PRIORITY.start(Interrupt::VBlank);
+/// spawner.must_spawn(sample_input());
+/// # }
+/// ```
+pub struct InterruptExecutor {
+    started: Mutex<Cell<bool>>,
+    executor: UnsafeCell<MaybeUninit<raw::Executor>>,
+}
+
+// SAFETY: the inner executor is only written once in `start()`, guarded by `started`,
+// and is only polled from the one interrupt handler it installs.
+unsafe impl Send for InterruptExecutor {}
+unsafe impl Sync for InterruptExecutor {}
+
+impl Default for InterruptExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InterruptExecutor {
+    /// Create a new, not yet started executor
+    pub const fn new() -> Self {
+        Self {
+            started: Mutex::new(Cell::new(false)),
+            executor: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Start polling this executor from `interrupt`
+    ///
+    /// Installs a handler for `interrupt` that stays installed for the rest of the
+    /// program, and returns a [`SendSpawner`] for spawning tasks onto it. A task running
+    /// in it can get a plain [`Spawner`] with [`Spawner::for_current_executor()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the executor was already started.
+    pub fn start(&'static self, interrupt: Interrupt) -> SendSpawner {
+        if critical_section::with(|cs| self.started.borrow(cs).replace(true)) {
+            panic!("InterruptExecutor::start() called multiple times on the same executor");
+        }
+
+        unsafe {
+            (*self.executor.get())
+                .as_mut_ptr()
+                .write(raw::Executor::new(core::ptr::null_mut()))
+        }
+
+        let handler = unsafe {
+            add_interrupt_handler(interrupt, move |_| {
+                // SAFETY: initialized above, before the handler was installed
+                let executor = (*self.executor.get()).assume_init_ref();
+                executor.poll();
+            })
+        };
+        core::mem::forget(handler);
+
+        self.spawner()
+    }
+
+    /// Get a [`SendSpawner`] for this executor
+    ///
+    /// # Panics
+    ///
+    /// Panics if the executor hasn't been started.
+    pub fn spawner(&'static self) -> SendSpawner {
+        if !critical_section::with(|cs| self.started.borrow(cs).get()) {
+            panic!("InterruptExecutor::spawner() called before start()");
+        }
+
+        let executor = unsafe { (*self.executor.get()).assume_init_ref() };
+        executor.spawner().make_send()
+    }
+}
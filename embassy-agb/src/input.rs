@@ -11,10 +11,20 @@
 //!    - Reason: Only wake futures waiting for specific buttons that changed
 //!    - Embassy pattern: Targeted waking, not broadcast waking
 //!
-//! 3. **Configurable latency**: Poll rate can be adjusted from 30Hz to 120Hz
+//! 3. **Configurable latency**: Presets from 30Hz to 120Hz, or any rate or interval
 //!    - Higher rates: Lower latency but more CPU usage
 //!    - Lower rates: Higher latency but better power efficiency
 //!    - Note: Button presses may not register until next poll cycle
+//!
+//! 4. **Optional keypad interrupt**: [`AsyncInput::with_interrupt()`] adds KEYCNT
+//!    - Reason: Presses of the chosen buttons wake waiting futures immediately, even
+//!      from Halt, instead of on the next poll
+//!    - Limitation: The hardware only signals presses, so releases still need polling
+//!
+//! ## Registers
+//! - `KEYINPUT` (0x4000130): current button state, 0 = pressed
+//! - `KEYCNT` (0x4000132): bits 0-9 select interrupt buttons, bit 14 enables the IRQ,
+//!   bit 15 picks AND (all selected held) over OR (any selected held)
 
 use core::future::Future;
 use core::pin::Pin;
@@ -22,33 +32,160 @@ use core::task::{Context, Poll};
 use portable_atomic::Ordering;
 
 use agb::input::{Button, ButtonController, Tri};
+use agb::interrupt::{add_interrupt_handler, Interrupt, InterruptHandler};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::pubsub::{PubSubChannel, Subscriber};
 use embassy_sync::waitqueue::AtomicWaker;
 
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use embassy_sync::blocking_mutex::Mutex;
+
 #[cfg(feature = "time")]
 use embassy_time;
 
 #[cfg(feature = "executor")]
 use embassy_executor;
 
-/// Keypad input register (KEYINPUT) at 0x04000130  
+/// Keypad input register (KEYINPUT) at 0x04000130
 const KEYPAD_INPUT: *mut u16 = 0x04000130 as *mut u16;
 
+/// Bits of KEYINPUT that correspond to buttons
+const KEYPAD_MASK: u16 = 0x03FF;
+
+/// Keypad interrupt control register (KEYCNT)
+const KEYPAD_CONTROL: *mut u16 = 0x0400_0132 as *mut u16;
+
+/// KEYCNT bit enabling the keypad interrupt
+const KEYCNT_IRQ_ENABLE: u16 = 1 << 14;
+
 const BUTTON_COUNT: usize = 10;
+
+/// All buttons, in the same order as their waker index
+const ALL_BUTTONS: [Button; BUTTON_COUNT] = [
+    Button::A,
+    Button::B,
+    Button::SELECT,
+    Button::START,
+    Button::RIGHT,
+    Button::LEFT,
+    Button::UP,
+    Button::DOWN,
+    Button::R,
+    Button::L,
+];
+
 /// Per-button wakers - following Embassy's pattern
 static BUTTON_WAKERS: [AtomicWaker; BUTTON_COUNT] = [const { AtomicWaker::new() }; BUTTON_COUNT];
 
 /// Global button state for timer-based monitoring
 static GLOBAL_BUTTON_STATE: portable_atomic::AtomicU16 = portable_atomic::AtomicU16::new(0);
 
-/// Whether the input polling task is running
-static POLLING_TASK_RUNNING: portable_atomic::AtomicBool = portable_atomic::AtomicBool::new(false);
+/// Buttons with an unread press or auto-repeat tick since the last `update()`
+#[cfg(all(feature = "time", feature = "executor"))]
+static REPEAT_PENDING: portable_atomic::AtomicU16 = portable_atomic::AtomicU16::new(0);
+
+/// Auto-repeat timing of the polling task and the deadline of each held button
+#[cfg(all(feature = "time", feature = "executor"))]
+static REPEATS: Mutex<CriticalSectionRawMutex, RefCell<RepeatState>> =
+    Mutex::new(RefCell::new(RepeatState {
+        delay: None,
+        interval: embassy_time::Duration::from_millis(100),
+        next: [None; BUTTON_COUNT],
+    }));
+
+#[cfg(all(feature = "time", feature = "executor"))]
+struct RepeatState {
+    delay: Option<embassy_time::Duration>,
+    interval: embassy_time::Duration,
+    /// Per-button deadline for the next auto-repeat tick
+    next: [Option<embassy_time::Instant>; BUTTON_COUNT],
+}
+
+/// Instant each button was last seen going down, `None` while released
+#[cfg(all(feature = "time", feature = "executor"))]
+static PRESSED_AT: Mutex<
+    CriticalSectionRawMutex,
+    Cell<[Option<embassy_time::Instant>; BUTTON_COUNT]>,
+> = Mutex::new(Cell::new([None; BUTTON_COUNT]));
+
+/// Instant of the latest button press or release, `None` if there hasn't been one
+#[cfg(feature = "time")]
+static LAST_INPUT_CHANGE: Mutex<CriticalSectionRawMutex, Cell<Option<embassy_time::Instant>>> =
+    Mutex::new(Cell::new(None));
+
+/// VBlank count of each button's latest unconsumed press edge
+static BUFFERED_PRESSES: Mutex<CriticalSectionRawMutex, Cell<[Option<u32>; BUTTON_COUNT]>> =
+    Mutex::new(Cell::new([None; BUTTON_COUNT]));
+
+/// Number of button events buffered for each subscriber
+pub const INPUT_EVENT_CAPACITY: usize = 16;
+
+/// Maximum number of concurrent [`InputEvents`] subscribers
+pub const INPUT_EVENT_SUBSCRIBERS: usize = 4;
+
+/// Button edge events published by the polling task
+static INPUT_EVENTS: PubSubChannel<
+    CriticalSectionRawMutex,
+    InputEvent,
+    INPUT_EVENT_CAPACITY,
+    INPUT_EVENT_SUBSCRIBERS,
+    0,
+> = PubSubChannel::new();
+
+/// New configuration for the polling task, sent by [`AsyncInput::set_config()`]
+#[cfg(all(feature = "time", feature = "executor"))]
+static POLLING_CONFIG: embassy_sync::signal::Signal<CriticalSectionRawMutex, InputConfig> =
+    embassy_sync::signal::Signal::new();
+
+/// New polling rate for the running task, sent by [`crate::enable_input_polling()`]
+///
+/// Kept apart from [`POLLING_CONFIG`] so the rest of the running config is left alone.
+#[cfg(all(feature = "time", feature = "executor"))]
+static POLLING_RATE: embassy_sync::signal::Signal<CriticalSectionRawMutex, PollingRate> =
+    embassy_sync::signal::Signal::new();
+
+/// Buttons that raise the keypad interrupt, 0 when it's off
+static INTERRUPT_BUTTONS: portable_atomic::AtomicU16 = portable_atomic::AtomicU16::new(0);
+
+/// How many [`KeypadInterrupt`]s watch each button, [`INTERRUPT_BUTTONS`] is their union
+static INTERRUPT_USERS: Mutex<CriticalSectionRawMutex, Cell<[u8; BUTTON_COUNT]>> =
+    Mutex::new(Cell::new([0; BUTTON_COUNT]));
+
+/// Tells the polling task to exit, sent by [`crate::disable_input_polling()`]
+#[cfg(all(feature = "time", feature = "executor"))]
+static POLLING_STOP: embassy_sync::signal::Signal<CriticalSectionRawMutex, ()> =
+    embassy_sync::signal::Signal::new();
+
+/// Whether the input polling task is running (or spawned and about to)
+#[cfg(all(feature = "time", feature = "executor"))]
+static POLLING_TASK_ALIVE: portable_atomic::AtomicBool = portable_atomic::AtomicBool::new(false);
+
+/// Whether the global button state has been initialized
+static INPUT_INITIALIZED: portable_atomic::AtomicBool = portable_atomic::AtomicBool::new(false);
+
+/// Whether button state is sourced from an [`InputPlayback`] instead of KEYINPUT
+static PLAYBACK_ACTIVE: portable_atomic::AtomicBool = portable_atomic::AtomicBool::new(false);
+
+/// Button state for the current playback frame
+static PLAYBACK_STATE: portable_atomic::AtomicU16 = portable_atomic::AtomicU16::new(0);
+
+/// Recorded frames being played back, advanced once per [`AsyncInput::update()`]
+static PLAYBACK: Mutex<CriticalSectionRawMutex, RefCell<Option<PlaybackBuffer>>> =
+    Mutex::new(RefCell::new(None));
+
+struct PlaybackBuffer {
+    frames: Vec<u16>,
+    position: usize,
+}
 
 /// Input polling rate options
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum PollingRate {
     /// 30Hz - Lower latency, more power efficient
     Hz30,
     /// 60Hz - Default, matches VBlank rate
+    #[default]
     Hz60,
     /// 90Hz - Higher responsiveness
     Hz90,
@@ -56,10 +193,19 @@ pub enum PollingRate {
     Hz120,
     /// Custom rate in Hz (clamped to 10-240 range)
     Custom(u32),
+    /// Exact time between polls, e.g. 250ms for a menu that only needs to notice presses
+    ///
+    /// Intervals under 1ms are raised to 1ms. [`InputConfig::from_interval()`] checks the
+    /// interval instead.
+    #[cfg(feature = "time")]
+    Interval(embassy_time::Duration),
 }
 
 impl PollingRate {
     /// Get the polling rate as Hz value
+    ///
+    /// For [`Interval`](Self::Interval) this is rounded down, so it is 0 for intervals
+    /// over a second.
     pub fn as_hz(self) -> u32 {
         match self {
             PollingRate::Hz30 => 30,
@@ -67,13 +213,18 @@ impl PollingRate {
             PollingRate::Hz90 => 90,
             PollingRate::Hz120 => 120,
             PollingRate::Custom(hz) => hz.clamp(10, 240),
+            #[cfg(feature = "time")]
+            PollingRate::Interval(_) => (1_000_000 / self.interval().as_micros()) as u32,
         }
     }
-}
 
-impl Default for PollingRate {
-    fn default() -> Self {
-        PollingRate::Hz60
+    /// Time between polls
+    #[cfg(feature = "time")]
+    pub fn interval(self) -> embassy_time::Duration {
+        match self {
+            PollingRate::Interval(interval) => interval.max(embassy_time::Duration::from_millis(1)),
+            rate => embassy_time::Duration::from_micros(1_000_000 / rate.as_hz() as u64),
+        }
     }
 }
 
@@ -82,12 +233,28 @@ impl Default for PollingRate {
 pub struct InputConfig {
     /// Polling rate
     pub poll_rate: PollingRate,
+    /// How long a button must be held before it starts auto-repeating
+    ///
+    /// `None` disables auto-repeat. The initial press is always reported by
+    /// [`AsyncInput::is_repeated()`] regardless of this setting.
+    #[cfg(feature = "time")]
+    pub repeat_delay: Option<embassy_time::Duration>,
+    /// Interval between auto-repeat ticks once `repeat_delay` has elapsed
+    #[cfg(feature = "time")]
+    pub repeat_interval: embassy_time::Duration,
+    /// How many frames a press stays available to [`AsyncInput::consume_buffered()`]
+    ///
+    /// `0` only accepts a press from the current frame.
+    pub buffer_frames: u8,
 }
 
 impl InputConfig {
     /// Create config with specific polling rate
     pub fn new(poll_rate: PollingRate) -> Self {
-        Self { poll_rate }
+        Self {
+            poll_rate,
+            ..Self::default()
+        }
     }
 }
 
@@ -95,13 +262,44 @@ impl Default for InputConfig {
     fn default() -> Self {
         Self {
             poll_rate: PollingRate::default(),
+            #[cfg(feature = "time")]
+            repeat_delay: None,
+            #[cfg(feature = "time")]
+            repeat_interval: embassy_time::Duration::from_millis(100),
+            buffer_frames: 0,
         }
     }
 }
 
+#[cfg(feature = "time")]
+impl InputConfig {
+    /// Create config that polls every `interval`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use embassy_agb::input::InputConfig;
+    /// use embassy_agb::Duration;
+    ///
+    /// // ~15Hz is plenty for a pause menu
+    /// let menu = InputConfig::from_interval(Duration::from_millis(66));
+    /// ```
+    pub fn from_interval(interval: embassy_time::Duration) -> Self {
+        assert!(
+            interval.as_ticks() > 0,
+            "input polling interval must be non-zero"
+        );
+        Self::new(PollingRate::Interval(interval))
+    }
+}
+
 impl From<PollingRate> for InputConfig {
     fn from(poll_rate: PollingRate) -> Self {
-        Self { poll_rate }
+        Self::new(poll_rate)
     }
 }
 
@@ -122,62 +320,360 @@ fn button_to_index(button: Button) -> Option<usize> {
     }
 }
 
-/// Mark that input polling should be enabled
+/// Read the currently pressed buttons as a bitmask
+///
+/// While an [`InputPlayback`] is active this returns the recorded state for the
+/// current frame instead of reading KEYINPUT, so every reader (the polling task,
+/// futures and `AsyncInput` queries) sees the same replayed input.
+fn read_keypad() -> u16 {
+    if PLAYBACK_ACTIVE.load(Ordering::SeqCst) {
+        PLAYBACK_STATE.load(Ordering::SeqCst)
+    } else {
+        read_hardware_keypad()
+    }
+}
+
+/// Read the buttons physically held, ignoring any playback
+fn read_hardware_keypad() -> u16 {
+    !unsafe { KEYPAD_INPUT.read_volatile() } & KEYPAD_MASK
+}
+
+/// Point KEYCNT at the interrupt buttons that aren't held
+///
+/// The keypad interrupt fires again as soon as it's acknowledged while a selected
+/// button is down. Leaving held buttons out stops that, and each one is selected again
+/// once it's seen released.
+fn sync_keypad_interrupt() {
+    let buttons = INTERRUPT_BUTTONS.load(Ordering::SeqCst);
+    if buttons == 0 {
+        return;
+    }
+
+    let armed = buttons & !read_hardware_keypad();
+    unsafe { KEYPAD_CONTROL.write_volatile(armed | KEYCNT_IRQ_ENABLE) };
+}
+
+/// Keypad interrupt installed by [`AsyncInput::enable_interrupt()`]
+///
+/// Every [`AsyncInput`] can hold one, so the buttons are counted per user and KEYCNT is
+/// only cleared once the last one watching any button is dropped.
+struct KeypadInterrupt {
+    buttons: u16,
+    _handler: InterruptHandler,
+}
+
+impl KeypadInterrupt {
+    fn new(buttons: u16) -> Self {
+        update_interrupt_users(buttons, true);
+        let handler = unsafe { add_interrupt_handler(Interrupt::Keypad, |_| poll_input_changes()) };
+        sync_keypad_interrupt();
+
+        Self {
+            buttons,
+            _handler: handler,
+        }
+    }
+}
+
+impl Drop for KeypadInterrupt {
+    fn drop(&mut self) {
+        if update_interrupt_users(self.buttons, false) == 0 {
+            unsafe { KEYPAD_CONTROL.write_volatile(0) };
+        } else {
+            sync_keypad_interrupt();
+        }
+    }
+}
+
+/// Add or remove one user of each of `buttons`, returning the buttons still watched
+fn update_interrupt_users(buttons: u16, add: bool) -> u16 {
+    INTERRUPT_USERS.lock(|users| {
+        let mut counts = users.get();
+        let mut watched = 0u16;
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            let mask = button.bits() as u16;
+            if (buttons & mask) != 0 {
+                counts[i] = if add {
+                    counts[i].saturating_add(1)
+                } else {
+                    counts[i].saturating_sub(1)
+                };
+            }
+            if counts[i] != 0 {
+                watched |= mask;
+            }
+        }
+        users.set(counts);
+        INTERRUPT_BUTTONS.store(watched, Ordering::SeqCst);
+        watched
+    })
+}
+
+/// Step the active playback, if any, to its next recorded frame
+fn advance_playback() {
+    PLAYBACK.lock(|playback| {
+        if let Some(buffer) = playback.borrow_mut().as_mut() {
+            // Past the end of the recording every button reads as released
+            let state = buffer.frames.get(buffer.position).copied().unwrap_or(0);
+            buffer.position = buffer.position.saturating_add(1);
+            PLAYBACK_STATE.store(state, Ordering::SeqCst);
+        }
+    });
+}
+
+/// Initialize the global button state on first use
 fn ensure_input_initialized() {
-    if !POLLING_TASK_RUNNING.swap(true, Ordering::SeqCst) {
+    if !INPUT_INITIALIZED.swap(true, Ordering::SeqCst) {
         // Initialize global state on first call
-        let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+        let current = read_keypad();
         GLOBAL_BUTTON_STATE.store(current, Ordering::SeqCst);
     }
 }
 
 /// Check for button changes and wake appropriate wakers
+///
+/// Runs in the polling task and in the keypad interrupt, so the whole check happens in
+/// a critical section to report each change once.
 fn poll_input_changes() {
-    let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+    critical_section::with(|_| {
+        publish_input_changes();
+        sync_keypad_interrupt();
+    });
+}
+
+/// Wake, buffer and publish the buttons that changed since the last check
+fn publish_input_changes() {
+    let current = read_keypad();
     let previous = GLOBAL_BUTTON_STATE.load(Ordering::SeqCst);
 
     if current != previous {
         // Find which buttons changed and wake only those wakers
         let changed = current ^ previous;
-        let buttons = [
-            Button::A,
-            Button::B,
-            Button::SELECT,
-            Button::START,
-            Button::RIGHT,
-            Button::LEFT,
-            Button::UP,
-            Button::DOWN,
-            Button::R,
-            Button::L,
-        ];
+        let frame = crate::display::vblank_count() as u32;
+        let publisher = INPUT_EVENTS.immediate_publisher();
+        let mut buffered = BUFFERED_PRESSES.lock(|presses| presses.get());
 
-        for (i, button) in buttons.iter().enumerate() {
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
             let button_mask = button.bits() as u16;
             if (changed & button_mask) != 0 {
                 // Only wake the waker for this specific button
                 BUTTON_WAKERS[i].wake();
+
+                let is_pressed = (current & button_mask) != 0;
+                if is_pressed {
+                    buffered[i] = Some(frame);
+                }
+
+                publisher.publish_immediate(InputEvent {
+                    button: *button,
+                    kind: if is_pressed {
+                        ButtonEvent::Pressed
+                    } else {
+                        ButtonEvent::Released
+                    },
+                    frame,
+                });
             }
         }
 
+        BUFFERED_PRESSES.lock(|presses| presses.set(buffered));
+
+        #[cfg(feature = "time")]
+        note_input_change();
+
+        // Both the polling task and the keypad interrupt land here, so neither misses an edge
+        #[cfg(all(feature = "time", feature = "executor"))]
+        {
+            record_press_instants(previous, current);
+            record_repeat_edges(previous, current);
+        }
+
         // Update global state after waking relevant futures
         GLOBAL_BUTTON_STATE.store(current, Ordering::SeqCst);
     }
 }
 
+/// Record that a button was just pressed or released, for [`AsyncInput::idle_for()`]
+#[cfg(feature = "time")]
+fn note_input_change() {
+    let now = embassy_time::Instant::now();
+    LAST_INPUT_CHANGE.lock(|last| last.set(Some(now)));
+}
+
+/// Instant of the latest button change, or boot if there hasn't been one
+#[cfg(feature = "time")]
+fn last_input_change() -> embassy_time::Instant {
+    LAST_INPUT_CHANGE
+        .lock(Cell::get)
+        .unwrap_or(embassy_time::Instant::from_ticks(0))
+}
+
+/// Spawn the polling task, or hand the new config to the one already running
+#[cfg(all(feature = "time", feature = "executor"))]
+pub(crate) fn start_polling(spawner: &embassy_executor::Spawner, rate: PollingRate) {
+    if POLLING_TASK_ALIVE.swap(true, Ordering::SeqCst) {
+        // Still running, maybe with a stop request it hasn't seen yet: cancel that
+        POLLING_STOP.reset();
+        POLLING_RATE.signal(rate);
+    } else if spawner
+        .spawn(input_polling_task(InputConfig::from(rate)))
+        .is_err()
+    {
+        // Spawned directly but not polled yet: it picks the rate up on its first poll
+        POLLING_RATE.signal(rate);
+    }
+}
+
+/// Ask the polling task to exit at its next poll
+#[cfg(all(feature = "time", feature = "executor"))]
+pub(crate) fn stop_polling() {
+    if POLLING_TASK_ALIVE.load(Ordering::SeqCst) {
+        POLLING_STOP.signal(());
+    }
+}
+
+/// Whether the polling task is running and hasn't been asked to stop
+#[cfg(all(feature = "time", feature = "executor"))]
+pub(crate) fn is_polling() -> bool {
+    POLLING_TASK_ALIVE.load(Ordering::SeqCst) && !POLLING_STOP.signaled()
+}
+
 /// Background task that polls input at the configured rate
+///
+/// Prefer [`enable_input_polling`](crate::enable_input_polling), which only spawns it
+/// once. The task exits after [`disable_input_polling`](crate::disable_input_polling).
 #[cfg(all(feature = "time", feature = "executor"))]
 #[embassy_executor::task]
-pub async fn input_polling_task(config: InputConfig) {
-    let poll_interval_ms = 1000 / config.poll_rate.as_hz() as u64;
+pub async fn input_polling_task(mut config: InputConfig) {
+    POLLING_TASK_ALIVE.store(true, Ordering::SeqCst);
+
+    // Only configs sent after the task started apply. Rate changes and stop requests
+    // are cleared on exit instead, so ones sent before its first poll still count.
+    POLLING_CONFIG.reset();
 
     // Initialize global button state
-    let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+    let current = read_keypad();
     GLOBAL_BUTTON_STATE.store(current, Ordering::SeqCst);
 
+    // Buttons already held when polling starts count as pressed now
+    record_press_instants(0, current);
+    set_repeat_timing(&config);
+
     loop {
         poll_input_changes();
-        embassy_time::Timer::after(embassy_time::Duration::from_millis(poll_interval_ms)).await;
+        update_repeats();
+
+        let delay = embassy_time::Timer::after(config.poll_rate.interval());
+        match embassy_futures::select::select4(
+            delay,
+            POLLING_CONFIG.wait(),
+            POLLING_RATE.wait(),
+            POLLING_STOP.wait(),
+        )
+        .await
+        {
+            embassy_futures::select::Either4::First(()) => {}
+            embassy_futures::select::Either4::Second(new_config) => {
+                config = new_config;
+                set_repeat_timing(&config);
+            }
+            embassy_futures::select::Either4::Third(rate) => config.poll_rate = rate,
+            embassy_futures::select::Either4::Fourth(()) => break,
+        }
+    }
+
+    POLLING_RATE.reset();
+    POLLING_STOP.reset();
+
+    // Nothing runs between here and the task finishing, so it can be spawned again
+    POLLING_TASK_ALIVE.store(false, Ordering::SeqCst);
+}
+
+/// Record the press edge instant of each button for [`AsyncInput::held_duration()`]
+#[cfg(all(feature = "time", feature = "executor"))]
+fn record_press_instants(previous: u16, current: u16) {
+    let changed = current ^ previous;
+    if changed == 0 {
+        return;
+    }
+
+    let now = embassy_time::Instant::now();
+    PRESSED_AT.lock(|pressed_at| {
+        let mut instants = pressed_at.get();
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            let mask = button.bits() as u16;
+            if (changed & mask) != 0 {
+                instants[i] = if (current & mask) != 0 {
+                    Some(now)
+                } else {
+                    None
+                };
+            }
+        }
+        pressed_at.set(instants);
+    });
+}
+
+/// Use the auto-repeat timing of the polling task's `config` from now on
+#[cfg(all(feature = "time", feature = "executor"))]
+fn set_repeat_timing(config: &InputConfig) {
+    REPEATS.lock(|repeats| {
+        let mut repeats = repeats.borrow_mut();
+        repeats.delay = config.repeat_delay;
+        repeats.interval = config.repeat_interval;
+    });
+}
+
+/// Record press edges for [`AsyncInput::is_repeated()`]
+///
+/// A press edge always fires and arms the repeat deadline. Releasing a button
+/// clears its deadline, so the delay starts over on the next press.
+#[cfg(all(feature = "time", feature = "executor"))]
+fn record_repeat_edges(previous: u16, current: u16) {
+    let now = embassy_time::Instant::now();
+    let pressed = current & !previous;
+
+    REPEATS.lock(|repeats| {
+        let mut repeats = repeats.borrow_mut();
+        let delay = repeats.delay;
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            let mask = button.bits() as u16;
+            if (current & mask) == 0 {
+                repeats.next[i] = None;
+            } else if (pressed & mask) != 0 {
+                repeats.next[i] = delay.map(|delay| now + delay);
+            }
+        }
+    });
+
+    if pressed != 0 {
+        REPEAT_PENDING.fetch_or(pressed, Ordering::SeqCst);
+    }
+}
+
+/// Fire the auto-repeat ticks of held buttons whose deadline has passed
+#[cfg(all(feature = "time", feature = "executor"))]
+fn update_repeats() {
+    let now = embassy_time::Instant::now();
+    let mut fired = 0u16;
+
+    REPEATS.lock(|repeats| {
+        let mut repeats = repeats.borrow_mut();
+        let interval = repeats.interval;
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            if let Some(at) = repeats.next[i] {
+                if now >= at {
+                    fired |= button.bits() as u16;
+                    // Keep a steady cadence, but don't try to catch up on missed ticks
+                    let next = at + interval;
+                    repeats.next[i] = Some(if next > now { next } else { now + interval });
+                }
+            }
+        }
+    });
+
+    if fired != 0 {
+        REPEAT_PENDING.fetch_or(fired, Ordering::SeqCst);
     }
 }
 
@@ -190,22 +686,178 @@ pub enum ButtonEvent {
     Released,
 }
 
-/// Future that waits for a specific button event
+/// One of the eight D-pad directions, or none
+///
+/// North is up the screen (negative y), matching GBA screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction8 {
+    /// No direction held (or opposing directions cancel out)
+    None,
+    /// Up
+    N,
+    /// Up and right
+    NE,
+    /// Right
+    E,
+    /// Down and right
+    SE,
+    /// Down
+    S,
+    /// Down and left
+    SW,
+    /// Left
+    W,
+    /// Up and left
+    NW,
+}
+
+impl Direction8 {
+    /// Build a direction from a net x/y pair, using only the sign of each axis
+    pub fn from_xy(x: i32, y: i32) -> Self {
+        match (x.signum(), y.signum()) {
+            (0, -1) => Direction8::N,
+            (1, -1) => Direction8::NE,
+            (1, 0) => Direction8::E,
+            (1, 1) => Direction8::SE,
+            (0, 1) => Direction8::S,
+            (-1, 1) => Direction8::SW,
+            (-1, 0) => Direction8::W,
+            (-1, -1) => Direction8::NW,
+            _ => Direction8::None,
+        }
+    }
+
+    /// Get the unit x/y step for this direction, each axis in `-1..=1`
+    pub fn to_xy(self) -> (i32, i32) {
+        match self {
+            Direction8::None => (0, 0),
+            Direction8::N => (0, -1),
+            Direction8::NE => (1, -1),
+            Direction8::E => (1, 0),
+            Direction8::SE => (1, 1),
+            Direction8::S => (0, 1),
+            Direction8::SW => (-1, 1),
+            Direction8::W => (-1, 0),
+            Direction8::NW => (-1, -1),
+        }
+    }
+}
+
+/// Copyable snapshot of the button state for one frame
+///
+/// Produced by [`AsyncInput::snapshot()`]. It borrows nothing, so it can be stored,
+/// compared against an earlier snapshot, or passed into pure game-logic functions.
+/// Each field is a bitmask of [`Button`] bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ButtonSnapshot {
+    /// Buttons that went down since the previous snapshot's frame
+    pub pressed: u16,
+    /// Buttons that went up since the previous snapshot's frame
+    pub released: u16,
+    /// Buttons that are down
+    pub held: u16,
+}
+
+impl ButtonSnapshot {
+    /// Check if a specific button was just pressed
+    pub fn is_pressed(&self, button: Button) -> bool {
+        (self.pressed & button.bits() as u16) != 0
+    }
+
+    /// Check if a specific button was just released
+    pub fn is_released(&self, button: Button) -> bool {
+        (self.released & button.bits() as u16) != 0
+    }
+
+    /// Check if a specific button is held
+    pub fn is_held(&self, button: Button) -> bool {
+        (self.held & button.bits() as u16) != 0
+    }
+
+    /// Check if any button was just pressed
+    pub fn any_pressed(&self) -> bool {
+        self.pressed != 0
+    }
+
+    /// Check if any button was just released
+    pub fn any_released(&self) -> bool {
+        self.released != 0
+    }
+
+    /// Compute the `(pressed, released)` bitmasks between `prev` and this snapshot
+    ///
+    /// Only the held state of the two snapshots is compared, so this works for
+    /// snapshots taken any number of frames apart.
+    pub fn diff(&self, prev: &ButtonSnapshot) -> (u16, u16) {
+        (self.held & !prev.held, prev.held & !self.held)
+    }
+}
+
+/// A button edge published to [`InputEvents`] subscribers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    /// The button that changed
+    pub button: Button,
+    /// Whether the button was pressed or released
+    pub kind: ButtonEvent,
+    /// VBlank count when the polling task observed the change
+    ///
+    /// Stays at 0 until an [`AsyncDisplay`](crate::display::AsyncDisplay) has been created.
+    pub frame: u32,
+}
+
+/// Subscriber to the button events published by the input polling task
+///
+/// Use [`next_message_pure()`](Subscriber::next_message_pure) to await the next event,
+/// or [`try_next_message_pure()`](Subscriber::try_next_message_pure) to drain events
+/// without blocking.
+///
+/// Each subscriber buffers up to [`INPUT_EVENT_CAPACITY`] events. When a slow subscriber
+/// falls behind, the *oldest* events are dropped so the polling task never blocks;
+/// `next_message()` reports this as [`WaitResult::Lagged`](embassy_sync::pubsub::WaitResult::Lagged).
+pub type InputEvents = Subscriber<
+    'static,
+    CriticalSectionRawMutex,
+    InputEvent,
+    INPUT_EVENT_CAPACITY,
+    INPUT_EVENT_SUBSCRIBERS,
+    0,
+>;
+
+/// Future that waits for a specific button edge
+///
+/// The future only completes on a transition into the target state: it must first
+/// observe the button in the opposite state ("armed") before the target state counts.
+/// This makes it edge-triggered rather than level-triggered, so a button that is
+/// already held when the future is created does not resolve it immediately.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 struct ButtonEventFuture {
     button: Button,
     waiting_for_press: bool,
+    armed: bool,
     completed: bool,
 }
 
 impl ButtonEventFuture {
     fn new(button: Button, waiting_for_press: bool) -> Self {
+        let current = read_keypad();
+        let is_pressed = (current & button.bits() as u16) != 0;
+
         Self {
             button,
             waiting_for_press,
+            armed: is_pressed != waiting_for_press,
             completed: false,
         }
     }
+
+    fn event(&self) -> ButtonEvent {
+        if self.waiting_for_press {
+            ButtonEvent::Pressed
+        } else {
+            ButtonEvent::Released
+        }
+    }
 }
 
 impl Future for ButtonEventFuture {
@@ -213,35 +865,124 @@ impl Future for ButtonEventFuture {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         if self.completed {
-            return Poll::Ready(if self.waiting_for_press {
-                ButtonEvent::Pressed
-            } else {
-                ButtonEvent::Released
-            });
+            return Poll::Ready(self.event());
         }
 
         if let Some(index) = button_to_index(self.button) {
+            // Only this button's waker is registered, so changes to other
+            // buttons in the same poll cycle never wake this future
             BUTTON_WAKERS[index].register(cx.waker());
 
             // Check current state
-            let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+            let current = read_keypad();
             let is_pressed = (current & self.button.bits() as u16) != 0;
 
-            if self.waiting_for_press && is_pressed {
-                self.completed = true;
-                Poll::Ready(ButtonEvent::Pressed)
-            } else if !self.waiting_for_press && !is_pressed {
+            if is_pressed != self.waiting_for_press {
+                // Seen in the opposite state, the next transition is a real edge
+                self.armed = true;
+                Poll::Pending
+            } else if self.armed {
                 self.completed = true;
-                Poll::Ready(ButtonEvent::Released)
+                Poll::Ready(self.event())
             } else {
                 Poll::Pending
             }
         } else {
-            Poll::Ready(if self.waiting_for_press {
-                ButtonEvent::Pressed
-            } else {
-                ButtonEvent::Released
-            })
+            Poll::Ready(self.event())
+        }
+    }
+}
+
+/// Future that waits for a combination of buttons to be held at the same time
+///
+/// Like [`ButtonEventFuture`], this is edge-triggered: if the full combo is already
+/// held when the future is created, one of its members must be released first.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+struct ComboFuture {
+    mask: u16,
+    armed: bool,
+}
+
+impl ComboFuture {
+    fn new(buttons: Button) -> Self {
+        let mask = buttons.bits() as u16;
+        let current = read_keypad();
+
+        Self {
+            mask,
+            armed: (current & mask) != mask,
+        }
+    }
+}
+
+impl Future for ComboFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.mask == 0 {
+            return Poll::Ready(());
+        }
+
+        // Only wake for buttons that are part of the combo
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            if (self.mask & button.bits() as u16) != 0 {
+                BUTTON_WAKERS[i].register(cx.waker());
+            }
+        }
+
+        let current = read_keypad();
+
+        if (current & self.mask) != self.mask {
+            // A member is up, so the next time all are down is a fresh combo
+            self.armed = true;
+            Poll::Pending
+        } else if self.armed {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Future that waits for any button in a mask to be newly pressed
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+struct AnyOfFuture {
+    mask: u16,
+    last_state: u16,
+}
+
+impl AnyOfFuture {
+    fn new(buttons: Button) -> Self {
+        Self {
+            mask: buttons.bits() as u16,
+            last_state: read_keypad(),
+        }
+    }
+}
+
+impl Future for AnyOfFuture {
+    type Output = Button;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.mask == 0 {
+            return Poll::Ready(Button::empty());
+        }
+
+        // Only wake for the watched buttons
+        for (i, button) in ALL_BUTTONS.iter().enumerate() {
+            if (self.mask & button.bits() as u16) != 0 {
+                BUTTON_WAKERS[i].register(cx.waker());
+            }
+        }
+
+        let current = read_keypad();
+        let newly_pressed = current & !self.last_state & self.mask;
+        self.last_state = current;
+
+        if newly_pressed != 0 {
+            Poll::Ready(Button::from_bits_truncate(newly_pressed as u32))
+        } else {
+            Poll::Pending
         }
     }
 }
@@ -254,7 +995,7 @@ struct AnyButtonEventFuture {
 
 impl AnyButtonEventFuture {
     fn new() -> Self {
-        let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+        let current = read_keypad();
         Self {
             last_state: current,
         }
@@ -271,25 +1012,12 @@ impl Future for AnyButtonEventFuture {
         }
 
         // Check current state
-        let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+        let current = read_keypad();
         let changed = current ^ self.last_state;
 
         if changed != 0 {
             // Find which button changed
-            let buttons = [
-                Button::A,
-                Button::B,
-                Button::SELECT,
-                Button::START,
-                Button::RIGHT,
-                Button::LEFT,
-                Button::UP,
-                Button::DOWN,
-                Button::R,
-                Button::L,
-            ];
-
-            for button in buttons.iter() {
+            for button in ALL_BUTTONS.iter() {
                 let button_mask = button.bits() as u16;
                 if (changed & button_mask) != 0 {
                     let is_pressed = (current & button_mask) != 0;
@@ -320,7 +1048,16 @@ impl Future for AnyButtonEventFuture {
 /// Async wrapper for agb input operations
 pub struct AsyncInput {
     controller: ButtonController,
-    _config: InputConfig,
+    config: InputConfig,
+    /// Keypad interrupt, while enabled
+    keypad_interrupt: Option<KeypadInterrupt>,
+    /// Button state latched by the last `update()`
+    current: u16,
+    /// Button state latched by the `update()` before that
+    previous: u16,
+    /// Press and auto-repeat ticks latched by the last `update()`
+    #[cfg(all(feature = "time", feature = "executor"))]
+    repeated: u16,
 }
 
 impl AsyncInput {
@@ -330,28 +1067,180 @@ impl AsyncInput {
 
     pub(crate) fn with_config(config: InputConfig) -> Self {
         ensure_input_initialized();
+        let current = read_keypad();
 
         Self {
             controller: ButtonController::new(),
-            _config: config,
+            config,
+            keypad_interrupt: None,
+            current,
+            previous: current,
+            #[cfg(all(feature = "time", feature = "executor"))]
+            repeated: 0,
         }
     }
 
-    /// Wait for a specific button to be pressed
-    pub async fn wait_for_button_press(&mut self, button: Button) -> ButtonEvent {
-        // If button is already pressed, wait for release first
-        let current = !unsafe { KEYPAD_INPUT.read_volatile() };
-        let is_pressed = (current & button.bits() as u16) != 0;
+    /// Get the current input configuration
+    pub fn config(&self) -> &InputConfig {
+        &self.config
+    }
+
+    /// Replace the input configuration
+    ///
+    /// If the polling task started by [`enable_input_polling`](crate::enable_input_polling)
+    /// is running, it switches to the new poll rate and auto-repeat timing right away,
+    /// without being respawned. The polling task is shared, so this affects every
+    /// [`AsyncInput`], while `buffer_frames` only applies to this one.
+    pub fn set_config(&mut self, config: InputConfig) {
+        self.config = config;
+
+        #[cfg(all(feature = "time", feature = "executor"))]
+        POLLING_CONFIG.signal(config);
+    }
+
+    /// Turn on the keypad interrupt for `buttons`, see [`enable_interrupt()`](Self::enable_interrupt)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # async fn example(gba: &mut embassy_agb::InitializedGba) {
+    /// let input = gba.input().with_interrupt(Button::A | Button::START);
+    ///
+    /// // Resolves the moment A goes down, not on the next poll
+    /// input.wait_for_button_press(Button::A).await;
+    /// # }
+    /// ```
+    pub fn with_interrupt(mut self, buttons: Button) -> Self {
+        self.enable_interrupt(buttons);
+        self
+    }
 
-        if is_pressed {
-            // Wait for release first
-            ButtonEventFuture::new(button, false).await;
+    /// Raise the keypad interrupt whenever one of `buttons` is pressed
+    ///
+    /// The interrupt runs the same change detection as the polling task, so
+    /// [`wait_for_button_press()`](Self::wait_for_button_press),
+    /// [`wait_for_any_of()`](Self::wait_for_any_of), [`subscribe()`](Self::subscribe) and
+    /// the other edge-based waits resolve as soon as a watched button goes down, and the
+    /// CPU wakes from Halt for it. Only presses raise the interrupt: releases, auto-repeat
+    /// and buttons outside `buttons` still need
+    /// [`enable_input_polling`](crate::enable_input_polling), which can run at a slow
+    /// rate alongside it.
+    ///
+    /// A held button stays out of the interrupt until it's seen released, either by the
+    /// polling task or by [`update()`](Self::update), so holding it doesn't flood the CPU
+    /// with interrupts. Replaces any buttons set by an earlier call on this input; buttons
+    /// enabled through another [`AsyncInput`] keep raising the interrupt.
+    pub fn enable_interrupt(&mut self, buttons: Button) {
+        // Remove the old handler first so KEYCNT isn't cleared after the new one is set up
+        self.keypad_interrupt = None;
+
+        let buttons = buttons.bits() as u16 & KEYPAD_MASK;
+        if buttons != 0 {
+            ensure_input_initialized();
+            self.keypad_interrupt = Some(KeypadInterrupt::new(buttons));
         }
+    }
+
+    /// Turn off the keypad interrupt, going back to polling only
+    pub fn disable_interrupt(&mut self) {
+        self.keypad_interrupt = None;
+    }
 
-        // Now wait for press
+    /// Whether the keypad interrupt is on
+    pub fn is_interrupt_enabled(&self) -> bool {
+        self.keypad_interrupt.is_some()
+    }
+
+    /// Wait for a specific button to be pressed
+    ///
+    /// Resolves only when `button` transitions from released to pressed. If the
+    /// button is already held when this is called, it must be released and pressed
+    /// again. Changes to other buttons are ignored and do not wake the task.
+    ///
+    /// Requires the input polling task (see [`enable_input_polling`](crate::enable_input_polling))
+    /// or the keypad interrupt (see [`enable_interrupt()`](Self::enable_interrupt)) to
+    /// detect changes.
+    pub async fn wait_for_button_press(&self, button: Button) -> ButtonEvent {
         ButtonEventFuture::new(button, true).await
     }
 
+    /// Wait for a specific button to be released
+    ///
+    /// Resolves only when `button` transitions from pressed to released. If the
+    /// button is not held when this is called, it must be pressed and released first.
+    pub async fn wait_for_button_release(&self, button: Button) -> ButtonEvent {
+        ButtonEventFuture::new(button, false).await
+    }
+
+    /// Wait for all buttons in `buttons` to be held at the same time
+    ///
+    /// The buttons do not need to be pressed in the same frame: pressing A and then B
+    /// a few frames later still completes the combo, as long as both are down at the
+    /// same instant. Releasing any member resets detection, and a combo that is
+    /// already held when this is called must be broken and re-formed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # async fn example(input: embassy_agb::input::AsyncInput) {
+    /// input.wait_for_combo(Button::L | Button::R | Button::START).await;
+    /// // Soft-reset combo entered
+    /// # }
+    /// ```
+    pub async fn wait_for_combo(&self, buttons: Button) {
+        ComboFuture::new(buttons).await
+    }
+
+    /// Subscribe to button edge events published by the input polling task
+    ///
+    /// This lets a separate task consume input without being tied to display timing.
+    /// Events are only published while the polling task (see
+    /// [`enable_input_polling`](crate::enable_input_polling)) is running.
+    ///
+    /// Returns an error if all [`INPUT_EVENT_SUBSCRIBERS`] slots are in use; dropping
+    /// a subscriber frees its slot.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::input::{ButtonEvent, InputEvents};
+    /// #[embassy_agb::task]
+    /// async fn input_handler(mut events: InputEvents) {
+    ///     loop {
+    ///         let event = events.next_message_pure().await;
+    ///         if event.kind == ButtonEvent::Pressed {
+    ///             // React to event.button
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe(&self) -> Result<InputEvents, embassy_sync::pubsub::Error> {
+        INPUT_EVENTS.subscriber()
+    }
+
+    /// Wait for any of `buttons` to be pressed, returning which ones were
+    ///
+    /// Resolves with the subset of `buttons` that went down since the last check. If
+    /// several of them are pressed in the same poll cycle they are all returned OR'd
+    /// together. Buttons already held when this is called must be released first.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # async fn example(input: embassy_agb::input::AsyncInput) {
+    /// let choice = input.wait_for_any_of(Button::A | Button::B | Button::START).await;
+    /// if choice.contains(Button::START) {
+    ///     // Resume game
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_any_of(&self, buttons: Button) -> Button {
+        AnyOfFuture::new(buttons).await
+    }
+
     /// Wait for any button to be pressed or released
     pub async fn wait_for_any_button_press(&mut self) -> (Button, ButtonEvent) {
         AnyButtonEventFuture::new().await
@@ -368,56 +1257,561 @@ impl AsyncInput {
     }
 
     /// Get current button state (non-blocking)
+    ///
+    /// While an [`InputPlayback`] is active, each call advances it by one frame.
     pub fn update(&mut self) {
+        advance_playback();
         self.controller.update();
+        self.previous = self.current;
+        self.current = read_keypad();
+
+        #[cfg(feature = "time")]
+        if self.current != self.previous {
+            note_input_change();
+        }
+
+        if self.keypad_interrupt.is_some() {
+            // Select interrupt buttons released since the last press again
+            critical_section::with(|_| sync_keypad_interrupt());
+        }
+
+        #[cfg(all(feature = "time", feature = "executor"))]
+        {
+            self.repeated = REPEAT_PENDING.swap(0, Ordering::SeqCst);
+        }
     }
 
     /// Check if a button is currently pressed
     pub fn is_pressed(&self, button: Button) -> bool {
-        let current = !unsafe { KEYPAD_INPUT.read_volatile() };
+        let current = read_keypad();
         (current & button.bits() as u16) != 0
     }
 
-    /// Check if a button is currently pressed using agb's ButtonController
+    /// Get the net D-pad direction as an x/y pair
+    ///
+    /// Each axis is in `-1..=1`: LEFT/UP are negative, RIGHT/DOWN positive, and
+    /// holding opposing buttons cancels out. Perpendicular buttons combine into a
+    /// diagonal. Like [`is_pressed()`](Self::is_pressed), this reads the live state.
+    pub fn direction(&self) -> (i32, i32) {
+        let x = self.is_pressed(Button::RIGHT) as i32 - self.is_pressed(Button::LEFT) as i32;
+        let y = self.is_pressed(Button::DOWN) as i32 - self.is_pressed(Button::UP) as i32;
+        (x, y)
+    }
+
+    /// Get the D-pad direction as one of eight compass directions
+    pub fn direction8(&self) -> Direction8 {
+        let (x, y) = self.direction();
+        Direction8::from_xy(x, y)
+    }
+
+    /// Check if a button is currently pressed, as latched by the last [`update()`](Self::update)
     pub fn is_pressed_polling(&self, button: Button) -> bool {
-        self.controller.is_pressed(button)
+        (self.current & button.bits() as u16) != 0
+    }
+
+    /// Check if all buttons in `buttons` were held at the last [`update()`](Self::update)
+    ///
+    /// Unlike [`is_pressed_polling()`](Self::is_pressed_polling), which is true if *any*
+    /// of the given buttons is pressed, this requires every one of them to be down.
+    pub fn is_combo_pressed(&self, buttons: Button) -> bool {
+        let mask = buttons.bits() as u16;
+        (self.button_state_bits() & mask) == mask
     }
 
-    /// Check if a button was just pressed this frame using agb's ButtonController
+    /// Check if any button bound to `action` in `map` is currently held
+    ///
+    /// Uses the same per-frame state as [`is_pressed_polling()`](Self::is_pressed_polling).
+    /// The map is read on every call, so rebinding takes effect immediately.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # use embassy_agb::input::InputMap;
+    /// #[derive(Clone, Copy, PartialEq, Eq)]
+    /// enum Action {
+    ///     Jump,
+    ///     Fire,
+    /// }
+    ///
+    /// # fn example(input: &embassy_agb::input::AsyncInput) {
+    /// let mut map = InputMap::new();
+    /// map.bind(Action::Jump, Button::A | Button::UP);
+    /// map.bind(Action::Fire, Button::B);
+    ///
+    /// if input.action_pressed(&map, Action::Jump) {
+    ///     // Jump
+    /// }
+    /// # }
+    /// ```
+    pub fn action_pressed<K: PartialEq>(&self, map: &InputMap<K>, action: K) -> bool {
+        self.is_pressed_polling(map.buttons(&action))
+    }
+
+    /// Check if a button was just pressed this frame, as latched by [`update()`](Self::update)
     pub fn is_just_pressed_polling(&self, button: Button) -> bool {
-        self.controller.is_just_pressed(button)
+        (self.current & !self.previous & button.bits() as u16) != 0
+    }
+
+    /// Check if a button was just released this frame
+    ///
+    /// True only on the first `update()` after the button goes from pressed to
+    /// released, mirroring [`is_just_pressed_polling()`](Self::is_just_pressed_polling).
+    pub fn is_just_released_polling(&self, button: Button) -> bool {
+        (self.previous & !self.current & button.bits() as u16) != 0
+    }
+
+    /// Take a recent press of `button` out of the input buffer
+    ///
+    /// Returns true if the polling task saw `button` go down within the last
+    /// [`buffer_frames`](InputConfig::buffer_frames) frames and that press has not been
+    /// consumed yet. A successful call clears the buffered press, so each press is
+    /// consumed at most once. This makes jumps pressed slightly before landing still
+    /// register.
+    ///
+    /// Frames are counted in VBlanks, so an [`AsyncDisplay`](crate::display::AsyncDisplay)
+    /// must exist for presses to age out of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # fn example(input: &embassy_agb::input::AsyncInput, on_ground: bool) {
+    /// if on_ground && input.consume_buffered(Button::A) {
+    ///     // Jump
+    /// }
+    /// # }
+    /// ```
+    pub fn consume_buffered(&self, button: Button) -> bool {
+        let Some(index) = button_to_index(button) else {
+            return false;
+        };
+        let now = crate::display::vblank_count() as u32;
+        let window = self.config.buffer_frames as u32;
+
+        BUFFERED_PRESSES.lock(|presses| {
+            let mut buffered = presses.get();
+            match buffered[index].take() {
+                Some(at) if now.wrapping_sub(at) <= window => {
+                    presses.set(buffered);
+                    true
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Capture the per-frame button state as a [`ButtonSnapshot`]
+    ///
+    /// The edges are relative to the previous `update()`, matching
+    /// [`is_just_pressed_polling()`](Self::is_just_pressed_polling) and
+    /// [`is_just_released_polling()`](Self::is_just_released_polling).
+    pub fn snapshot(&self) -> ButtonSnapshot {
+        ButtonSnapshot {
+            pressed: self.current & !self.previous,
+            released: self.previous & !self.current,
+            held: self.current,
+        }
+    }
+
+    /// Check if a button fired a press or auto-repeat tick since the last `update()`
+    ///
+    /// Returns true on the initial press and then once per
+    /// [`repeat_interval`](InputConfig::repeat_interval) after the button has been held for
+    /// [`repeat_delay`](InputConfig::repeat_delay). With `repeat_delay: None` only the
+    /// initial press is reported. Releasing the button resets the delay.
+    ///
+    /// The repeat timing comes from the config passed to
+    /// [`input_polling_task`], so the polling task must be running. That task is part of
+    /// the `executor` feature, so this needs both `executor` and `time`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # use embassy_agb::input::{InputConfig, PollingRate};
+    /// # use embassy_agb::Duration;
+    /// # async fn example(spawner: embassy_agb::Spawner, gba: &mut embassy_agb::InitializedGba) {
+    /// let config = InputConfig {
+    ///     repeat_delay: Some(Duration::from_millis(400)),
+    ///     repeat_interval: Duration::from_millis(80),
+    ///     ..InputConfig::new(PollingRate::Hz60)
+    /// };
+    /// spawner.must_spawn(embassy_agb::input::input_polling_task(config));
+    ///
+    /// let mut input = gba.input_with_config(config);
+    /// loop {
+    ///     input.update();
+    ///     if input.is_repeated(Button::DOWN) {
+    ///         // Move the menu cursor down
+    ///     }
+    ///     # break;
+    /// }
+    /// # }
+    /// ```
+    #[cfg(all(feature = "time", feature = "executor"))]
+    pub fn is_repeated(&self, button: Button) -> bool {
+        (self.repeated & button.bits() as u16) != 0
+    }
+
+    /// Get how long a button has been continuously held
+    ///
+    /// Returns `None` if the button is currently up. Release is checked against the
+    /// hardware directly, so this becomes `None` as soon as the button is let go, even
+    /// before the next poll. The press instant is recorded when the polling task or the
+    /// keypad interrupt sees the press, so one neither has seen yet reports a zero duration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::input::Button;
+    /// # use embassy_agb::Duration;
+    /// # fn example(input: &embassy_agb::input::AsyncInput) {
+    /// if let Some(held) = input.held_duration(Button::B) {
+    ///     if held >= Duration::from_secs(1) {
+    ///         // Fully charged shot
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[cfg(all(feature = "time", feature = "executor"))]
+    pub fn held_duration(&self, button: Button) -> Option<embassy_time::Duration> {
+        let index = button_to_index(button)?;
+        if !self.is_pressed(button) {
+            return None;
+        }
+
+        match PRESSED_AT.lock(|pressed_at| pressed_at.get()[index]) {
+            Some(at) => Some(embassy_time::Instant::now().saturating_duration_since(at)),
+            None => Some(embassy_time::Duration::from_ticks(0)),
+        }
+    }
+
+    /// Whether no button has been pressed or released for at least `duration`
+    ///
+    /// Changes are noticed by the [polling task](crate::enable_input_polling), the keypad
+    /// interrupt and [`update()`](Self::update), so at least one of those must be running.
+    /// A button held down without changing counts as idle. Before the first change, time
+    /// is counted from power-on.
+    #[cfg(feature = "time")]
+    pub fn idle_for(&self, duration: embassy_time::Duration) -> bool {
+        embassy_time::Instant::now() >= last_input_change() + duration
+    }
+
+    /// Wait until no button has been pressed or released for `duration`
+    ///
+    /// Sleeps until the idle deadline, checking again if input arrived in the meantime,
+    /// so nothing is polled while waiting. See [`idle_for()`](Self::idle_for) for what
+    /// counts as input.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use embassy_agb::futures::select::{select, Either};
+    /// use embassy_agb::Duration;
+    ///
+    /// # async fn example(input: &embassy_agb::input::AsyncInput) {
+    /// # async fn play_level() {}
+    /// // Back to the attract screen after 30 seconds without input
+    /// match select(play_level(), input.wait_for_idle(Duration::from_secs(30))).await {
+    ///     Either::First(()) => { /* Level finished */ }
+    ///     Either::Second(()) => { /* Show the demo */ }
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn wait_for_idle(&self, duration: embassy_time::Duration) {
+        loop {
+            let deadline = last_input_change() + duration;
+            if embassy_time::Instant::now() >= deadline {
+                return;
+            }
+            embassy_time::Timer::at(deadline).await;
+        }
     }
 
     /// Get the tri-state for directional inputs (non-blocking)
+    ///
+    /// LEFT is [`Tri::Negative`] and RIGHT [`Tri::Positive`]; neither or both give
+    /// [`Tri::Zero`]. `x_tri() as i32` is -1, 0 or 1. Reads the state latched by
+    /// [`update()`](Self::update), like [`is_pressed_polling()`](Self::is_pressed_polling).
     pub fn x_tri(&self) -> Tri {
-        self.controller.x_tri()
+        (
+            self.is_pressed_polling(Button::LEFT),
+            self.is_pressed_polling(Button::RIGHT),
+        )
+            .into()
     }
 
     /// Get the tri-state for directional inputs (non-blocking)
+    ///
+    /// UP is [`Tri::Negative`] and DOWN [`Tri::Positive`], matching screen coordinates.
     pub fn y_tri(&self) -> Tri {
-        self.controller.y_tri()
+        (
+            self.is_pressed_polling(Button::UP),
+            self.is_pressed_polling(Button::DOWN),
+        )
+            .into()
+    }
+
+    /// Get the tri-state for the shoulder buttons (non-blocking)
+    ///
+    /// L is [`Tri::Negative`] and R [`Tri::Positive`], e.g. for cycling through items.
+    pub fn lr_tri(&self) -> Tri {
+        (
+            self.is_pressed_polling(Button::L),
+            self.is_pressed_polling(Button::R),
+        )
+            .into()
+    }
+
+    /// Get the D-pad as an x/y step, each axis in `-1..=1`
+    ///
+    /// Same as `(x_tri() as i32, y_tri() as i32)`. Unlike [`direction()`](Self::direction),
+    /// which reads the live buttons, this uses the state latched by
+    /// [`update()`](Self::update), so it agrees with the other per-frame queries.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(input: &embassy_agb::input::AsyncInput, (mut x, mut y): (i32, i32)) {
+    /// const SPEED: i32 = 2;
+    /// let (dx, dy) = input.dpad_vector();
+    /// x += dx * SPEED;
+    /// y += dy * SPEED;
+    /// # }
+    /// ```
+    pub fn dpad_vector(&self) -> (i32, i32) {
+        (self.x_tri() as i32, self.y_tri() as i32)
+    }
+
+    /// The agb [`ButtonController`] updated alongside the `_polling` methods, for agb-only helpers
+    ///
+    /// It's updated in the same [`update()`](Self::update) call as the state read by
+    /// [`is_pressed_polling()`](Self::is_pressed_polling) and the other per-frame queries,
+    /// so both agree within a frame. The one exception is an active [`InputPlayback`]: the
+    /// controller reads the buttons directly and sees the physical ones, not the recording.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(input: &embassy_agb::input::AsyncInput) {
+    /// let step: agb::fixnum::Vector2D<i32> = input.controller().just_pressed_vector();
+    /// # }
+    /// ```
+    pub fn controller(&self) -> &ButtonController {
+        &self.controller
+    }
+
+    /// Mutable access to the agb [`ButtonController`], see [`controller()`](Self::controller)
+    ///
+    /// Calling [`ButtonController::update()`] through this advances the controller's
+    /// just-pressed state without [`update()`](Self::update), after which the two no
+    /// longer agree until the next frame's `update()`.
+    pub fn controller_mut(&mut self) -> &mut ButtonController {
+        &mut self.controller
     }
 
     /// Get the current button state as raw bits
     pub(crate) fn button_state_bits(&self) -> u16 {
-        let mut bits = 0u16;
-        for button in [
-            Button::A,
-            Button::B,
-            Button::START,
-            Button::SELECT,
-            Button::LEFT,
-            Button::RIGHT,
-            Button::UP,
-            Button::DOWN,
-            Button::L,
-            Button::R,
-        ] {
-            if self.controller.is_pressed(button) {
-                bits |= button.bits() as u16;
-            }
+        self.current
+    }
+}
+
+/// Bindings from logical actions to physical buttons
+///
+/// `K` is usually a small `enum` of game actions. An action can be bound to several
+/// buttons at once and is considered held when *any* of them is down. Actions with no
+/// binding never read as pressed.
+#[derive(Debug, Clone)]
+pub struct InputMap<K> {
+    bindings: Vec<(K, Button)>,
+}
+
+impl<K: PartialEq> InputMap<K> {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Add `buttons` to the buttons bound to `action`
+    pub fn bind(&mut self, action: K, buttons: Button) {
+        match self.bindings.iter_mut().find(|(k, _)| *k == action) {
+            Some((_, bound)) => *bound |= buttons,
+            None => self.bindings.push((action, buttons)),
         }
-        bits
+    }
+
+    /// Replace the buttons bound to `action` with `buttons`
+    pub fn rebind(&mut self, action: K, buttons: Button) {
+        self.unbind(&action);
+        self.bindings.push((action, buttons));
+    }
+
+    /// Remove all buttons bound to `action`
+    pub fn unbind(&mut self, action: &K) {
+        self.bindings.retain(|(k, _)| k != action);
+    }
+
+    /// Get the buttons bound to `action`, empty if it has no binding
+    pub fn buttons(&self, action: &K) -> Button {
+        self.bindings
+            .iter()
+            .find(|(k, _)| k == action)
+            .map_or(Button::empty(), |(_, buttons)| *buttons)
+    }
+}
+
+impl<K: PartialEq> Default for InputMap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Records the button state of every frame for later replay with [`InputPlayback`]
+///
+/// Wraps an [`AsyncInput`] and derefs to it, so it can be used in its place. Each call
+/// to [`update()`](Self::update) updates the wrapped input and appends the new state.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use agb::input::Button;
+/// # use embassy_agb::input::{InputPlayback, InputRecorder};
+/// # async fn example(gba: &mut embassy_agb::InitializedGba) {
+/// let mut recorder = InputRecorder::new(gba.input());
+/// for _ in 0..600 {
+///     recorder.update();
+///     if recorder.is_pressed_polling(Button::A) {
+///         // Game logic
+///     }
+/// }
+/// let (input, frames) = recorder.into_parts();
+///
+/// // Later: replay the same 600 frames
+/// let _playback = InputPlayback::start(frames).unwrap();
+/// # }
+/// ```
+pub struct InputRecorder {
+    input: AsyncInput,
+    frames: Vec<u16>,
+}
+
+impl InputRecorder {
+    /// Start recording from `input`
+    pub fn new(input: AsyncInput) -> Self {
+        Self {
+            input,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Update the wrapped input and record the resulting button state
+    pub fn update(&mut self) {
+        self.input.update();
+        self.frames.push(self.input.button_state_bits());
+    }
+
+    /// Get the button states recorded so far, one per frame
+    pub fn frames(&self) -> &[u16] {
+        &self.frames
+    }
+
+    /// Stop recording, returning the wrapped input and the recorded frames
+    pub fn into_parts(self) -> (AsyncInput, Vec<u16>) {
+        (self.input, self.frames)
+    }
+}
+
+impl core::ops::Deref for InputRecorder {
+    type Target = AsyncInput;
+
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl core::ops::DerefMut for InputRecorder {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.input
+    }
+}
+
+/// Replays frames captured by [`InputRecorder`] in place of the hardware keypad
+///
+/// While a playback is active, every input reader sees the recorded state instead of
+/// KEYINPUT: [`AsyncInput`] queries, [`wait_frame()`](crate::GbaPeripherals::wait_frame),
+/// the button futures and the input polling task. Each [`AsyncInput::update()`] advances
+/// the playback by exactly one frame, so a game that updates its input once per frame
+/// sees the same sequence of states on every run.
+///
+/// Once all frames have been played, every button reads as released until the playback
+/// is dropped, at which point input goes back to the hardware.
+///
+/// The `*_polling` futures read agb's `ButtonController` directly and are not affected.
+pub struct InputPlayback {
+    len: usize,
+}
+
+impl InputPlayback {
+    /// Start replaying `frames`
+    ///
+    /// Returns `None` if another playback is already active.
+    pub fn start(frames: Vec<u16>) -> Option<Self> {
+        if PLAYBACK_ACTIVE.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        let len = frames.len();
+        PLAYBACK.lock(|playback| {
+            *playback.borrow_mut() = Some(PlaybackBuffer {
+                frames,
+                position: 0,
+            });
+        });
+        PLAYBACK_STATE.store(0, Ordering::SeqCst);
+        PLAYBACK_ACTIVE.store(true, Ordering::SeqCst);
+
+        Some(Self { len })
+    }
+
+    /// Get the number of frames played so far
+    pub fn position(&self) -> usize {
+        PLAYBACK.lock(|playback| {
+            playback
+                .borrow()
+                .as_ref()
+                .map_or(0, |buffer| buffer.position.min(self.len))
+        })
+    }
+
+    /// Get the total number of recorded frames
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the recording contains no frames
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check if every recorded frame has been played
+    pub fn is_finished(&self) -> bool {
+        self.position() >= self.len
+    }
+
+    /// Stop the playback and return to reading the hardware keypad
+    pub fn stop(self) {}
+}
+
+impl Drop for InputPlayback {
+    fn drop(&mut self) {
+        PLAYBACK_ACTIVE.store(false, Ordering::SeqCst);
+        PLAYBACK.lock(|playback| {
+            playback.borrow_mut().take();
+        });
     }
 }
 
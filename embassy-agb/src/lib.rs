@@ -22,10 +22,16 @@
 //! - Async display operations (VBlank waiting, DMA transfers)
 //! - Async input handling (button press events) with automatic polling
 //! - Async sound mixing
-//! - Embassy time integration with GBA timers
+//! - Embassy time integration with GBA timers, or with VBlank for a timer-free driver
 //! - Task spawning and management
 //! - Automatic power management via Halt mode
 //!
+//! ## Panics
+//!
+//! A panic in any task stops every task. agb's panic handler (enabled through its
+//! default `backtrace` feature) shows the panic message, location and a backtrace QR
+//! code on screen, and logs the message to mGBA, so crashes on hardware stay readable.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -65,6 +71,8 @@
 //! }
 //! ```
 
+extern crate alloc;
+
 // Include generated code
 include!(concat!(env!("OUT_DIR"), "/_generated.rs"));
 
@@ -78,10 +86,10 @@ pub use embassy_agb_macros::{main, task};
 pub use embassy_time as time;
 
 #[cfg(feature = "time")]
-pub use embassy_time::{Duration, Instant, Ticker, Timer};
+pub use embassy_time::{Duration, Instant, Ticker, TimeoutError, Timer};
 
 pub use embassy_futures as futures;
-pub use embassy_sync as sync;
+pub use embassy_futures::yield_now;
 
 // Re-export agb for convenience
 pub use agb;
@@ -90,7 +98,10 @@ pub use agb;
 pub mod config;
 pub use config::*;
 
+pub mod debug;
+
 #[cfg(feature = "_time-driver")]
+#[cfg_attr(feature = "time-driver-vblank", path = "time_driver_vblank.rs")]
 mod time_driver;
 
 #[cfg(feature = "executor")]
@@ -100,16 +111,36 @@ pub use executor::*;
 
 /// Async display utilities
 pub mod display;
+pub use display::on_vblank;
 pub mod input;
+mod power;
+pub use power::*;
+pub mod link;
+pub mod rng;
+pub mod rtc;
+pub mod rumble;
+pub mod save;
 /// Async sound utilities
 pub mod sound;
+pub mod sync;
 /// Utility functions and macros
 pub mod utils;
+#[cfg(feature = "_time-driver")]
+pub mod watchdog;
 
 /// Internal utilities (do not use directly)
 #[doc(hidden)]
 pub mod _internal;
 
+#[cfg(test)]
+#[agb::entry]
+fn main(_gba: agb::Gba) -> ! {
+    // Replaced by the test runner
+    loop {
+        agb::halt();
+    }
+}
+
 /// Initialize the embassy-agb HAL with the given configuration.
 ///
 /// This function must be called once before using any embassy-agb functionality.
@@ -121,15 +152,15 @@ pub mod _internal;
 /// let gba = embassy_agb::init(Default::default());
 /// ```
 pub fn init(config: Config) -> InitializedGba {
+    // Take peripherals first, so a second call panics before touching anything
+    let peripherals = Peripherals::take();
+
     // Get the agb instance from internal storage (set by macro)
     let gba = unsafe { _internal::get_agb_instance() };
 
-    // Configure the time driver with user settings
+    // Move the time driver to the user's timer settings
     #[cfg(feature = "_time-driver")]
-    time_driver::configure_timer_frequency(config.timer.overflow_amount);
-
-    // Take peripherals
-    let peripherals = Peripherals::take();
+    time_driver::configure(&config.timer);
 
     InitializedGba {
         gba,
@@ -138,6 +169,31 @@ pub fn init(config: Config) -> InitializedGba {
     }
 }
 
+/// Marks a peripheral wrapper as in use until dropped
+///
+/// Wrappers borrow [`InitializedGba`] mutably, so safe code can't normally hold two for
+/// the same hardware at once. The flag turns anything that gets past the borrow checker
+/// into a clear panic, like [`Peripherals::take()`], instead of two wrappers silently
+/// driving the same hardware.
+pub(crate) struct TakeGuard(&'static portable_atomic::AtomicBool);
+
+impl TakeGuard {
+    /// Mark `flag` as taken, panicking if it already is
+    pub(crate) fn take(flag: &'static portable_atomic::AtomicBool, name: &str) -> Self {
+        assert!(
+            !flag.swap(true, portable_atomic::Ordering::SeqCst),
+            "{name} already taken: drop the previous peripherals, split() or {name} first"
+        );
+        Self(flag)
+    }
+}
+
+impl Drop for TakeGuard {
+    fn drop(&mut self) {
+        self.0.store(false, portable_atomic::Ordering::SeqCst);
+    }
+}
+
 /// The initialized GBA with embassy integration
 pub struct InitializedGba {
     gba: &'static mut agb::Gba,
@@ -156,10 +212,16 @@ impl InitializedGba {
     /// - Direct field access: `peripherals.display`, `peripherals.mixer`, `peripherals.input`
     /// - Automatic frame handling: `wait_frame()` handles input updates, audio mixing, and VBlank
     /// - Frame events: Returns button presses, releases, and frame counter
-    /// - Convenience methods: `play_sound()` and `play_sound_high_priority()`
+    /// - Convenience methods: `play_sound()`, `play_sound_high_priority()` and `play_sound_with()`
     ///
     /// For advanced use cases requiring finer control, see [`split()`](Self::split).
     ///
+    /// # Panics
+    ///
+    /// Panics with "display already taken" or "mixer already taken" if a display or
+    /// mixer from an earlier call is somehow still alive. The same applies to
+    /// [`split()`](Self::split), [`display()`](Self::display) and [`mixer()`](Self::mixer).
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -183,11 +245,7 @@ impl InitializedGba {
         &mut self,
         mixer_frequency: agb::sound::mixer::Frequency,
     ) -> GbaPeripherals<'_> {
-        GbaPeripherals::new(
-            &mut self.gba,
-            mixer_frequency,
-            input::InputConfig::default(),
-        )
+        GbaPeripherals::new(self.gba, mixer_frequency, input::InputConfig::default())
     }
 
     /// Get peripherals with custom input polling configuration
@@ -199,7 +257,7 @@ impl InitializedGba {
         mixer_frequency: agb::sound::mixer::Frequency,
         input_config: input::InputConfig,
     ) -> GbaPeripherals<'_> {
-        GbaPeripherals::new(&mut self.gba, mixer_frequency, input_config)
+        GbaPeripherals::new(self.gba, mixer_frequency, input_config)
     }
 
     /// Split the GBA into display, mixer, and input peripherals
@@ -275,6 +333,21 @@ impl InitializedGba {
         input::AsyncInput::with_config(config)
     }
 
+    /// Get async access to the cartridge save media
+    ///
+    /// Sets up agb's save backend for `media` on the first call, which also marks the
+    /// ROM so emulators know which save type to emulate. Later calls must pass the same
+    /// media.
+    ///
+    /// Returns [`SaveError::NoMedia`](save::SaveError::NoMedia) if no save chip
+    /// responds, [`SaveError::WrongBackend`](save::SaveError::WrongBackend) if the
+    /// backend was set up for different media, and
+    /// [`SaveError::InUse`](save::SaveError::InUse) if another
+    /// [`SaveData`](save::SaveData) is still alive.
+    pub fn save(&mut self, media: save::SaveMedia) -> Result<save::SaveData, save::SaveError> {
+        save::SaveData::open(&mut self.gba.save, media)
+    }
+
     /// Get access to the underlying agb::Gba for compatibility
     pub fn agb(&mut self) -> &mut agb::Gba {
         self.gba
@@ -289,15 +362,42 @@ impl InitializedGba {
 ///
 /// - **Button presses**: Buttons that transitioned from released to pressed
 /// - **Button releases**: Buttons that transitioned from pressed to released
+/// - **Held buttons**: Buttons that were down when the edges were computed
 /// - **Frame counter**: Auto-incrementing counter for animations and timing
+/// - **Dropped frames**: Running count of VBlanks missed because a frame ran too long
+/// - **Frame time**: Wall-clock time since the previous frame, for frame-rate-independent
+///   movement (requires the `time` feature)
 #[derive(Debug, Clone, Copy, Default)]
 pub struct FrameEvents {
     /// Bit flags for buttons that were just pressed this frame
     pressed: u16,
     /// Bit flags for buttons that were just released this frame  
     released: u16,
+    /// Bit flags for buttons that were held this frame
+    held: u16,
     /// Frame counter (wraps at u32::MAX)
     pub frame_count: u32,
+    /// Total VBlanks missed since the peripherals were created
+    ///
+    /// A VBlank is missed when it passes before `wait_frame()` is called, meaning that
+    /// frame's work blew its ~16.7ms budget. Compare with the previous frame's value to
+    /// spot a new stutter.
+    pub dropped_frames: u32,
+    /// Time since the previous [`GbaPeripherals::wait_frame()`] returned
+    ///
+    /// One VBlank interval (~16.7ms) on the first frame. Scale movement by this to keep
+    /// speeds the same when frames are dropped:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// # let (mut position, velocity) = (0u64, 60u64);
+    /// let events = peripherals.wait_frame().await;
+    /// // velocity in pixels per second
+    /// position += velocity * events.frame_time.as_micros() / 1_000_000;
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub frame_time: Duration,
 }
 
 impl FrameEvents {
@@ -311,6 +411,14 @@ impl FrameEvents {
         (self.released & button.bits() as u16) != 0
     }
 
+    /// Check if a specific button was held this frame
+    ///
+    /// This is captured at the same instant as the pressed/released edges, so it is
+    /// always coherent with them (a button that was just pressed is also held).
+    pub fn is_held(&self, button: agb::input::Button) -> bool {
+        (self.held & button.bits() as u16) != 0
+    }
+
     /// Check if any button was pressed this frame
     pub fn any_pressed(&self) -> bool {
         self.pressed != 0
@@ -330,6 +438,124 @@ impl FrameEvents {
     pub fn released_buttons(&self) -> u16 {
         self.released
     }
+
+    /// Get all buttons that were held this frame as a bitmask
+    pub fn held_buttons(&self) -> u16 {
+        self.held
+    }
+}
+
+/// Per-frame updates to run in [`GbaPeripherals::wait_frame_with()`]
+///
+/// The default runs everything, matching [`GbaPeripherals::wait_frame()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameOptions {
+    /// Mix one frame of audio (default: true)
+    pub mix_audio: bool,
+    /// Read the buttons and report presses and releases (default: true)
+    pub update_input: bool,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            mix_audio: true,
+            update_input: true,
+        }
+    }
+}
+
+/// Result of [`GbaPeripherals::wait_frame_or_button()`]
+#[derive(Debug, Clone, Copy)]
+pub enum FrameOutcome {
+    /// The next frame arrived before any of the watched buttons were pressed
+    Frame(FrameEvents),
+    /// A watched button was pressed before the frame ended
+    Button {
+        /// The watched buttons that were pressed
+        pressed: agb::input::Button,
+        /// Events so far this frame, including the press
+        events: FrameEvents,
+    },
+}
+
+impl FrameOutcome {
+    /// The frame's events, whichever way the wait ended
+    pub fn events(&self) -> &FrameEvents {
+        match self {
+            Self::Frame(events) | Self::Button { events, .. } => events,
+        }
+    }
+}
+
+/// Frame timing statistics returned by [`GbaPeripherals::frame_stats()`]
+#[cfg(feature = "time")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Average time between `wait_frame()` calls over the last 60 frames
+    pub avg_frame_time: Duration,
+    /// Longest time between `wait_frame()` calls over the last 60 frames
+    pub worst_frame_time: Duration,
+    /// VBlanks missed since the stats were last reset
+    ///
+    /// Counted the same way as [`FrameEvents::dropped_frames`], so the two always move
+    /// together.
+    pub dropped_frames: u32,
+}
+
+/// Number of frames averaged by [`GbaPeripherals::frame_stats()`]
+#[cfg(feature = "time")]
+const FRAME_STATS_WINDOW: usize = 60;
+
+/// Rolling record of frame times, in microseconds
+#[cfg(feature = "time")]
+#[derive(Default)]
+struct FrameTimings {
+    last: Option<Instant>,
+    /// Time from the last frame resolving to the next frame being started
+    #[cfg(feature = "frame-assert")]
+    last_work: Option<Duration>,
+    window: heapless::Deque<u32, FRAME_STATS_WINDOW>,
+    /// [`FrameEvents::dropped_frames`] when the stats were last reset
+    dropped_base: u32,
+}
+
+#[cfg(feature = "time")]
+impl FrameTimings {
+    /// Note that the game has finished its work for the frame at `now`
+    #[cfg(feature = "frame-assert")]
+    fn begin(&mut self, now: Instant) {
+        self.last_work = self.last.map(|last| now - last);
+    }
+
+    /// Record a frame that finished at `now`, returning its frame time
+    fn record(&mut self, now: Instant) -> Duration {
+        let mut frame_time = Duration::from_micros(display::VBLANK_INTERVAL_US);
+
+        if let Some(last) = self.last {
+            frame_time = now - last;
+            let micros = frame_time.as_micros().min(u32::MAX as u64) as u32;
+            if self.window.is_full() {
+                self.window.pop_front();
+            }
+            let _ = self.window.push_back(micros);
+        }
+        self.last = Some(now);
+
+        frame_time
+    }
+
+    /// Stats for the current window, given the peripherals' total of missed VBlanks
+    fn stats(&self, dropped_frames: u32) -> FrameStats {
+        let total: u64 = self.window.iter().map(|&micros| micros as u64).sum();
+        let worst = self.window.iter().copied().max().unwrap_or(0);
+
+        FrameStats {
+            avg_frame_time: Duration::from_micros(total / self.window.len().max(1) as u64),
+            worst_frame_time: Duration::from_micros(worst as u64),
+            dropped_frames: dropped_frames.saturating_sub(self.dropped_base),
+        }
+    }
 }
 
 /// High-level peripheral wrapper with automatic frame handling
@@ -390,6 +616,14 @@ pub struct GbaPeripherals<'a> {
     pub input: input::AsyncInput,
     frame_count: u32,
     prev_button_state: u16,
+    /// VBlank the previous frame resolved on
+    last_vblank: Option<usize>,
+    dropped_frames: u32,
+    /// Audio for the current frame was mixed by a frame wait that didn't reach VBlank,
+    /// either a `wait_frame_or_button()` that returned early or a dropped future
+    mixed_ahead: bool,
+    #[cfg(feature = "time")]
+    timings: FrameTimings,
 }
 
 impl<'a> GbaPeripherals<'a> {
@@ -404,16 +638,71 @@ impl<'a> GbaPeripherals<'a> {
             input: input::AsyncInput::with_config(input_config),
             frame_count: 0,
             prev_button_state: 0,
+            last_vblank: None,
+            dropped_frames: 0,
+            mixed_ahead: false,
+            #[cfg(feature = "time")]
+            timings: FrameTimings::default(),
         }
     }
 
+    /// Move the display out, e.g. to hand it to a dedicated render task
+    ///
+    /// The returned display owns the graphics, while [`display`](Self::display) stays
+    /// behind so [`wait_frame()`](Self::wait_frame) keeps working. After this, only use
+    /// `peripherals.display` for waiting (VBlank, HBlank, scanlines): its
+    /// [`frame()`](display::AsyncDisplay::frame) and
+    /// [`graphics()`](display::AsyncDisplay::graphics) panic, and register effects such
+    /// as fades would fight with the render task.
+    ///
+    /// The display borrows the [`InitializedGba`] rather than these peripherals, so
+    /// mixer and input remain usable in the main loop. Spawning a task with it requires a
+    /// `'static` borrow, e.g. by keeping the `InitializedGba` in a `StaticCell`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the display was already taken.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use embassy_agb::display::AsyncDisplay;
+    /// use embassy_agb::sync::Channel;
+    ///
+    /// static PLAYER_X: Channel<i32, 1> = Channel::new();
+    ///
+    /// #[embassy_agb::task]
+    /// async fn render(mut display: AsyncDisplay<'static>) {
+    ///     loop {
+    ///         let x = PLAYER_X.receive().await;
+    ///         let mut frame = display.frame().await;
+    ///         // Draw the player at x
+    ///         frame.commit();
+    ///     }
+    /// }
+    ///
+    /// # async fn example(spawner: embassy_agb::Spawner, mut peripherals: embassy_agb::GbaPeripherals<'static>) {
+    /// spawner.must_spawn(render(peripherals.take_display()));
+    /// loop {
+    ///     let events = peripherals.wait_frame().await;
+    ///     # let x = 0;
+    ///     PLAYER_X.try_send(x).ok();
+    /// }
+    /// # }
+    /// ```
+    pub fn take_display(&mut self) -> display::AsyncDisplay<'a> {
+        self.display
+            .take()
+            .expect("display already taken with take_display()")
+    }
+
     /// Wait for the next frame, automatically handling all per-frame updates
     ///
     /// This method:
     /// 1. Updates input state (detects button changes)
     /// 2. Processes one frame of audio mixing
     /// 3. Waits for VBlank (~16.7ms at 60Hz)
-    /// 4. Returns frame events (button changes, frame count, etc.)
+    /// 4. Returns frame events (button changes, held buttons, frame count, etc.)
     ///
     /// Call this once per frame in your game loop.
     ///
@@ -434,10 +723,186 @@ impl<'a> GbaPeripherals<'a> {
     /// # }
     /// ```
     pub async fn wait_frame(&mut self) -> FrameEvents {
-        self.input.update();
+        self.wait_frame_with(FrameOptions::default()).await
+    }
+
+    /// Wait for the next frame, choosing which per-frame updates to run
+    ///
+    /// Like [`wait_frame()`](Self::wait_frame), but `options` can skip audio mixing or
+    /// the input update for this frame. When input isn't updated, the returned events
+    /// report no presses or releases and the same held buttons as the previous frame.
+    ///
+    /// # Skipping audio mixing
+    ///
+    /// The sound hardware keeps replaying its last two mixed buffers while
+    /// [`AsyncMixer::frame()`](sound::AsyncMixer::frame) isn't called, so skipping it
+    /// with sound playing gives a buzzing loop. Stop all sounds and keep mixing for two
+    /// more frames so both buffers hold silence, then skip mixing for as long as needed.
+    /// Mixing can resume at any time without a glitch.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// use embassy_agb::FrameOptions;
+    ///
+    /// // Entering a silent pause menu
+    /// peripherals.mixer.stop_all();
+    /// peripherals.wait_frame().await;
+    /// peripherals.wait_frame().await;
+    ///
+    /// let menu_frame = FrameOptions {
+    ///     mix_audio: false,
+    ///     ..Default::default()
+    /// };
+    /// loop {
+    ///     let events = peripherals.wait_frame_with(menu_frame).await;
+    ///     if events.is_pressed(agb::input::Button::START) {
+    ///         break;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_frame_with(&mut self, options: FrameOptions) -> FrameEvents {
+        let events = self.begin_frame(options);
+        self.display.wait_for_vblank().await;
+        self.end_frame(events)
+    }
+
+    /// Wait `n` frames, running the per-frame updates of each
+    ///
+    /// The right way to pause the game loop, e.g. for 30 frames after the player is hit.
+    /// Each frame goes through [`wait_frame()`](Self::wait_frame), so audio keeps being
+    /// mixed and input keeps being updated. A plain
+    /// [`Timer::after()`](embassy_time::Timer::after) in the game loop would skip those
+    /// updates for its whole duration: sound buzzes as the hardware replays its last
+    /// buffers, and presses and releases in between are never seen.
+    ///
+    /// Returns the events of the last frame, with the presses and releases of all `n`
+    /// frames combined. With `n` of 0 it returns straight away, reporting no presses or
+    /// releases.
+    ///
+    /// Dropping the future part way, e.g. in a `select` with a cutscene skip, is safe:
+    /// the frames already waited were fully processed, and the next frame wait won't mix
+    /// the interrupted frame's audio a second time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// // Hold the "Stage clear" banner for half a second, music still playing
+    /// let events = peripherals.delay_frames(30).await;
+    /// if events.is_pressed(agb::input::Button::START) {
+    ///     // Start was pressed at some point during the pause
+    /// }
+    /// # }
+    /// ```
+    pub async fn delay_frames(&mut self, n: u32) -> FrameEvents {
+        let mut events = FrameEvents {
+            held: self.prev_button_state,
+            frame_count: self.frame_count,
+            dropped_frames: self.dropped_frames,
+            ..Default::default()
+        };
+
+        for _ in 0..n {
+            let frame = self.wait_frame().await;
+            events = FrameEvents {
+                pressed: events.pressed | frame.pressed,
+                released: events.released | frame.released,
+                ..frame
+            };
+        }
+        events
+    }
+
+    /// Wait for the next frame, or return early when one of `buttons` is pressed
+    ///
+    /// Runs the same per-frame updates as [`wait_frame()`](Self::wait_frame), then
+    /// returns [`FrameOutcome::Button`] straight away if one of `buttons` was pressed
+    /// since the last frame, or as soon as one is pressed while waiting for VBlank. This
+    /// lets menus react within a poll cycle instead of on the next frame. Without
+    /// [`enable_input_polling`], presses are only seen at the start of each call.
+    ///
+    /// An early return doesn't end the frame: the frame counter isn't advanced and the
+    /// next `wait_frame()` waits for the same VBlank without mixing audio again. The
+    /// press is reported once, so it won't show up as pressed in the next frame's events.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// use agb::input::Button;
+    /// use embassy_agb::FrameOutcome;
+    ///
+    /// loop {
+    ///     match peripherals.wait_frame_or_button(Button::A | Button::B).await {
+    ///         FrameOutcome::Frame(events) => {
+    ///             // Animate the menu cursor
+    ///         }
+    ///         FrameOutcome::Button { pressed, .. } => {
+    ///             if pressed.contains(Button::A) {
+    ///                 // Confirm selection
+    ///             }
+    ///             # break;
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_frame_or_button(&mut self, buttons: agb::input::Button) -> FrameOutcome {
+        use agb::input::Button;
+        use embassy_futures::select::{select, Either};
+
+        let mut events = self.begin_frame(FrameOptions::default());
+
+        let already_pressed = Button::from_bits_truncate(events.pressed as u32) & buttons;
+        let pressed = if !already_pressed.is_empty() {
+            already_pressed
+        } else {
+            match select(
+                self.display.wait_for_vblank(),
+                self.input.wait_for_any_of(buttons),
+            )
+            .await
+            {
+                Either::First(()) => return FrameOutcome::Frame(self.end_frame(events)),
+                Either::Second(pressed) => pressed,
+            }
+        };
 
-        // Get current button state as raw bits
-        let current_state = self.input.button_state_bits();
+        // Report the press now and treat the buttons as held from here on
+        let bits = pressed.bits() as u16;
+        events.pressed |= bits;
+        events.held |= bits;
+        self.prev_button_state |= bits;
+
+        #[cfg(feature = "time")]
+        if let Some(last) = self.timings.last {
+            events.frame_time = Instant::now() - last;
+        }
+
+        FrameOutcome::Button { pressed, events }
+    }
+
+    /// Work done before waiting for VBlank: dropped frame accounting, input and mixing
+    fn begin_frame(&mut self, options: FrameOptions) -> FrameEvents {
+        #[cfg(feature = "frame-assert")]
+        self.timings.begin(Instant::now());
+
+        // Any VBlank since the last frame resolved went by without a frame being ready
+        if let Some(last) = self.last_vblank {
+            let missed = display::vblank_count().wrapping_sub(last) as u32;
+            self.dropped_frames = self.dropped_frames.saturating_add(missed);
+        }
+
+        let current_state = if options.update_input {
+            self.input.update();
+            // Get current button state as raw bits
+            self.input.button_state_bits()
+        } else {
+            self.prev_button_state
+        };
 
         // Calculate button changes
         let pressed = current_state & !self.prev_button_state;
@@ -445,25 +910,171 @@ impl<'a> GbaPeripherals<'a> {
 
         self.prev_button_state = current_state;
 
-        self.mixer.frame();
-        self.display.wait_for_vblank().await;
+        // A frame cut short before its VBlank already mixed its audio
+        let mixed_ahead = core::mem::take(&mut self.mixed_ahead);
+        if !options.mix_audio {
+            self.mixer.skip_frame();
+        } else if !mixed_ahead {
+            self.mixer.frame();
+        }
+        // Cleared by end_frame(), so stays set if this frame's VBlank wait is cut short
+        self.mixed_ahead = options.mix_audio;
 
-        let events = FrameEvents {
+        FrameEvents {
             pressed,
             released,
+            held: current_state,
             frame_count: self.frame_count,
-        };
+            dropped_frames: self.dropped_frames,
+            #[cfg(feature = "time")]
+            frame_time: Duration::from_micros(display::VBLANK_INTERVAL_US),
+        }
+    }
 
+    /// Bookkeeping once the frame's VBlank has arrived
+    fn end_frame(&mut self, events: FrameEvents) -> FrameEvents {
+        self.mixed_ahead = false;
+        self.last_vblank = Some(display::vblank_count());
         self.frame_count = self.frame_count.wrapping_add(1);
 
-        events
+        FrameEvents {
+            #[cfg(feature = "time")]
+            frame_time: self.timings.record(Instant::now()),
+            ..events
+        }
     }
 
-    /// Play a sound effect with default priority
+    /// Change the input configuration, e.g. a lower poll rate in menus
+    ///
+    /// Updates [`input`](Self::input) through
+    /// [`AsyncInput::set_config()`](input::AsyncInput::set_config). If automatic polling
+    /// was started with [`enable_input_polling`], the running task adopts the new poll
+    /// rate on its next tick instead of needing a respawn.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// use embassy_agb::input::{InputConfig, PollingRate};
+    ///
+    /// // Entering fast-paced action
+    /// peripherals.set_input_config(InputConfig::new(PollingRate::Hz120));
+    /// # }
+    /// ```
+    pub fn set_input_config(&mut self, config: input::InputConfig) {
+        self.input.set_config(config);
+    }
+
+    /// Frame timing statistics gathered by [`wait_frame()`](Self::wait_frame)
+    ///
+    /// Frame times are measured between successive `wait_frame()` calls returning, so a
+    /// steady 60Hz game reports an average of about 16.7ms and no dropped frames. The
+    /// average and worst times cover the last 60 frames. Dropped frames are the missed
+    /// VBlanks also reported in [`FrameEvents::dropped_frames`], counted from the last
+    /// [`reset_stats()`](Self::reset_stats).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// let events = peripherals.wait_frame().await;
+    ///
+    /// if events.frame_count % 60 == 0 {
+    ///     let stats = peripherals.frame_stats();
+    ///     if stats.dropped_frames > 0 {
+    ///         // Not holding 60Hz
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn frame_stats(&self) -> FrameStats {
+        self.timings.stats(self.dropped_frames)
+    }
+
+    /// Panic if the previous frame's work took longer than `max`
+    ///
+    /// The work is the time from one [`wait_frame()`](Self::wait_frame) returning to the
+    /// next one being called, i.e. the game logic and drawing, excluding the wait for
+    /// VBlank. Call this right after `wait_frame()` to catch a frame that overran its
+    /// budget the moment it happens, rather than noticing the game quietly dropping to
+    /// 30Hz. Does nothing before the second frame.
+    ///
+    /// Only checks anything with the `frame-assert` feature. Without it this compiles to
+    /// nothing, so enable the feature for development builds and leave the calls in.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+    /// use embassy_agb::Duration;
+    ///
+    /// loop {
+    ///     peripherals.wait_frame().await;
+    ///     // Leave a few milliseconds of headroom below 16.7ms
+    ///     peripherals.assert_frame_budget(Duration::from_millis(14));
+    ///
+    ///     // Game logic and drawing
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    #[inline(always)]
+    pub fn assert_frame_budget(&self, max: Duration) {
+        #[cfg(feature = "frame-assert")]
+        if let Some(work) = self.timings.last_work {
+            assert!(
+                work <= max,
+                "frame {} over budget: work took {}us, budget is {}us",
+                self.frame_count.wrapping_sub(1),
+                work.as_micros(),
+                max.as_micros()
+            );
+        }
+        #[cfg(not(feature = "frame-assert"))]
+        let _ = max;
+    }
+
+    /// Clear the frame timing statistics, e.g. when switching scenes
+    #[cfg(feature = "time")]
+    pub fn reset_stats(&mut self) {
+        self.timings = FrameTimings {
+            dropped_base: self.dropped_frames,
+            ..FrameTimings::default()
+        };
+    }
+
+    /// Play a sound effect at the given priority
     ///
     /// Convenience method that creates a `SoundChannel` and plays it through the mixer.
-    /// Returns `Ok(channel_id)` if the sound starts playing, or `Err(SoundError)`
-    /// if all channels are busy.
+    /// Returns `Ok(sound_id)` if the sound starts playing, or `Err(SoundError)`
+    /// if all channels are busy and none can be replaced, see [`sound::SoundPriority`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::include_wav;
+    /// # static HIT: agb::sound::mixer::SoundData = include_wav!("hit.wav");
+    /// use embassy_agb::sound::SoundPriority;
+    ///
+    /// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>, is_boss: bool) {
+    /// // Hits on the boss must always be heard
+    /// let priority = if is_boss { SoundPriority::High } else { SoundPriority::Low };
+    /// peripherals.play_sound_with(&HIT, priority);
+    /// # }
+    /// ```
+    pub fn play_sound_with(
+        &mut self,
+        sound: &'static agb::sound::mixer::SoundData,
+        priority: sound::SoundPriority,
+    ) -> Result<sound::SoundId, sound::SoundError> {
+        self.mixer.play_sound(priority.channel(*sound))
+    }
+
+    /// Play a sound effect with default priority
+    ///
+    /// Same as [`play_sound_with()`](Self::play_sound_with) with
+    /// [`SoundPriority::Low`](sound::SoundPriority::Low).
     ///
     /// # Example
     ///
@@ -481,15 +1092,16 @@ impl<'a> GbaPeripherals<'a> {
     pub fn play_sound(
         &mut self,
         sound: &'static agb::sound::mixer::SoundData,
-    ) -> Result<agb::sound::mixer::ChannelId, sound::SoundError> {
-        let channel = agb::sound::mixer::SoundChannel::new(*sound);
-        self.mixer.play_sound(channel)
+    ) -> Result<sound::SoundId, sound::SoundError> {
+        self.play_sound_with(sound, sound::SoundPriority::Low)
     }
 
     /// Play a sound effect with high priority
     ///
     /// High priority sounds will replace low priority sounds if all channels are busy.
     /// Use this for important sounds like background music or critical sound effects.
+    /// Same as [`play_sound_with()`](Self::play_sound_with) with
+    /// [`SoundPriority::High`](sound::SoundPriority::High).
     ///
     /// # Example
     ///
@@ -504,17 +1116,188 @@ impl<'a> GbaPeripherals<'a> {
     pub fn play_sound_high_priority(
         &mut self,
         sound: &'static agb::sound::mixer::SoundData,
-    ) -> Result<agb::sound::mixer::ChannelId, sound::SoundError> {
-        let channel = agb::sound::mixer::SoundChannel::new_high_priority(*sound);
-        self.mixer.play_sound(channel)
+    ) -> Result<sound::SoundId, sound::SoundError> {
+        self.play_sound_with(sound, sound::SoundPriority::High)
+    }
+}
+
+/// Change the time driver's precision while running
+///
+/// Stops the time driver's timer, reprograms it to overflow every `overflow_amount`
+/// counts of its 65.536kHz clock and restarts it. Time accumulated so far is carried
+/// over, so [`Instant::now()`] never goes backwards across the change and pending timers
+/// still fire on time (to the new precision).
+///
+/// At 65.536kHz: 4=~61μs, 16=~244μs, 64=~1ms (default), 256=~4ms, 1024=~16ms. Lower
+/// values give finer timer resolution at the cost of more interrupts per second.
+///
+/// Does nothing with the `time-driver-vblank` driver, whose precision is fixed at one
+/// frame.
+///
+/// # Panics
+///
+/// Panics if `overflow_amount` is 0.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// // Fine resolution while benchmarking
+/// embassy_agb::reconfigure_timer(4);
+/// // ... run the benchmark
+/// // Back to the default ~1ms ticks for normal play
+/// embassy_agb::reconfigure_timer(64);
+/// ```
+#[cfg(feature = "_time-driver")]
+pub fn reconfigure_timer(overflow_amount: u16) {
+    time_driver::reconfigure(overflow_amount);
+}
+
+/// Time driver interrupt statistics returned by [`time_driver_stats()`]
+#[cfg(feature = "_time-driver")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimerStats {
+    /// Timer interrupts handled since boot, or VBlanks with `time-driver-vblank`
+    pub interrupts: u64,
+    /// Counts of the 65.536kHz clock between interrupts, as set by
+    /// [`reconfigure_timer()`]; 0 in cascade mode, where the timer only interrupts when
+    /// its 32-bit count wraps, and with `time-driver-vblank`, which uses no timer
+    pub overflow_amount: u16,
+}
+
+/// Read how many interrupts the time driver has taken
+///
+/// Each interrupt costs a little CPU time, and their rate is set by the overflow
+/// amount: 65536 / `overflow_amount` per second. Comparing the count against
+/// [`monotonic_ticks()`] over a stretch of play shows the actual rate, to weigh timer
+/// precision against overhead when picking a value for [`reconfigure_timer()`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// let before = embassy_agb::time_driver_stats();
+/// let start = embassy_agb::monotonic_ticks();
+/// // ... play for a while
+/// let after = embassy_agb::time_driver_stats();
+/// let seconds = (embassy_agb::monotonic_ticks() - start) / embassy_agb::time::TICK_HZ;
+/// let per_second = (after.interrupts - before.interrupts) / seconds.max(1);
+/// ```
+#[cfg(feature = "_time-driver")]
+pub fn time_driver_stats() -> TimerStats {
+    time_driver::stats()
+}
+
+/// Read the time driver's monotonic tick counter
+///
+/// Returns ticks since the executor started, at `embassy_time::TICK_HZ` (32.768kHz
+/// unless another `tick-hz-*` rate was picked), the same value [`Instant::now()`]
+/// wraps. Handy as a cheap timestamp for profiling without going through the
+/// `embassy-time` types.
+///
+/// The counter is a `u64`, so at the default rate it won't overflow for millions of
+/// years; differences can be taken with plain subtraction. It reads 0 until the time driver has started.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// let start = embassy_agb::monotonic_ticks();
+/// // ... work to measure
+/// let micros = (embassy_agb::monotonic_ticks() - start) * 1_000_000 / embassy_agb::time::TICK_HZ;
+/// ```
+#[cfg(feature = "_time-driver")]
+pub fn monotonic_ticks() -> u64 {
+    time_driver::now()
+}
+
+/// Run `future`, giving up with [`TimeoutError`] if it hasn't completed after `timeout`
+///
+/// The deadline is tracked by the embassy-agb time driver, so nothing is polled while
+/// waiting and the CPU can halt. If the timeout wins, `future` is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use agb::input::Button;
+/// use embassy_agb::{with_timeout, Duration};
+///
+/// # async fn example(input: &embassy_agb::input::AsyncInput) {
+/// // "Press A to continue", skipping ahead after 3 seconds
+/// match with_timeout(Duration::from_secs(3), input.wait_for_button_press(Button::A)).await {
+///     Ok(_) => { /* player pressed A */ }
+///     Err(_) => { /* timed out */ }
+/// }
+/// # }
+/// ```
+#[cfg(feature = "time")]
+pub async fn with_timeout<F: core::future::Future>(
+    timeout: Duration,
+    future: F,
+) -> Result<F::Output, TimeoutError> {
+    embassy_time::with_timeout(timeout, future).await
+}
+
+/// Create a [`YieldEvery`] that yields to the executor once every `n` iterations
+///
+/// A long loop that never awaits keeps every other task waiting: the input polling task,
+/// audio, and anything else that woke up. Calling [`YieldEvery::tick()`] in the loop
+/// hands control back to the executor every `n` iterations so those tasks get polled,
+/// without paying for a yield on every iteration. Use [`yield_now()`] to yield once.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(map: &mut [u8]) {
+/// // Generate a big map without freezing input or audio
+/// let mut yielder = embassy_agb::yield_every(64);
+/// for tile in map.iter_mut() {
+///     *tile = 0; // Expensive generation step
+///     yielder.tick().await;
+/// }
+/// # }
+/// ```
+pub fn yield_every(n: u32) -> YieldEvery {
+    YieldEvery {
+        every: n.max(1),
+        count: 0,
+    }
+}
+
+/// Yields to the executor every few iterations, see [`yield_every()`]
+#[derive(Debug, Clone)]
+pub struct YieldEvery {
+    every: u32,
+    count: u32,
+}
+
+impl YieldEvery {
+    /// Count one iteration, yielding if it is the `n`th since the last yield
+    pub async fn tick(&mut self) {
+        self.count += 1;
+        if self.count >= self.every {
+            self.count = 0;
+            yield_now().await;
+        }
     }
 }
 
 /// Enable automatic input polling with the given polling rate.
 ///
-/// This function should be called once at startup to automatically spawn
-/// the input polling task. If not called, input methods will still work
-/// but will use polling-based approach instead of interrupt-driven.
+/// Call this at startup to spawn the input polling task. If not called, input methods
+/// will still work but will use polling-based approach instead of interrupt-driven.
+///
+/// ## Lifecycle
+///
+/// - The first call spawns the polling task.
+/// - Calling it again while the task runs doesn't spawn a second one; the running task
+///   switches to `rate` instead. Only the rate changes: auto-repeat timing set with
+///   [`GbaPeripherals::set_input_config()`] or
+///   [`AsyncInput::set_config()`](input::AsyncInput::set_config) is kept.
+/// - [`disable_input_polling()`] stops the task at its next poll. Edge waits such as
+///   [`AsyncInput::wait_for_button_press()`](input::AsyncInput::wait_for_button_press)
+///   then only resolve through the keypad interrupt, if enabled.
+/// - Calling this after `disable_input_polling()` starts polling again, even if the old
+///   task hadn't exited yet.
+/// - If [`input_polling_task`](input::input_polling_task) was spawned directly, that
+///   task is reused and switches to `rate` as well, even if it hasn't started yet.
 ///
 /// # Example
 ///
@@ -534,6 +1317,21 @@ impl<'a> GbaPeripherals<'a> {
 /// ```
 #[cfg(all(feature = "time", feature = "executor"))]
 pub fn enable_input_polling(spawner: &Spawner, rate: input::PollingRate) {
-    let config = input::InputConfig::from(rate);
-    spawner.must_spawn(input::input_polling_task(config));
+    input::start_polling(spawner, rate);
+}
+
+/// Stop the input polling task started by [`enable_input_polling()`]
+///
+/// The task exits at its next poll, e.g. to save power on a screen driven by the keypad
+/// interrupt (see [`AsyncInput::enable_interrupt()`](input::AsyncInput::enable_interrupt)).
+/// Does nothing if polling isn't running.
+#[cfg(all(feature = "time", feature = "executor"))]
+pub fn disable_input_polling() {
+    input::stop_polling();
+}
+
+/// Whether input polling is running, see [`enable_input_polling()`]
+#[cfg(all(feature = "time", feature = "executor"))]
+pub fn is_input_polling_enabled() -> bool {
+    input::is_polling()
 }
@@ -0,0 +1,210 @@
+//! Async link cable communication in multiplayer mode
+//!
+//! In multiplayer mode every connected GBA exchanges one 16-bit word per transfer. The
+//! parent (player 0, the console with the small plug in) starts each transfer; the
+//! children just set up their word and wait. All consoles get the serial interrupt
+//! when the transfer completes.
+//!
+//! ## Registers
+//! - `SIOMULTI0-3` (0x4000120-0x4000126): word received from each player (0xFFFF if absent)
+//! - `SIOCNT` (0x4000128): baud rate (bits 0-1), SI (bit 2, 0 = parent), SD (bit 3,
+//!   1 = all ready), player ID (bits 4-5), error (bit 6), start/busy (bit 7),
+//!   mode (bits 12-13), IRQ enable (bit 14)
+//! - `SIOMLT_SEND` (0x400012A): word to send in the next transfer
+//! - `RCNT` (0x4000134): bit 15 clear selects the SIO modes
+//! - `IE` (0x4000200): bit 7 for the serial interrupt
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use portable_atomic::{AtomicUsize, Ordering};
+
+use agb::interrupt::{add_interrupt_handler, Interrupt, InterruptHandler};
+use embassy_sync::waitqueue::AtomicWaker;
+
+/// Received words, one register per player
+const SIO_MULTI: *const u16 = 0x0400_0120 as *const u16;
+/// Serial control register
+const SIO_CONTROL: *mut u16 = 0x0400_0128 as *mut u16;
+/// Outgoing word register
+const SIO_SEND: *mut u16 = 0x0400_012A as *mut u16;
+/// Serial mode select register
+const SIO_MODE: *mut u16 = 0x0400_0134 as *mut u16;
+
+/// SIOCNT bit: 0 on the parent
+const SIOCNT_SI: u16 = 1 << 2;
+/// SIOCNT bit: set when all connected consoles are ready
+const SIOCNT_SD: u16 = 1 << 3;
+/// SIOCNT bit: set when the last transfer failed
+const SIOCNT_ERROR: u16 = 1 << 6;
+/// SIOCNT bit: start a transfer (parent), set while one is in progress
+const SIOCNT_START: u16 = 1 << 7;
+/// SIOCNT mode bits for multiplayer mode
+const SIOCNT_MULTIPLAYER: u16 = 0b10 << 12;
+/// SIOCNT bit enabling the serial interrupt
+const SIOCNT_IRQ: u16 = 1 << 14;
+
+/// Number of completed transfers
+static TRANSFER_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Transfer completion waker
+static TRANSFER_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Link cable transfer speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkBaud {
+    /// 9600 bps
+    Bps9600,
+    /// 38400 bps
+    Bps38400,
+    /// 57600 bps
+    Bps57600,
+    /// 115200 bps (default)
+    #[default]
+    Bps115200,
+}
+
+/// Two-player link cable in multiplayer mode
+///
+/// Both consoles must use the same [`LinkBaud`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::link::{LinkBaud, LinkCable};
+///
+/// # async fn example(my_input: u16) {
+/// let mut link = LinkCable::new(LinkBaud::Bps115200);
+/// loop {
+///     // Exchange this frame's input with the other player
+///     link.send(my_input);
+///     let their_input = link.recv().await;
+///     # break;
+/// }
+/// # }
+/// ```
+pub struct LinkCable {
+    _handler: InterruptHandler,
+    /// Transfer count when the word for the next transfer was queued
+    queued_at: Option<usize>,
+    /// Whether the last [`send()`](Self::send) started a transfer as the parent
+    started: bool,
+}
+
+impl LinkCable {
+    /// Put the serial port in multiplayer mode at `baud`
+    pub fn new(baud: LinkBaud) -> Self {
+        let handler = unsafe {
+            add_interrupt_handler(Interrupt::Serial, |_| {
+                TRANSFER_COUNTER.fetch_add(1, Ordering::SeqCst);
+                TRANSFER_WAKER.wake();
+            })
+        };
+
+        unsafe {
+            SIO_MODE.write_volatile(0);
+            SIO_CONTROL.write_volatile(SIOCNT_MULTIPLAYER | SIOCNT_IRQ | baud as u16);
+        }
+
+        Self {
+            _handler: handler,
+            queued_at: None,
+            started: false,
+        }
+    }
+
+    /// Whether this console is the parent, which starts every transfer
+    pub fn is_parent(&self) -> bool {
+        control() & SIOCNT_SI == 0
+    }
+
+    /// Whether every connected console is in multiplayer mode and ready
+    pub fn is_connected(&self) -> bool {
+        control() & SIOCNT_SD != 0
+    }
+
+    /// This console's player number (0-3), valid once a transfer has completed
+    pub fn player_id(&self) -> u8 {
+        ((control() >> 4) & 0b11) as u8
+    }
+
+    /// Queue `data` for the next transfer, starting it if this is the parent
+    ///
+    /// On a child the word goes out when the parent next starts a transfer. Pair each
+    /// `send` with a [`recv()`](Self::recv) to get the other player's word back.
+    pub fn send(&mut self, data: u16) {
+        self.queued_at = Some(TRANSFER_COUNTER.load(Ordering::SeqCst));
+
+        unsafe { SIO_SEND.write_volatile(data) };
+        self.started = self.is_parent() && self.is_connected();
+        if self.started {
+            unsafe { SIO_CONTROL.write_volatile(control() | SIOCNT_START) };
+        }
+    }
+
+    /// Wait for the next transfer to complete and return the other player's word
+    ///
+    /// Completes on the serial interrupt. On a child this waits for the parent to start
+    /// the next transfer. Returns 0xFFFF straight away if the other console isn't
+    /// connected, or on the parent if no [`send()`](Self::send) started a transfer, and
+    /// 0xFFFF if the transfer failed.
+    pub async fn recv(&mut self) -> u16 {
+        let queued_at = self.queued_at.take();
+        let started = core::mem::take(&mut self.started);
+
+        // No serial interrupt would ever come to end the wait
+        if !self.is_connected() || (self.is_parent() && !started) {
+            return 0xFFFF;
+        }
+
+        let queued_at = queued_at.unwrap_or_else(|| TRANSFER_COUNTER.load(Ordering::SeqCst));
+        TransferFuture { queued_at }.await;
+
+        if control() & SIOCNT_ERROR != 0 {
+            return 0xFFFF;
+        }
+        let other = if self.player_id() == 0 { 1 } else { 0 };
+        unsafe { SIO_MULTI.add(other).read_volatile() }
+    }
+}
+
+impl Drop for LinkCable {
+    fn drop(&mut self) {
+        unsafe { SIO_CONTROL.write_volatile(control() & !SIOCNT_IRQ) };
+    }
+}
+
+/// Current value of SIOCNT
+fn control() -> u16 {
+    unsafe { SIO_CONTROL.read_volatile() }
+}
+
+/// Future that completes once a transfer finishes after `queued_at`
+struct TransferFuture {
+    queued_at: usize,
+}
+
+impl TransferFuture {
+    fn is_done(&self) -> bool {
+        TRANSFER_COUNTER.load(Ordering::SeqCst) != self.queued_at
+    }
+}
+
+impl Future for TransferFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.is_done() {
+            return Poll::Ready(());
+        }
+
+        TRANSFER_WAKER.register(cx.waker());
+
+        // Check again in case the transfer finished before the waker was registered
+        if self.is_done() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
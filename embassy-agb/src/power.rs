@@ -0,0 +1,292 @@
+//! Idling, Stop mode deep sleep and BIOS resets
+//!
+//! [`wait_for_interrupt()`] halts the CPU until the next interrupt, the same Halt the
+//! executor uses when idle, for loops that run outside the executor.
+//!
+//! Stop mode powers down the CPU, LCD, sound and timers until a keypad, serial or
+//! Game Pak interrupt arrives. It saves far more power than the Halt the executor uses
+//! when idle, but nothing runs while stopped.
+//!
+//! [`soft_reset()`] and [`reset_to_main()`] restart the game from its entry point. The
+//! executor, its tasks and every `static` live in RAM, so both clear IWRAM and EWRAM
+//! through the BIOS first: agb's startup code copies initialised statics back but never
+//! zeroes the rest, and a task left marked as spawned couldn't be spawned again.
+//!
+//! ## Registers
+//! - `DISPCNT` (0x4000000): bit 7 forces a blank screen while stopped
+//! - `KEYINPUT` (0x4000130): current button state, 0 = pressed
+//! - `KEYCNT` (0x4000132): bits 0-9 select wake buttons, bit 14 enables the IRQ
+//! - `IE` (0x4000200): bit 12 for the keypad interrupt
+//! - `IF` (0x4000202): pending interrupts, acknowledged before [`reset_to_main()`]
+//! - `IME` (0x4000208): master interrupt enable, cleared before a reset and by
+//!   critical sections; Halt ends on `IE & IF` whatever its value
+//! - `TM{n}CNT_H` (0x4000102 + n*4) and `DMA{n}CNT_H` (0x40000BA + n*12): stopped by
+//!   [`reset_to_main()`]
+//! - `SOUNDCNT_X` (0x4000084): sound master enable, cleared by [`reset_to_main()`]
+
+use core::sync::atomic::{compiler_fence, Ordering};
+
+use agb::input::Button;
+use agb::interrupt::{add_interrupt_handler, Interrupt};
+
+/// Display control register (DISPCNT)
+const DISPLAY_CONTROL: *mut u16 = 0x0400_0000 as *mut u16;
+/// Keypad input register (KEYINPUT)
+const KEYPAD_INPUT: *const u16 = 0x0400_0130 as *const u16;
+/// Keypad interrupt control register (KEYCNT)
+const KEYPAD_CONTROL: *mut u16 = 0x0400_0132 as *mut u16;
+/// Interrupt enable register (IE)
+const INTERRUPT_ENABLE: *mut u16 = 0x0400_0200 as *mut u16;
+/// Interrupt request flags register (IF)
+const INTERRUPT_FLAGS: *mut u16 = 0x0400_0202 as *mut u16;
+/// Interrupt master enable register (IME)
+const INTERRUPT_MASTER_ENABLE: *mut u16 = 0x0400_0208 as *mut u16;
+/// Timer 0 control register (TM0CNT_H), the others follow every 4 bytes
+const TIMER_CONTROL: *mut u16 = 0x0400_0102 as *mut u16;
+/// DMA 0 control register (DMA0CNT_H), the others follow every 12 bytes
+const DMA_CONTROL: *mut u16 = 0x0400_00BA as *mut u16;
+/// Sound master control register (SOUNDCNT_X)
+const SOUND_MASTER_CONTROL: *mut u16 = 0x0400_0084 as *mut u16;
+/// Object attribute memory, 128 objects of 8 bytes
+const OAM: *mut u16 = 0x0700_0000 as *mut u16;
+/// Object attribute 0 bit that disables (hides) a regular object
+const OBJ_ATTR0_DISABLE: u16 = 1 << 9;
+/// BIOS flag choosing where SoftReset restarts: 0 for the cartridge, else EWRAM
+const RESET_RETURN_ADDRESS_FLAG: *mut u8 = 0x0300_7FFA as *mut u8;
+
+/// DISPCNT forced blank bit
+const DISPCNT_FORCED_BLANK: u16 = 1 << 7;
+/// KEYCNT bit enabling the keypad interrupt (logical OR of the selected buttons)
+const KEYCNT_IRQ_ENABLE: u16 = 1 << 14;
+/// Bits of KEYINPUT/KEYCNT that correspond to buttons
+const KEYPAD_MASK: u16 = 0x03FF;
+
+/// Currently pressed buttons as a bitmask
+fn pressed_buttons() -> u16 {
+    !unsafe { KEYPAD_INPUT.read_volatile() } & KEYPAD_MASK
+}
+
+/// BIOS Stop call number, shifted into the comment field for ARM-mode `swi`
+const SWI_STOP: u32 = if cfg!(target_feature = "thumb-mode") {
+    0x03
+} else {
+    0x03 << 16
+};
+
+/// Enter Stop mode through the BIOS
+fn stop() {
+    unsafe {
+        core::arch::asm!("swi {SWI}", SWI = const SWI_STOP, clobber_abi("C"));
+    }
+}
+
+/// BIOS RegisterRamReset call number, shifted into the comment field for ARM-mode `swi`
+const SWI_REGISTER_RAM_RESET: u32 = if cfg!(target_feature = "thumb-mode") {
+    0x01
+} else {
+    0x01 << 16
+};
+
+/// RegisterRamReset flags for EWRAM and IWRAM (except the BIOS area at its top)
+const RESET_RAM: u32 = 0b0000_0011;
+/// RegisterRamReset flags for palette RAM, VRAM and OAM
+const RESET_VIDEO_MEMORY: u32 = 0b0001_1100;
+/// RegisterRamReset flags for the serial, sound and all other I/O registers
+const RESET_REGISTERS: u32 = 0b1110_0000;
+
+/// Clear what `flags` selects with RegisterRamReset, then restart with SoftReset
+///
+/// RegisterRamReset wipes the stack, so both calls share one `asm!` block that never
+/// touches memory in between.
+fn reset(flags: u32) -> ! {
+    unsafe {
+        INTERRUPT_MASTER_ENABLE.write_volatile(0);
+        // Restart from the cartridge rather than EWRAM
+        RESET_RETURN_ADDRESS_FLAG.write_volatile(0);
+        // SoftReset is call number 0 in both ARM and Thumb mode
+        core::arch::asm!(
+            "swi {RESET}",
+            "swi 0",
+            RESET = const SWI_REGISTER_RAM_RESET,
+            in("r0") flags,
+            options(noreturn),
+        );
+    }
+}
+
+/// Restart the game as if the console had been switched off and on
+///
+/// Clears all RAM, video memory and I/O registers through the BIOS, then restarts the
+/// cartridge from its entry point without the boot logo. Nothing in RAM survives; save
+/// memory (SRAM, Flash, EEPROM) and the cartridge RTC are untouched, so persist
+/// anything the restarted game needs there first.
+///
+/// Safe to call from anywhere, including interrupt handlers. Multiboot programs, which
+/// run from EWRAM, can't be restarted this way.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use agb::input::Button;
+///
+/// # fn example(input: &embassy_agb::input::AsyncInput) {
+/// // The classic A+B+Start+Select reset combo
+/// if input.is_combo_pressed(Button::A | Button::B | Button::START | Button::SELECT) {
+///     embassy_agb::soft_reset();
+/// }
+/// # }
+/// ```
+pub fn soft_reset() -> ! {
+    reset(RESET_RAM | RESET_VIDEO_MEMORY | RESET_REGISTERS)
+}
+
+/// Stop all tasks and run `main` again from the start, e.g. to return to the title screen
+///
+/// Tasks can't be torn down and respawned in place (the executor and task storage are
+/// statics), so this stops the hardware the game was using and restarts through the
+/// BIOS like [`soft_reset()`], keeping the picture on screen until `main` draws again:
+///
+/// 1. Interrupts are disabled and pending ones acknowledged
+/// 2. All timers and DMA channels are stopped, and sound is switched off
+/// 3. Every object in OAM is hidden
+/// 4. IWRAM and EWRAM are cleared, resetting the executor, its tasks and every `static`
+/// 5. The cartridge restarts from its entry point, running `main` again
+///
+/// Survives: save memory and the cartridge RTC, plus VRAM, palettes and the display
+/// registers, so background layers stay visible (sprites are hidden) until the
+/// restarted game reconfigures the display. Everything in RAM is lost.
+///
+/// Multiboot programs, which run from EWRAM, can't be restarted this way.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # let quit_to_title = true;
+/// // "Quit to title" picked in the pause menu
+/// if quit_to_title {
+///     embassy_agb::reset_to_main();
+/// }
+/// ```
+pub fn reset_to_main() -> ! {
+    unsafe {
+        INTERRUPT_MASTER_ENABLE.write_volatile(0);
+        INTERRUPT_ENABLE.write_volatile(0);
+        INTERRUPT_FLAGS.write_volatile(0xFFFF);
+
+        for n in 0..4 {
+            TIMER_CONTROL.add(n * 2).write_volatile(0);
+            DMA_CONTROL.add(n * 6).write_volatile(0);
+        }
+        SOUND_MASTER_CONTROL.write_volatile(0);
+
+        for object in 0..128 {
+            OAM.add(object * 4).write_volatile(OBJ_ATTR0_DISABLE);
+        }
+    }
+
+    reset(RESET_RAM)
+}
+
+/// Enter Stop mode until one of the `wake` buttons is pressed
+///
+/// Blocks the whole system: no tasks, interrupts or timers run while stopped, and
+/// [`Instant::now()`](crate::Instant::now) does not advance. The screen is blanked for
+/// the duration and restored afterwards. Stop any playing sound first, as the sound
+/// hardware is frozen mid-sample.
+///
+/// If a wake button is already held (e.g. the one that opened the pause screen), this
+/// first waits for it to be released so it doesn't wake the system straight away. Only
+/// the keypad interrupt is enabled while stopped, and the previous interrupt enables
+/// and keypad interrupt settings are restored before returning.
+///
+/// # Panics
+///
+/// Panics if `wake` contains no buttons.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use agb::input::Button;
+///
+/// // Sleep until Start is pressed
+/// embassy_agb::enter_stop_mode(Button::START);
+/// ```
+pub fn enter_stop_mode(wake: Button) {
+    let wake = wake.bits() as u16 & KEYPAD_MASK;
+    assert!(wake != 0, "enter_stop_mode needs at least one wake button");
+
+    while pressed_buttons() & wake != 0 {}
+
+    let saved_display = unsafe { DISPLAY_CONTROL.read_volatile() };
+    let saved_keypad = unsafe { KEYPAD_CONTROL.read_volatile() };
+    let saved_enable = unsafe { INTERRUPT_ENABLE.read_volatile() };
+
+    // Stop only returns once the interrupt has been serviced, so it needs a handler
+    let handler = unsafe { add_interrupt_handler(Interrupt::Keypad, |_| {}) };
+
+    unsafe {
+        DISPLAY_CONTROL.write_volatile(saved_display | DISPCNT_FORCED_BLANK);
+        KEYPAD_CONTROL.write_volatile(wake | KEYCNT_IRQ_ENABLE);
+        INTERRUPT_ENABLE.write_volatile(1 << Interrupt::Keypad as u16);
+    }
+
+    // Serial and Game Pak interrupts can also end Stop mode, so check what woke us
+    loop {
+        stop();
+        if pressed_buttons() & wake != 0 {
+            break;
+        }
+    }
+
+    drop(handler);
+
+    unsafe {
+        KEYPAD_CONTROL.write_volatile(saved_keypad);
+        INTERRUPT_ENABLE.write_volatile(saved_enable);
+        DISPLAY_CONTROL.write_volatile(saved_display);
+    }
+}
+
+/// Halt the CPU until an interrupt is requested
+///
+/// The safe way to idle in a hand-written loop: the display, timers and sound keep
+/// running, and the CPU wakes on the next enabled interrupt (VBlank, a timer, the
+/// keypad). Compiler fences on both sides keep the check of the wake condition before
+/// the halt and every read of what the interrupt handler wrote after it.
+///
+/// Fences alone can't stop an interrupt from firing between the check and the halt:
+/// its handler runs, sets the flag, and the CPU then halts waiting for an interrupt
+/// that has already happened. Do the check and the call inside a critical section.
+/// That clears `IME`, so the handler is held back, but Halt still ends on any enabled
+/// interrupt that is pending, and the handler runs once the critical section ends.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::agb::external::critical_section;
+/// use embassy_agb::agb::external::portable_atomic::{AtomicBool, Ordering};
+///
+/// static FRAME_READY: AtomicBool = AtomicBool::new(false);
+///
+/// # fn example() {
+/// // FRAME_READY is set by a VBlank handler
+/// loop {
+///     critical_section::with(|_| {
+///         if !FRAME_READY.load(Ordering::Relaxed) {
+///             embassy_agb::wait_for_interrupt();
+///         }
+///     });
+///
+///     if FRAME_READY.swap(false, Ordering::Relaxed) {
+///         // Draw the next frame
+///     }
+/// }
+/// # }
+/// ```
+pub fn wait_for_interrupt() {
+    // Finish every access before halting, including reading the wake condition
+    compiler_fence(Ordering::SeqCst);
+    agb::halt();
+    // Don't read anything an interrupt handler may have written until after the halt
+    compiler_fence(Ordering::SeqCst);
+}
@@ -0,0 +1,137 @@
+//! Random numbers for gameplay
+//!
+//! [`Rng`] is a PCG32 generator: small, fast and statistically good, but not
+//! cryptographic. [`Rng::new()`] seeds it from hardware timing so each playthrough
+//! differs, while [`Rng::with_seed()`] gives a fully deterministic sequence for replays
+//! and tests.
+
+/// Scanline counter register (VCOUNT)
+const VCOUNT: *const u16 = 0x0400_0006 as *const u16;
+
+/// PCG32 multiplier
+const PCG_MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+/// Pseudo-random number generator
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::rng::Rng;
+///
+/// let mut rng = Rng::new();
+/// let damage = rng.range(5, 10);
+/// if rng.bool() {
+///     // Critical hit
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+    increment: u64,
+    /// VBlank count entropy was last mixed in at, `None` when manually seeded
+    mixed_at: Option<usize>,
+}
+
+impl Rng {
+    /// Create a generator seeded from hardware timing
+    ///
+    /// The seed combines the time since boot (from the time driver when the `time`
+    /// feature is enabled), the VBlank count and the current scanline. Afterwards the
+    /// scanline is mixed in again at most once per frame, so the sequence also depends
+    /// on when in the frame numbers are drawn.
+    pub fn new() -> Self {
+        let mut rng = Self::with_seed(hardware_entropy());
+        rng.mixed_at = Some(crate::display::vblank_count());
+        rng
+    }
+
+    /// Create a deterministic generator: the same seed always gives the same sequence
+    pub fn with_seed(seed: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            // Any odd increment works; derive it from the seed so seeds pick distinct streams
+            increment: (seed.rotate_left(32) << 1) | 1,
+            mixed_at: None,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Mix extra entropy into the state, e.g. the frame a button was pressed on
+    ///
+    /// A manually seeded generator stays deterministic as long as the entropy does.
+    pub fn add_entropy(&mut self, entropy: u32) {
+        self.state ^= (entropy as u64).wrapping_mul(PCG_MULTIPLIER);
+        self.step();
+    }
+
+    /// Next random 32-bit value
+    pub fn next_u32(&mut self) -> u32 {
+        if let Some(mixed_at) = self.mixed_at {
+            let frame = crate::display::vblank_count();
+            if frame != mixed_at {
+                self.mixed_at = Some(frame);
+                self.add_entropy(unsafe { VCOUNT.read_volatile() } as u32);
+            }
+        }
+
+        let old = self.state;
+        self.step();
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    /// Random value in `min..max`, without modulo bias
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min >= max`.
+    pub fn range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min < max, "Rng::range needs min < max");
+        let span = max.wrapping_sub(min) as u32;
+
+        // Lemire's method: reject the few values that would make some results likelier
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let product = self.next_u32() as u64 * span as u64;
+            if product as u32 >= threshold {
+                return min.wrapping_add((product >> 32) as i32);
+            }
+        }
+    }
+
+    /// Random true or false with equal odds
+    pub fn bool(&mut self) -> bool {
+        self.next_u32() & (1 << 31) != 0
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Seed material from whatever timing state is available
+fn hardware_entropy() -> u64 {
+    #[cfg(feature = "time")]
+    let ticks = embassy_time::Instant::now().as_ticks();
+    #[cfg(not(feature = "time"))]
+    let ticks = 0;
+
+    let scanline = unsafe { VCOUNT.read_volatile() } as u64;
+    let frame = crate::display::vblank_count() as u64;
+
+    ticks ^ (frame << 32) ^ (scanline << 16)
+}
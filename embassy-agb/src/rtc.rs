@@ -0,0 +1,257 @@
+//! Cartridge real-time clock (RTC)
+//!
+//! Some cartridges carry a Seiko S-3511 compatible clock wired to the cartridge GPIO
+//! port, which keeps the date and time while the console is off. This is separate from
+//! the embassy time driver, which only measures time since boot.
+//!
+//! ## Registers
+//! - `GPIO data` (0x80000C4): bit 0 SCK, bit 1 SIO, bit 2 CS
+//! - `GPIO direction` (0x80000C6): 1 = output, per data bit
+//! - `GPIO control` (0x80000C8): bit 0 enables reading the port
+
+use core::cmp::Ordering;
+
+/// GPIO data register
+const GPIO_DATA: *mut u16 = 0x0800_00C4 as *mut u16;
+/// GPIO direction register
+const GPIO_DIRECTION: *mut u16 = 0x0800_00C6 as *mut u16;
+/// GPIO control register
+const GPIO_CONTROL: *mut u16 = 0x0800_00C8 as *mut u16;
+
+/// Serial clock line
+const SCK: u16 = 1 << 0;
+/// Serial data line
+const SIO: u16 = 1 << 1;
+/// Chip select line
+const CS: u16 = 1 << 2;
+
+/// Status register command
+const CMD_STATUS: u8 = 0x62;
+/// Date and time command
+const CMD_DATE_TIME: u8 = 0x64;
+/// Command bit selecting a read
+const CMD_READ: u8 = 1;
+
+/// Status bit: 24-hour mode
+const STATUS_24_HOUR: u8 = 1 << 6;
+/// Status bit: the clock lost power and its time is not valid
+const STATUS_POWER_LOST: u8 = 1 << 7;
+
+/// VBlanks between checks in [`Rtc::wait_until()`]
+const WAIT_POLL_FRAMES: u32 = 30;
+
+/// Error type for RTC operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcError {
+    /// The cartridge has no clock, or it returned garbage
+    NotPresent,
+    /// The clock lost power (e.g. its battery ran out) and doesn't know the time
+    PowerLost,
+}
+
+impl core::fmt::Display for RtcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RtcError::NotPresent => write!(f, "No real-time clock found"),
+            RtcError::PowerLost => write!(f, "Real-time clock lost power"),
+        }
+    }
+}
+
+/// A calendar date and time read from the RTC
+///
+/// Ordered chronologically, so dates can be compared directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    /// Year, 2000-2099
+    pub year: u16,
+    /// Month, 1-12
+    pub month: u8,
+    /// Day of the month, 1-31
+    pub day: u8,
+    /// Day of the week, 0-6 (the meaning of 0 is up to whoever set the clock)
+    pub weekday: u8,
+    /// Hour, 0-23
+    pub hour: u8,
+    /// Minute, 0-59
+    pub minute: u8,
+    /// Second, 0-59
+    pub second: u8,
+}
+
+impl DateTime {
+    fn sort_key(&self) -> (u16, u8, u8, u8, u8, u8) {
+        (
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+        )
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DateTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+/// Handle to the cartridge real-time clock
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::rtc::Rtc;
+///
+/// # fn example() {
+/// match Rtc::new() {
+///     Ok(mut rtc) => {
+///         let now = rtc.now().unwrap();
+///         let is_night = now.hour >= 20 || now.hour < 6;
+///     }
+///     Err(_) => {
+///         // No clock on this cartridge, fall back to a fixed time of day
+///     }
+/// }
+/// # }
+/// ```
+pub struct Rtc {
+    _private: (),
+}
+
+impl Rtc {
+    /// Detect the clock and switch it to 24-hour mode
+    ///
+    /// Returns [`RtcError::NotPresent`] if no clock answers, and
+    /// [`RtcError::PowerLost`] if it has lost track of the time.
+    pub fn new() -> Result<Self, RtcError> {
+        unsafe { GPIO_CONTROL.write_volatile(1) };
+
+        let rtc = Self { _private: () };
+
+        // Without a clock the port reads back ROM or open-bus data, which won't be a
+        // valid date. Check that first, as that data often has the power lost bit set.
+        // A clock that lost power restarts from a valid date, so it still passes.
+        rtc.read_date_time()?;
+
+        let [status] = transfer(CMD_STATUS | CMD_READ, None);
+        if status & STATUS_POWER_LOST != 0 {
+            return Err(RtcError::PowerLost);
+        }
+        if status & STATUS_24_HOUR == 0 {
+            transfer::<0>(CMD_STATUS, Some(STATUS_24_HOUR));
+        }
+
+        Ok(rtc)
+    }
+
+    /// Read the current date and time
+    pub fn now(&mut self) -> Result<DateTime, RtcError> {
+        self.read_date_time()
+    }
+
+    /// Wait until the clock reaches `target`
+    ///
+    /// The clock is checked about twice a second, so this resolves up to half a second
+    /// after `target`. Resolves straight away if `target` is in the past.
+    pub async fn wait_until(&mut self, target: DateTime) -> Result<(), RtcError> {
+        while self.now()? < target {
+            for _ in 0..WAIT_POLL_FRAMES {
+                crate::display::wait_for_vblank().await;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_date_time(&self) -> Result<DateTime, RtcError> {
+        let raw: [u8; 7] = transfer(CMD_DATE_TIME | CMD_READ, None);
+        let field = |i: usize, mask: u8, max: u8| {
+            let value = bcd(raw[i] & mask).ok_or(RtcError::NotPresent)?;
+            if value > max {
+                Err(RtcError::NotPresent)
+            } else {
+                Ok(value)
+            }
+        };
+
+        let date_time = DateTime {
+            year: 2000 + field(0, 0xFF, 99)? as u16,
+            month: field(1, 0x1F, 12)?,
+            day: field(2, 0x3F, 31)?,
+            weekday: field(3, 0x07, 6)?,
+            // Bit 7 is the PM flag, which 24-hour mode also sets for afternoon hours
+            hour: field(4, 0x3F, 23)?,
+            minute: field(5, 0x7F, 59)?,
+            second: field(6, 0x7F, 59)?,
+        };
+
+        if date_time.month == 0 || date_time.day == 0 {
+            return Err(RtcError::NotPresent);
+        }
+        Ok(date_time)
+    }
+}
+
+/// Decode a binary-coded decimal byte
+fn bcd(value: u8) -> Option<u8> {
+    let (tens, ones) = (value >> 4, value & 0x0F);
+    (tens < 10 && ones < 10).then_some(tens * 10 + ones)
+}
+
+/// Send `command` and optionally one data byte, then read `N` bytes back
+fn transfer<const N: usize>(command: u8, write: Option<u8>) -> [u8; N] {
+    let mut read = [0; N];
+
+    critical_section::with(|_| unsafe {
+        GPIO_DATA.write_volatile(SCK);
+        GPIO_DATA.write_volatile(SCK | CS);
+        GPIO_DIRECTION.write_volatile(SCK | SIO | CS);
+
+        // Commands go out MSB first, data LSB first
+        for i in (0..8).rev() {
+            write_bit((command >> i) & 1);
+        }
+        if let Some(data) = write {
+            for i in 0..8 {
+                write_bit((data >> i) & 1);
+            }
+        }
+
+        GPIO_DIRECTION.write_volatile(SCK | CS);
+        for byte in read.iter_mut() {
+            for i in 0..8 {
+                // Hold the clock low long enough for the chip to present the bit
+                for _ in 0..5 {
+                    GPIO_DATA.write_volatile(CS);
+                }
+                GPIO_DATA.write_volatile(SCK | CS);
+                let bit = ((GPIO_DATA.read_volatile() & SIO) >> 1) as u8;
+                *byte |= bit << i;
+            }
+        }
+
+        GPIO_DATA.write_volatile(SCK);
+        GPIO_DATA.write_volatile(SCK);
+    });
+
+    read
+}
+
+/// Clock one bit out on SIO
+unsafe fn write_bit(bit: u8) {
+    let sio = (bit as u16) << 1;
+    unsafe {
+        for _ in 0..3 {
+            GPIO_DATA.write_volatile(sio | CS);
+        }
+        GPIO_DATA.write_volatile(sio | CS | SCK);
+    }
+}
@@ -0,0 +1,121 @@
+//! Cartridge rumble motor
+//!
+//! Rumble cartridges drive their motor from bit 3 of the cartridge GPIO port. Presence
+//! is detected on a best-effort basis by checking that the port's direction register
+//! reads back two different values; on cartridges without a GPIO port every call is a
+//! no-op.
+//!
+//! ## Registers
+//! - `GPIO data` (0x80000C4): bit 3 drives the motor
+//! - `GPIO direction` (0x80000C6): bit 3 set makes the motor pin an output
+//! - `GPIO control` (0x80000C8): bit 0 enables reading the port
+
+/// GPIO data register
+const GPIO_DATA: *mut u16 = 0x0800_00C4 as *mut u16;
+/// GPIO direction register
+const GPIO_DIRECTION: *mut u16 = 0x0800_00C6 as *mut u16;
+/// GPIO control register
+const GPIO_CONTROL: *mut u16 = 0x0800_00C8 as *mut u16;
+
+/// GPIO pin wired to the motor
+const MOTOR: u16 = 1 << 3;
+/// GPIO pins that exist on the port
+const GPIO_PINS: u16 = 0x0F;
+
+/// Handle to the cartridge rumble motor
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::rumble::Rumble;
+/// use embassy_agb::Duration;
+///
+/// # async fn example() {
+/// let mut rumble = Rumble::new();
+/// // Shake when the player takes a hit
+/// rumble.pulse(Duration::from_millis(150)).await;
+/// # }
+/// ```
+pub struct Rumble {
+    present: bool,
+}
+
+impl Rumble {
+    /// Set up the motor pin, detecting whether the cartridge has a GPIO port
+    pub fn new() -> Self {
+        let present = critical_section::with(|_| unsafe {
+            GPIO_CONTROL.write_volatile(1);
+            let direction = GPIO_DIRECTION.read_volatile() & GPIO_PINS;
+
+            // Without a GPIO port the read comes from ROM and won't track the writes.
+            // ROM data can happen to match one pattern, so check with the motor pin
+            // both set and cleared.
+            let reads_back = |value: u16| {
+                GPIO_DIRECTION.write_volatile(value);
+                GPIO_DIRECTION.read_volatile() & GPIO_PINS == value
+            };
+            let present = reads_back(direction & !MOTOR) && reads_back(direction | MOTOR);
+
+            // Leave the other pins as they were, with the motor pin an output
+            GPIO_DIRECTION.write_volatile(if present {
+                direction | MOTOR
+            } else {
+                direction
+            });
+            present
+        });
+
+        let mut rumble = Self { present };
+        rumble.set(false);
+        rumble
+    }
+
+    /// Whether a GPIO port was found; when false, [`set()`](Self::set) does nothing
+    ///
+    /// A port being present doesn't guarantee a motor is wired to it.
+    pub fn is_present(&self) -> bool {
+        self.present
+    }
+
+    /// Turn the motor on or off
+    pub fn set(&mut self, on: bool) {
+        if !self.present {
+            return;
+        }
+
+        critical_section::with(|_| unsafe {
+            let data = GPIO_DATA.read_volatile() & GPIO_PINS & !MOTOR;
+            GPIO_DATA.write_volatile(if on { data | MOTOR } else { data });
+        });
+    }
+
+    /// Run the motor for `on`, then turn it off
+    ///
+    /// The motor is also turned off if the future is dropped early.
+    #[cfg(feature = "time")]
+    pub async fn pulse(&mut self, on: embassy_time::Duration) {
+        struct StopOnDrop<'a>(&'a mut Rumble);
+
+        impl Drop for StopOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(false);
+            }
+        }
+
+        self.set(true);
+        let _stop = StopOnDrop(self);
+        embassy_time::Timer::after(on).await;
+    }
+}
+
+impl Default for Rumble {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Rumble {
+    fn drop(&mut self) {
+        self.set(false);
+    }
+}
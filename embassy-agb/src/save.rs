@@ -0,0 +1,312 @@
+//! Async access to cartridge save media (SRAM, Flash, EEPROM)
+//!
+//! Wraps agb's save backend so long writes don't stall the game: data is written one
+//! block at a time (one sector on Flash) with a yield to the executor in between, so
+//! other tasks keep running while a save is in progress.
+//!
+//! [`SaveData::write_slot()`] and [`SaveData::read_slot()`] add a small header with a
+//! version number and checksum, so a game can tell an empty, corrupt or outdated save
+//! apart from a valid one. On top of slots, [`settings::Settings`] keeps typed values
+//! such as the volume or key bindings.
+//!
+//! ## Media types
+//! - SRAM: fast, 32KiB, no erase needed
+//! - Flash: 64KiB or 128KiB, 4KiB sectors that must be erased before writing
+//! - EEPROM: 512B or 8KiB, slow
+
+use alloc::vec;
+use portable_atomic::{AtomicU8, Ordering};
+
+use agb::save::SaveManager;
+
+pub mod settings;
+
+/// Bytes written between yields on media without erase sectors
+const WRITE_CHUNK: usize = 256;
+
+/// Length of the header [`SaveData::write_slot()`] puts in front of the payload
+pub const SLOT_HEADER_LEN: usize = 8;
+
+/// Which [`SaveMedia`] the save backend was initialized for, plus one (0 = none yet)
+static INITIALIZED_MEDIA: AtomicU8 = AtomicU8::new(0);
+
+/// Save media fitted to the cartridge
+///
+/// This also marks the ROM so emulators know which media to emulate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveMedia {
+    /// 32KiB battery-backed SRAM or FRAM
+    Sram32K,
+    /// 64KiB Flash
+    Flash64K,
+    /// 128KiB Flash
+    Flash128K,
+    /// 512B EEPROM
+    Eeprom512B,
+    /// 8KiB EEPROM
+    Eeprom8K,
+}
+
+/// Error type for save operations
+#[derive(Debug, Clone)]
+pub enum SaveError {
+    /// No save media was detected on the cartridge
+    NoMedia,
+    /// The save backend was already set up for a different [`SaveMedia`]
+    WrongBackend,
+    /// Another [`SaveData`] is still alive
+    InUse,
+    /// The access would run past the end of the save media or the buffer
+    OutOfBounds,
+    /// The slot has never been written
+    EmptySlot,
+    /// The slot's checksum doesn't match its contents
+    Corrupt,
+    /// The slot was written with a different version
+    VersionMismatch {
+        /// Version stored in the slot
+        found: u16,
+    },
+    /// Any other error from the save media
+    Media(agb::save::Error),
+}
+
+impl core::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SaveError::NoMedia => write!(f, "No save media found"),
+            SaveError::WrongBackend => write!(f, "Save backend set up for different media"),
+            SaveError::InUse => write!(f, "Save media already in use"),
+            SaveError::OutOfBounds => write!(f, "Save access out of bounds"),
+            SaveError::EmptySlot => write!(f, "Save slot is empty"),
+            SaveError::Corrupt => write!(f, "Save slot is corrupt"),
+            SaveError::VersionMismatch { found } => {
+                write!(f, "Save slot has version {found}")
+            }
+            SaveError::Media(err) => write!(f, "Save media error: {err:?}"),
+        }
+    }
+}
+
+impl From<agb::save::Error> for SaveError {
+    fn from(err: agb::save::Error) -> Self {
+        match err {
+            agb::save::Error::NoMedia => SaveError::NoMedia,
+            agb::save::Error::OutOfBounds => SaveError::OutOfBounds,
+            agb::save::Error::MediaInUse => SaveError::InUse,
+            err => SaveError::Media(err),
+        }
+    }
+}
+
+/// Async accessor for the cartridge save media
+///
+/// Get one from [`InitializedGba::save()`](crate::InitializedGba::save). Only one can
+/// exist at a time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::save::{SaveError, SaveMedia};
+///
+/// # async fn example(gba: &mut embassy_agb::InitializedGba) -> Result<(), SaveError> {
+/// const SAVE_VERSION: u16 = 1;
+///
+/// let mut save = gba.save(SaveMedia::Sram32K)?;
+///
+/// let mut high_score = [0u8; 4];
+/// match save.read_slot(0, SAVE_VERSION, &mut high_score).await {
+///     Ok(_) => {}
+///     Err(SaveError::EmptySlot | SaveError::Corrupt) => high_score = [0; 4],
+///     Err(err) => return Err(err),
+/// }
+///
+/// save.write_slot(0, SAVE_VERSION, &1234u32.to_le_bytes()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SaveData {
+    inner: agb::save::SaveData,
+}
+
+impl SaveData {
+    pub(crate) fn open(manager: &mut SaveManager, media: SaveMedia) -> Result<Self, SaveError> {
+        let tag = media as u8 + 1;
+        match INITIALIZED_MEDIA.compare_exchange(0, tag, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => match media {
+                SaveMedia::Sram32K => manager.init_sram(),
+                SaveMedia::Flash64K => manager.init_flash_64k(),
+                SaveMedia::Flash128K => manager.init_flash_128k(),
+                SaveMedia::Eeprom512B => manager.init_eeprom_512b(),
+                SaveMedia::Eeprom8K => manager.init_eeprom_8k(),
+            },
+            Err(current) if current != tag => return Err(SaveError::WrongBackend),
+            Err(_) => {}
+        }
+
+        Ok(Self {
+            inner: manager.access()?,
+        })
+    }
+
+    /// Total size of the save media in bytes
+    #[allow(clippy::len_without_is_empty)] // save media is never empty
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Erase sector size in bytes (1 for media that don't use sectors)
+    pub fn sector_size(&self) -> usize {
+        self.inner.sector_size()
+    }
+
+    /// Fill `buf` from the start of the save media
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<(), SaveError> {
+        self.read_at(0, buf).await
+    }
+
+    /// Write `data` to the start of the save media
+    pub async fn write(&mut self, data: &[u8]) -> Result<(), SaveError> {
+        self.write_at(0, data).await
+    }
+
+    /// Fill `buf` from the save media starting at `offset`
+    ///
+    /// Reads one block at a time, yielding to the executor in between.
+    pub async fn read_at(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), SaveError> {
+        self.check_bounds(offset, buf.len())?;
+
+        let step = self.step();
+        for (i, chunk) in buf.chunks_mut(step).enumerate() {
+            self.inner.read(offset + i * step, chunk)?;
+            embassy_futures::yield_now().await;
+        }
+        Ok(())
+    }
+
+    /// Write `data` to the save media starting at `offset`, verifying it afterwards
+    ///
+    /// Writes one block at a time (a whole sector on Flash), yielding to the executor in
+    /// between. On Flash, data sharing a sector with the written range is read back and
+    /// rewritten, so neighbouring data is preserved even for unaligned writes.
+    ///
+    /// If an error is returned or the future is dropped part way, the written range is
+    /// left partly updated.
+    pub async fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), SaveError> {
+        self.check_bounds(offset, data.len())?;
+
+        let uses_erase = self.inner.media_info().uses_prepare_write;
+        let step = self.step();
+        let end = offset + data.len();
+        let mut block_start = offset - offset % step;
+
+        while block_start < end {
+            let block = block_start..(block_start + step).min(self.len());
+            let start = offset.max(block.start);
+            let stop = end.min(block.end);
+            let src = &data[start - offset..stop - offset];
+
+            if uses_erase && (start, stop) != (block.start, block.end) {
+                // Erasing clears the whole sector, so merge with what's already there
+                let mut sector = vec![0; block.len()];
+                self.inner.read(block.start, &mut sector)?;
+                sector[start - block.start..stop - block.start].copy_from_slice(src);
+                self.inner
+                    .prepare_write(block.clone())?
+                    .write_and_verify(block.start, &sector)?;
+            } else {
+                self.inner
+                    .prepare_write(start..stop)?
+                    .write_and_verify(start, src)?;
+            }
+
+            embassy_futures::yield_now().await;
+            block_start = block.end;
+        }
+        Ok(())
+    }
+
+    /// Write `payload` as a versioned, checksummed slot at `offset`
+    ///
+    /// The slot takes [`SLOT_HEADER_LEN`] bytes plus the payload. Keeping slots
+    /// sector-aligned (see [`sector_size()`](Self::sector_size)) avoids rewriting
+    /// neighbouring slots on Flash.
+    pub async fn write_slot(
+        &mut self,
+        offset: usize,
+        version: u16,
+        payload: &[u8],
+    ) -> Result<(), SaveError> {
+        let len = u16::try_from(payload.len()).map_err(|_| SaveError::OutOfBounds)?;
+
+        let mut slot = vec![0; SLOT_HEADER_LEN + payload.len()];
+        slot[0..2].copy_from_slice(&version.to_le_bytes());
+        slot[2..4].copy_from_slice(&len.to_le_bytes());
+        slot[4..8].copy_from_slice(&checksum(version, payload).to_le_bytes());
+        slot[SLOT_HEADER_LEN..].copy_from_slice(payload);
+
+        self.write_at(offset, &slot).await
+    }
+
+    /// Read a slot written by [`write_slot()`](Self::write_slot) into `buf`
+    ///
+    /// Returns the payload length. Fails with [`SaveError::EmptySlot`] if the slot was
+    /// never written, [`SaveError::Corrupt`] if the checksum doesn't match,
+    /// [`SaveError::VersionMismatch`] if it was written with another `version`, and
+    /// [`SaveError::OutOfBounds`] if the payload doesn't fit in `buf`.
+    pub async fn read_slot(
+        &mut self,
+        offset: usize,
+        version: u16,
+        buf: &mut [u8],
+    ) -> Result<usize, SaveError> {
+        let mut header = [0; SLOT_HEADER_LEN];
+        self.read_at(offset, &mut header).await?;
+
+        // Erased Flash reads as 0xFF, fresh SRAM usually as 0x00
+        if header.iter().all(|&b| b == 0xFF) || header.iter().all(|&b| b == 0x00) {
+            return Err(SaveError::EmptySlot);
+        }
+
+        let found = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let stored = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+        if offset + SLOT_HEADER_LEN + len > self.len() {
+            return Err(SaveError::Corrupt);
+        }
+        let payload = buf.get_mut(..len).ok_or(SaveError::OutOfBounds)?;
+        self.read_at(offset + SLOT_HEADER_LEN, payload).await?;
+
+        if checksum(found, payload) != stored {
+            return Err(SaveError::Corrupt);
+        }
+        if found != version {
+            return Err(SaveError::VersionMismatch { found });
+        }
+        Ok(len)
+    }
+
+    /// Bytes handled between yields: one sector, or [`WRITE_CHUNK`] if sectors are smaller
+    fn step(&self) -> usize {
+        self.sector_size().max(WRITE_CHUNK)
+    }
+
+    fn check_bounds(&self, offset: usize, len: usize) -> Result<(), SaveError> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.len() => Ok(()),
+            _ => Err(SaveError::OutOfBounds),
+        }
+    }
+}
+
+/// FNV-1a hash of a slot's version and payload
+fn checksum(version: u16, payload: &[u8]) -> u32 {
+    version
+        .to_le_bytes()
+        .iter()
+        .chain(payload)
+        .fold(0x811C_9DC5, |hash, &byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        })
+}
@@ -0,0 +1,218 @@
+//! Typed key/value settings persisted in a save slot
+//!
+//! [`Settings`] keeps up to [`MAX_SETTINGS`] small values (volume, key bindings, text
+//! speed) keyed by a `u8`, and stores them as a single [`SaveData`] slot with a magic
+//! number and version in front. Missing, corrupt or outdated data is never an error
+//! when loading: the store just starts out empty, and
+//! [`get_or()`](Settings::get_or) hands back the defaults.
+//!
+//! ## Layout
+//! After the slot header: the magic bytes `EAST`, a count byte, then 5 bytes per
+//! setting (key, then the value as a little-endian `u32`). A full store takes
+//! [`SLOT_HEADER_LEN`] + 165 bytes, so it fits even on 512B EEPROM.
+
+use heapless::LinearMap;
+
+use super::{SaveData, SaveError, SLOT_HEADER_LEN};
+
+/// Most settings a [`Settings`] store can hold
+pub const MAX_SETTINGS: usize = 32;
+
+/// Marks the start of a settings payload
+const MAGIC: [u8; 4] = *b"EAST";
+/// Bytes per stored setting: key plus value
+const ENTRY_LEN: usize = 5;
+/// Largest payload a full store writes
+const MAX_PAYLOAD_LEN: usize = MAGIC.len() + 1 + MAX_SETTINGS * ENTRY_LEN;
+
+/// Fixed-size primitive that can be kept in [`Settings`]
+///
+/// Implemented for `bool` and the integer types up to 32 bits. Every value is stored
+/// in 32 bits.
+pub trait SettingValue: Copy {
+    /// Stored form of the value
+    fn to_bits(self) -> u32;
+
+    /// Value from its stored form, or `None` if it doesn't fit this type
+    fn from_bits(bits: u32) -> Option<Self>;
+}
+
+impl SettingValue for bool {
+    fn to_bits(self) -> u32 {
+        self as u32
+    }
+
+    fn from_bits(bits: u32) -> Option<Self> {
+        match bits {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! unsigned_setting_value {
+    ($($ty:ty),*) => {$(
+        impl SettingValue for $ty {
+            fn to_bits(self) -> u32 {
+                self as u32
+            }
+
+            fn from_bits(bits: u32) -> Option<Self> {
+                <$ty>::try_from(bits).ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_setting_value {
+    ($($ty:ty),*) => {$(
+        impl SettingValue for $ty {
+            fn to_bits(self) -> u32 {
+                self as i32 as u32
+            }
+
+            fn from_bits(bits: u32) -> Option<Self> {
+                <$ty>::try_from(bits as i32).ok()
+            }
+        }
+    )*};
+}
+
+unsigned_setting_value!(u8, u16, u32);
+signed_setting_value!(i8, i16, i32);
+
+/// Typed key/value settings stored in one save slot
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::save::settings::Settings;
+/// use embassy_agb::save::{SaveError, SaveMedia};
+///
+/// const SETTINGS_VERSION: u16 = 1;
+/// const VOLUME: u8 = 0;
+/// const JUMP_BUTTON: u8 = 1;
+///
+/// # async fn example(gba: &mut embassy_agb::InitializedGba) -> Result<(), SaveError> {
+/// let mut save = gba.save(SaveMedia::Eeprom512B)?;
+///
+/// // Defaults on first boot, or if the save is corrupt or from an older version
+/// let mut settings = Settings::load(&mut save, 0, SETTINGS_VERSION).await;
+/// let volume: u8 = settings.get_or(VOLUME, 8);
+///
+/// settings.set(VOLUME, volume.saturating_sub(1));
+/// settings.set(JUMP_BUTTON, agb::input::Button::A.bits() as u16);
+/// settings.save(&mut save).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Settings {
+    values: LinearMap<u8, u32, MAX_SETTINGS>,
+    offset: usize,
+    version: u16,
+    loaded: bool,
+}
+
+impl Settings {
+    /// Empty store kept in the slot at `offset`, written with `version`
+    pub fn new(offset: usize, version: u16) -> Self {
+        Self {
+            values: LinearMap::new(),
+            offset,
+            version,
+            loaded: false,
+        }
+    }
+
+    /// Load the settings stored at `offset`
+    ///
+    /// Returns an empty store if the slot is empty, corrupt, holds something other than
+    /// settings, or was saved with a different `version`.
+    pub async fn load(save: &mut SaveData, offset: usize, version: u16) -> Self {
+        let mut settings = Self::new(offset, version);
+
+        let mut payload = [0; MAX_PAYLOAD_LEN];
+        let Ok(len) = save.read_slot(offset, version, &mut payload).await else {
+            return settings;
+        };
+        if let Some(values) = parse(&payload[..len]) {
+            settings.values = values;
+            settings.loaded = true;
+        }
+        settings
+    }
+
+    /// Whether the settings came from the save rather than starting out empty
+    pub fn was_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// Value stored under `key`, or `None` if unset or not a valid `T`
+    pub fn get<T: SettingValue>(&self, key: u8) -> Option<T> {
+        self.values.get(&key).copied().and_then(T::from_bits)
+    }
+
+    /// Value stored under `key`, or `default` if unset or not a valid `T`
+    pub fn get_or<T: SettingValue>(&self, key: u8, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Store `value` under `key`, replacing any previous value
+    ///
+    /// Only changes the store in memory; call [`save()`](Self::save) to persist it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is new and [`MAX_SETTINGS`] settings are already stored.
+    pub fn set<T: SettingValue>(&mut self, key: u8, value: T) {
+        if self.values.insert(key, value.to_bits()).is_err() {
+            panic!("no room for more than {MAX_SETTINGS} settings");
+        }
+    }
+
+    /// Remove the value stored under `key`
+    pub fn remove(&mut self, key: u8) {
+        self.values.remove(&key);
+    }
+
+    /// Remove every value, so all [`get_or()`](Self::get_or) calls return their default
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Write the settings to their slot
+    pub async fn save(&self, save: &mut SaveData) -> Result<(), SaveError> {
+        let mut payload = heapless::Vec::<u8, MAX_PAYLOAD_LEN>::new();
+        // Cannot fail: the buffer is sized for a full store
+        let _ = payload.extend_from_slice(&MAGIC);
+        let _ = payload.push(self.values.len() as u8);
+        for (&key, value) in self.values.iter() {
+            let _ = payload.push(key);
+            let _ = payload.extend_from_slice(&value.to_le_bytes());
+        }
+
+        save.write_slot(self.offset, self.version, &payload).await
+    }
+
+    /// Bytes the slot will take on the save media with the current values
+    pub fn slot_len(&self) -> usize {
+        SLOT_HEADER_LEN + MAGIC.len() + 1 + self.values.len() * ENTRY_LEN
+    }
+}
+
+/// Values from a settings payload, or `None` if it isn't one
+fn parse(payload: &[u8]) -> Option<LinearMap<u8, u32, MAX_SETTINGS>> {
+    let entries = payload.strip_prefix(&MAGIC)?;
+    let (&count, entries) = entries.split_first()?;
+    if count as usize > MAX_SETTINGS || entries.len() != count as usize * ENTRY_LEN {
+        return None;
+    }
+
+    let mut values = LinearMap::new();
+    for entry in entries.chunks_exact(ENTRY_LEN) {
+        let value = u32::from_le_bytes([entry[1], entry[2], entry[3], entry[4]]);
+        values.insert(entry[0], value).ok()?;
+    }
+    Some(values)
+}
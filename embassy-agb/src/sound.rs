@@ -64,8 +64,54 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Example (Game Events)
+//!
+//! Music can run in its own task, told about game events through a
+//! [`Signal`](crate::sync::Signal). The task still calls [`AsyncMixer::frame()`] every
+//! frame while it waits:
+//!
+//! ```rust,no_run
+//! use agb::sound::mixer::{SoundChannel, SoundData};
+//! use agb::include_wav;
+//! use embassy_agb::display::AsyncDisplay;
+//! use embassy_agb::futures::select::{select, Either};
+//! use embassy_agb::sound::AsyncMixer;
+//! use embassy_agb::sync::Signal;
+//!
+//! static GAME_OVER_JINGLE: SoundData = include_wav!("sfx/game_over.wav");
+//!
+//! enum GameEvent {
+//!     PlayerDied,
+//! }
+//!
+//! static GAME_EVENTS: Signal<GameEvent> = Signal::new();
+//!
+//! #[embassy_agb::task]
+//! async fn music_task(mut mixer: AsyncMixer<'static>, display: AsyncDisplay<'static>) {
+//!     loop {
+//!         match select(GAME_EVENTS.wait(), display.wait_for_vblank()).await {
+//!             Either::First(GameEvent::PlayerDied) => {
+//!                 mixer.stop_all();
+//!                 mixer.play_sound(SoundChannel::new_high_priority(GAME_OVER_JINGLE)).ok();
+//!             }
+//!             Either::Second(()) => mixer.frame(),
+//!         }
+//!     }
+//! }
+//!
+//! // In the game loop:
+//! GAME_EVENTS.signal(GameEvent::PlayerDied);
+//! ```
+
+use agb::sound::mixer::{ChannelId, Frequency, MixerController, SoundChannel, SoundData};
+use portable_atomic::AtomicBool;
 
-use agb::sound::mixer::{Frequency, MixerController, SoundChannel};
+/// Whether an [`AsyncMixer`] exists
+static MIXER_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Largest gain accepted by [`AsyncMixer::play_sound_with_gain()`]
+const MAX_GAIN: i16 = 2;
 
 /// Error type for sound operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -77,6 +123,39 @@ impl core::fmt::Display for SoundError {
     }
 }
 
+/// Handle to a sound started through an [`AsyncMixer`]
+///
+/// The mixer keeps agb's `ChannelId` for each sound and hands out this handle instead.
+/// Unlike a `ChannelId` it can be copied and compared, so a sound can be followed from
+/// several places (a [`Ducker`], an [`SfxPool`], game code). It stops resolving once
+/// the sound ends, is stopped, or its channel is taken by another sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoundId(u32);
+
+/// Priority of a sound started with [`GbaPeripherals::play_sound_with()`](crate::GbaPeripherals::play_sound_with)
+///
+/// When all 8 channels are busy, a high priority sound takes the place of a low
+/// priority one, while a low priority sound fails to start. High priority sounds are
+/// never replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoundPriority {
+    /// Sound effects that can be cut off or dropped when channels run out
+    #[default]
+    Low,
+    /// Background music or sounds that must always play
+    High,
+}
+
+impl SoundPriority {
+    /// Channel playing `sound` at this priority
+    pub fn channel(self, sound: SoundData) -> SoundChannel {
+        match self {
+            Self::Low => SoundChannel::new(sound),
+            Self::High => SoundChannel::new_high_priority(sound),
+        }
+    }
+}
+
 /// Async-friendly wrapper for the agb sound mixer
 ///
 /// The mixer supports up to 8 simultaneous sound channels and can play
@@ -103,13 +182,152 @@ impl core::fmt::Display for SoundError {
 ///
 /// WAV files must be converted to match the chosen frequency.
 pub struct AsyncMixer<'a> {
-    mixer: agb::sound::mixer::Mixer<'a>,
+    controller: &'a mut MixerController,
+    /// Only `None` while [`set_frequency()`](Self::set_frequency) replaces it
+    mixer: Option<agb::sound::mixer::Mixer<'a>>,
+    frequency: Frequency,
+    /// agb's channel ID for each sound handed out, the only copy of it
+    channels: heapless::Vec<(SoundId, ChannelId), 8>,
+    /// Value of the next [`SoundId`]
+    next_id: u32,
+    /// Sounds started through [`play_sound()`](Self::play_sound), for [`stop_all()`](Self::stop_all)
+    playing: heapless::Vec<SoundId, 8>,
+    /// Volumes set through [`set_channel_volume()`](Self::set_channel_volume)
+    volumes: heapless::Vec<(SoundId, agb::fixnum::Num<i16, 8>), 8>,
+    /// VBlank of the last [`frame()`](Self::frame) call, `None` while mixing is paused
+    last_frame_vblank: Option<usize>,
+    /// Frames [`frame()`](Self::frame) wasn't called for, see [`missed_frames()`](Self::missed_frames)
+    missed_frames: u32,
+    /// Playing channels and their positions, for [`is_playing()`](Self::is_playing)
+    positions: heapless::Vec<(SoundId, agb::fixnum::Num<u32, 8>), 8>,
+    /// Channels to stop when they next loop, see [`set_looping()`](Self::set_looping),
+    /// with their position as of the last frame
+    loop_ends: heapless::Vec<(SoundId, agb::fixnum::Num<u32, 8>), 8>,
+    _taken: crate::TakeGuard,
+}
+
+/// The agb mixer in `mixer`, which is only missing if replacing it panicked
+fn active<'m, 'a>(
+    mixer: &'m mut Option<agb::sound::mixer::Mixer<'a>>,
+) -> &'m mut agb::sound::mixer::Mixer<'a> {
+    mixer
+        .as_mut()
+        .expect("mixer lost: set_frequency() panicked while replacing it")
+}
+
+/// Playing channel of the sound `id`, looked up through the channel IDs in `channels`
+///
+/// Takes the fields separately so callers can look up channels while iterating their
+/// own lists.
+fn lookup<'m>(
+    mixer: &'m mut agb::sound::mixer::Mixer<'_>,
+    channels: &[(SoundId, ChannelId)],
+    id: SoundId,
+) -> Option<&'m mut SoundChannel> {
+    let (_, channel_id) = channels.iter().find(|(tracked, _)| *tracked == id)?;
+    mixer.channel(channel_id)
+}
+
+/// Volume for `step` out of `steps` of a linear ramp from `from` to `to`
+#[cfg(feature = "time")]
+fn lerp_volume(
+    from: agb::fixnum::Num<i16, 8>,
+    to: agb::fixnum::Num<i16, 8>,
+    step: i32,
+    steps: i32,
+) -> agb::fixnum::Num<i16, 8> {
+    let (from, to) = (from.to_raw() as i32, to.to_raw() as i32);
+    agb::fixnum::Num::from_raw((from + (to - from) * step / steps) as i16)
+}
+
+/// Number of frames a fade over `over` takes, at least one
+#[cfg(feature = "time")]
+fn fade_frames(over: embassy_time::Duration) -> i32 {
+    (over.as_micros() / crate::display::VBLANK_INTERVAL_US).max(1) as i32
+}
+
+/// Playback rates for 0-11 semitones up, as raw `Num<u32, 8>` values (`256 * 2^(n/12)`)
+const SEMITONE_RATES: [u32; 12] = [256, 271, 287, 304, 323, 342, 362, 384, 406, 431, 456, 483];
+
+/// Playback rate that shifts pitch by `semitones`, clamped to four octaves either way
+fn rate_for_semitones(semitones: i32) -> agb::fixnum::Num<u32, 8> {
+    let semitones = semitones.clamp(-48, 48);
+    let octaves = semitones.div_euclid(12);
+    let rate = SEMITONE_RATES[semitones.rem_euclid(12) as usize];
+
+    agb::fixnum::Num::from_raw(if octaves >= 0 {
+        rate << octaves
+    } else {
+        rate >> -octaves
+    })
+}
+
+/// Panning for a horizontal screen position, `-1` at the left edge and `1` at the right
+fn pan_for_x(screen_x: i32) -> agb::fixnum::Num<i16, 8> {
+    let half_width = agb::display::WIDTH / 2;
+    let offset = screen_x.clamp(0, agb::display::WIDTH) - half_width;
+    agb::fixnum::Num::from_raw((offset * 256 / half_width) as i16)
 }
 
 impl<'a> AsyncMixer<'a> {
     pub(crate) fn new(mixer_controller: &'a mut MixerController, frequency: Frequency) -> Self {
-        let mixer = mixer_controller.mixer(frequency);
-        Self { mixer }
+        let taken = crate::TakeGuard::take(&MIXER_TAKEN, "mixer");
+        let mixer = Self::create_mixer(mixer_controller, frequency);
+        Self {
+            controller: mixer_controller,
+            mixer: Some(mixer),
+            frequency,
+            channels: heapless::Vec::new(),
+            next_id: 0,
+            playing: heapless::Vec::new(),
+            volumes: heapless::Vec::new(),
+            last_frame_vblank: None,
+            missed_frames: 0,
+            positions: heapless::Vec::new(),
+            loop_ends: heapless::Vec::new(),
+            _taken: taken,
+        }
+    }
+
+    fn create_mixer(
+        controller: &mut MixerController,
+        frequency: Frequency,
+    ) -> agb::sound::mixer::Mixer<'a> {
+        #[cfg(feature = "_time-driver")]
+        crate::time_driver::assert_sound_timers_free();
+
+        // SAFETY: the mixer's lifetime only ties it to the controller so that a single
+        // mixer exists at a time. AsyncMixer keeps the controller borrowed for 'a and
+        // clears its `Option` before creating a replacement, so it never holds two.
+        let controller: &'a mut MixerController = unsafe { &mut *(controller as *mut _) };
+        controller.mixer(frequency)
+    }
+
+    /// Switch the mixer to a different output frequency
+    ///
+    /// Useful to drop to [`Frequency::Hz10512`] during CPU-heavy scenes and go back up
+    /// to [`Frequency::Hz32768`] for a title screen. The underlying agb mixer is
+    /// replaced, so **every playing channel is stopped** and existing [`SoundId`]s stop
+    /// resolving. Keep calling [`frame()`](Self::frame) once per frame afterwards.
+    ///
+    /// Sound data must match the new frequency to play at the right pitch.
+    pub fn set_frequency(&mut self, frequency: Frequency) {
+        self.channels.clear();
+        self.playing.clear();
+        self.volumes.clear();
+        self.positions.clear();
+        self.loop_ends.clear();
+
+        // Only one mixer may exist at a time, so the old one must be torn down before
+        // the new one is created rather than replaced by assignment
+        self.mixer = None;
+        self.mixer = Some(Self::create_mixer(self.controller, frequency));
+        self.frequency = frequency;
+    }
+
+    /// Get the current output frequency
+    pub fn frequency(&self) -> Frequency {
+        self.frequency
     }
 
     /// Process one frame of audio
@@ -118,35 +336,815 @@ impl<'a> AsyncMixer<'a> {
     /// Call this just before waiting for VBlank.
     ///
     /// Skipping frames will cause audio glitches and crackling. Calling it more than once
-    /// per frame is harmless but wastes CPU cycles.
+    /// per frame is harmless but wastes CPU cycles. Skipped frames are counted in
+    /// [`missed_frames()`](Self::missed_frames), and in debug builds each gap is also
+    /// reported through [`debug_print!`](crate::debug_print).
     pub fn frame(&mut self) {
-        self.mixer.frame();
+        let vblank = crate::display::vblank_count();
+        if let Some(last) = self.last_frame_vblank {
+            let missed = vblank.wrapping_sub(last).saturating_sub(1) as u32;
+            if missed > 0 {
+                self.missed_frames = self.missed_frames.saturating_add(missed);
+
+                #[cfg(debug_assertions)]
+                crate::debug_print!("audio: frame() not called for {} frame(s)", missed);
+            }
+        }
+        self.last_frame_vblank = Some(vblank);
+
+        active(&mut self.mixer).frame();
+        self.end_loops();
+        self.refresh_positions();
+    }
+
+    /// Stop channels in `loop_ends` that wrapped back to their restart point this frame
+    fn end_loops(&mut self) {
+        let (mixer, channels) = (active(&mut self.mixer), &self.channels);
+        self.loop_ends.retain_mut(|(id, last_pos)| {
+            let Some(channel) = lookup(mixer, channels, *id) else {
+                return false;
+            };
+            let pos = channel.pos();
+            if pos < *last_pos {
+                channel.stop();
+                return false;
+            }
+            *last_pos = pos;
+            true
+        });
+    }
+
+    /// Frames in which [`frame()`](Self::frame) wasn't called, since the mixer was created
+    ///
+    /// Each missed frame replays a stale buffer, heard as crackling or a buzz. The count
+    /// is based on VBlanks, so it only advances while the VBlank interrupt is running
+    /// (it is once an [`AsyncDisplay`](crate::display::AsyncDisplay) exists). Frames
+    /// skipped on purpose with [`FrameOptions::mix_audio`](crate::FrameOptions::mix_audio)
+    /// aren't counted.
+    pub fn missed_frames(&self) -> u32 {
+        self.missed_frames
+    }
+
+    /// Note a frame skipped on purpose, so it isn't counted as missed
+    pub(crate) fn skip_frame(&mut self) {
+        self.last_frame_vblank = None;
     }
 
-    /// Play a sound and return its channel ID
+    /// Play a sound and return its ID
     ///
-    /// Returns `Ok(channel_id)` if the sound starts playing, or `Err(SoundError)`
+    /// Returns `Ok(sound_id)` if the sound starts playing, or `Err(SoundError)`
     /// if all channels are busy and the sound has low priority.
-    pub fn play_sound(
+    pub fn play_sound(&mut self, channel: SoundChannel) -> Result<SoundId, SoundError> {
+        let id = self.start(channel)?;
+        self.track(id);
+        Ok(id)
+    }
+
+    /// Start a sound on the underlying mixer and hand out an ID for it
+    ///
+    /// Unlike [`play_sound()`](Self::play_sound) the sound isn't counted by
+    /// [`stop_all()`](Self::stop_all) or [`is_playing()`](Self::is_playing).
+    fn start(&mut self, channel: SoundChannel) -> Result<SoundId, SoundError> {
+        let channel_id = active(&mut self.mixer)
+            .play_sound(channel)
+            .ok_or(SoundError)?;
+
+        // Forget sounds that finished or lost their channel, including any this one took
+        let mixer = active(&mut self.mixer);
+        self.channels
+            .retain(|(_, tracked)| mixer.channel(tracked).is_some());
+
+        let id = SoundId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        // At most 8 channels can be playing, and this sound is one of them
+        let _ = self.channels.push((id, channel_id));
+        Ok(id)
+    }
+
+    /// Play a sound with its volume scaled by `gain`, to even out loud and quiet assets
+    ///
+    /// `gain` multiplies the sound's own loudness: `1` leaves it unchanged, `0.5` halves
+    /// it. It is clamped to `0..=2`, as louder sounds clip. The gain becomes the channel's
+    /// starting volume, so [`channel_volume()`](Self::channel_volume) reports it and
+    /// [`ramp_volume()`](Self::ramp_volume) and [`fade_out()`](Self::fade_out) start from
+    /// it, while [`set_channel_volume()`](Self::set_channel_volume) replaces it. Any volume
+    /// already set on `channel` is overwritten. There is no mixer-wide volume to stack
+    /// with; the console's volume wheel scales the final output as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::sound::mixer::SoundChannel;
+    /// # static EXPLOSION: agb::sound::mixer::SoundData = agb::include_wav!("explosion.wav");
+    /// # fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>) {
+    /// use agb::fixnum::num;
+    ///
+    /// // This asset was mastered too hot
+    /// let _ = mixer.play_sound_with_gain(SoundChannel::new(EXPLOSION), num!(0.6));
+    /// # }
+    /// ```
+    pub fn play_sound_with_gain(
+        &mut self,
+        mut channel: SoundChannel,
+        gain: agb::fixnum::Num<i16, 8>,
+    ) -> Result<SoundId, SoundError> {
+        let gain = gain.clamp(agb::fixnum::Num::new(0), agb::fixnum::Num::new(MAX_GAIN));
+        channel.volume(gain);
+
+        let id = self.play_sound(channel)?;
+        self.remember_volume(id, gain);
+        Ok(id)
+    }
+
+    /// Play a sound panned between the left (`-1`) and right (`1`) speakers
+    ///
+    /// `pan` is clamped to `-1..=1`, with `0` playing equally from both sides.
+    pub fn play_sound_panned(
         &mut self,
-        channel: SoundChannel,
-    ) -> Result<agb::sound::mixer::ChannelId, SoundError> {
-        self.mixer.play_sound(channel).ok_or(SoundError)
+        mut channel: SoundChannel,
+        pan: agb::fixnum::Num<i16, 8>,
+    ) -> Result<SoundId, SoundError> {
+        let full = agb::fixnum::Num::new(1);
+        channel.panning(pan.clamp(-full, full));
+        self.play_sound(channel)
+    }
+
+    /// Play a sound panned to match a horizontal screen position
+    ///
+    /// `screen_x` of `0` is full left, [`WIDTH`](agb::display::WIDTH) is full right and the
+    /// middle of the screen is exactly centered. Off-screen positions clamp to the nearest
+    /// side.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # static EXPLOSION: agb::sound::mixer::SoundData = agb::include_wav!("explosion.wav");
+    /// # fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>, enemy_x: i32) {
+    /// let _ = mixer.play_at_x(&EXPLOSION, enemy_x);
+    /// # }
+    /// ```
+    pub fn play_at_x(&mut self, sound: &SoundData, screen_x: i32) -> Result<SoundId, SoundError> {
+        self.play_sound_panned(SoundChannel::new(*sound), pan_for_x(screen_x))
+    }
+
+    /// Play a sound that loops until stopped, e.g. an engine hum or a waterfall
+    ///
+    /// Loops from the start of the sound with agb's
+    /// [`should_loop()`](SoundChannel::should_loop), so there is no gap and no need to
+    /// replay it when it ends. Stop it with [`stop_channel()`](Self::stop_channel), or let
+    /// it finish its current pass with [`set_looping()`](Self::set_looping).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # static WATERFALL: agb::sound::mixer::SoundData = agb::include_wav!("waterfall.wav");
+    /// # fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>) {
+    /// if let Ok(waterfall) = mixer.play_looping(&WATERFALL) {
+    ///     // Later, once the player walks away
+    ///     mixer.stop_channel(waterfall);
+    /// }
+    /// # }
+    /// ```
+    pub fn play_looping(&mut self, sound: &SoundData) -> Result<SoundId, SoundError> {
+        let mut channel = SoundChannel::new(*sound);
+        channel.should_loop();
+        self.play_sound(channel)
+    }
+
+    /// Turn looping on or off for a playing channel
+    ///
+    /// Turning looping on makes the sound repeat once it reaches its end. Turning it off
+    /// lets the current pass finish: agb can't clear a channel's loop flag, so the channel
+    /// is stopped on the first [`frame()`](Self::frame) after it wraps back to its
+    /// restart point, and up to a frame of the restart may still be heard. Use
+    /// [`stop_channel()`](Self::stop_channel) to cut a looping sound off immediately.
+    /// Returns `Err(SoundError)` if the channel has already finished.
+    pub fn set_looping(&mut self, id: SoundId, looping: bool) -> Result<(), SoundError> {
+        let channel = self.channel(id).ok_or(SoundError)?;
+        let pos = channel.pos();
+        if looping {
+            channel.should_loop();
+        }
+
+        self.loop_ends.retain(|(ending, _)| *ending != id);
+        if !looping {
+            // At most 8 channels can be playing, and `id` is one of them
+            let _ = self.loop_ends.push((id, pos));
+        }
+        Ok(())
+    }
+
+    /// Set the playback rate of a playing channel
+    ///
+    /// `1` plays at normal speed, `2` an octave higher and `0.5` an octave lower.
+    /// Negative rates are treated as `0`. Returns `Err(SoundError)` if the channel has
+    /// already finished.
+    pub fn set_playback_rate(
+        &mut self,
+        id: SoundId,
+        rate: agb::fixnum::Num<i16, 8>,
+    ) -> Result<(), SoundError> {
+        let channel = self.channel(id).ok_or(SoundError)?;
+        let raw = rate.to_raw().max(0) as u32;
+        channel.playback(agb::fixnum::Num::<u32, 8>::from_raw(raw));
+        Ok(())
+    }
+
+    /// Set the volume of a playing channel
+    ///
+    /// `1` is the sound's own volume and `0` is silent; values above `1` amplify but may
+    /// clip. Negative volumes are treated as `0`. Returns `Err(SoundError)` if the channel
+    /// has already finished.
+    pub fn set_channel_volume(
+        &mut self,
+        id: SoundId,
+        volume: agb::fixnum::Num<i16, 8>,
+    ) -> Result<(), SoundError> {
+        let volume = volume.max(agb::fixnum::Num::new(0));
+        self.channel(id).ok_or(SoundError)?.volume(volume);
+        self.remember_volume(id, volume);
+        Ok(())
+    }
+
+    /// Record a playing channel's volume for [`channel_volume()`](Self::channel_volume)
+    fn remember_volume(&mut self, id: SoundId, volume: agb::fixnum::Num<i16, 8>) {
+        let (mixer, channels) = (active(&mut self.mixer), &self.channels);
+        self.volumes
+            .retain(|(tracked, _)| *tracked != id && lookup(mixer, channels, *tracked).is_some());
+        // At most 8 channels can be playing, and `id` is one of them
+        let _ = self.volumes.push((id, volume));
+    }
+
+    /// Get the volume of a playing channel, or `None` if it has finished
+    ///
+    /// agb can't report a channel's volume, so this is the last value given to
+    /// [`set_channel_volume()`](Self::set_channel_volume), or `1` if it was never called
+    /// for this channel. The gain of [`play_sound_with_gain()`](Self::play_sound_with_gain)
+    /// counts, but a volume set on the `SoundChannel` before playing isn't seen.
+    pub fn channel_volume(&mut self, id: SoundId) -> Option<agb::fixnum::Num<i16, 8>> {
+        self.channel(id)?;
+        Some(
+            self.volumes
+                .iter()
+                .find(|(tracked, _)| *tracked == id)
+                .map_or(agb::fixnum::Num::new(1), |(_, volume)| *volume),
+        )
+    }
+
+    /// Ramp a channel's volume from its current value to `to` over `over`
+    ///
+    /// The volume changes once per frame, starting from
+    /// [`channel_volume()`](Self::channel_volume). Like
+    /// [`wait_for_channel()`](Self::wait_for_channel), this calls [`frame()`](Self::frame)
+    /// once per VBlank while it runs. If the channel finishes or is replaced mid-ramp,
+    /// this returns early.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::fixnum::num;
+    /// # use embassy_agb::Duration;
+    /// # async fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>, bgm: embassy_agb::sound::SoundId) {
+    /// // Attack: swell the music up to full volume
+    /// mixer.ramp_volume(bgm, num!(1.0), Duration::from_millis(200)).await;
+    /// // Decay: settle back to a quieter level
+    /// mixer.ramp_volume(bgm, num!(0.6), Duration::from_millis(800)).await;
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn ramp_volume(
+        &mut self,
+        id: SoundId,
+        to: agb::fixnum::Num<i16, 8>,
+        over: embassy_time::Duration,
+    ) {
+        let Some(from) = self.channel_volume(id) else {
+            return;
+        };
+        let frames = fade_frames(over);
+
+        for step in 1..=frames {
+            if self
+                .set_channel_volume(id, lerp_volume(from, to, step, frames))
+                .is_err()
+            {
+                return;
+            }
+
+            self.frame_and_wait().await;
+        }
+    }
+
+    /// Play a sound shifted up or down by a number of semitones
+    ///
+    /// `12` plays an octave higher and `-12` an octave lower. The shift is clamped to
+    /// four octaves in either direction. Small random offsets make repeated sound effects
+    /// less monotonous.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::sound::mixer::SoundChannel;
+    /// # static COIN: agb::sound::mixer::SoundData = agb::include_wav!("coin.wav");
+    /// # fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>, rng: i32) {
+    /// let _ = mixer.play_sound_pitched(SoundChannel::new(COIN), rng % 3 - 1);
+    /// # }
+    /// ```
+    pub fn play_sound_pitched(
+        &mut self,
+        mut channel: SoundChannel,
+        semitones: i32,
+    ) -> Result<SoundId, SoundError> {
+        channel.playback(rate_for_semitones(semitones));
+        self.play_sound(channel)
+    }
+
+    /// Remember a newly started sound, forgetting any that have finished
+    fn track(&mut self, id: SoundId) {
+        let (mixer, channels) = (active(&mut self.mixer), &self.channels);
+        self.playing
+            .retain(|playing| lookup(mixer, channels, *playing).is_some());
+        // At most 8 channels can be playing, and `id` is one of them
+        let _ = self.playing.push(id);
+        self.refresh_positions();
+    }
+
+    /// Snapshot which tracked sounds are playing and where they are
+    fn refresh_positions(&mut self) {
+        self.positions.clear();
+        for &id in &self.playing {
+            if let Some(channel) = lookup(active(&mut self.mixer), &self.channels, id) {
+                let _ = self.positions.push((id, channel.pos()));
+            }
+        }
+    }
+
+    /// Whether a sound started through this mixer is still playing
+    ///
+    /// Unlike [`channel()`](Self::channel) this only needs a shared borrow, e.g. to sync
+    /// an animation to a sound from code that only sees `&AsyncMixer`. The answer is as
+    /// of the last [`frame()`](Self::frame) call, so a sound that just ran out is noticed
+    /// on the next frame. Sounds started directly on the underlying
+    /// [`mixer()`](Self::mixer) aren't tracked and always report `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(mixer: &embassy_agb::sound::AsyncMixer<'_>, roar: embassy_agb::sound::SoundId) {
+    /// let boss_mouth_open = mixer.is_playing(roar);
+    /// # }
+    /// ```
+    pub fn is_playing(&self, id: SoundId) -> bool {
+        self.positions.iter().any(|(playing, _)| *playing == id)
+    }
+
+    /// How far a sound started through this mixer has played, as an offset into its data
+    ///
+    /// The offset is in bytes of the sound's [`SoundData`]: one per sample for mono
+    /// sounds, two for stereo ones. Like [`is_playing()`](Self::is_playing) it is updated
+    /// once per [`frame()`](Self::frame), and `None` once the sound has finished.
+    pub fn channel_position(&self, id: SoundId) -> Option<usize> {
+        self.positions
+            .iter()
+            .find(|(playing, _)| *playing == id)
+            .map(|(_, pos)| pos.floor() as usize)
+    }
+
+    /// Whether any channel started with [`play_sound()`](Self::play_sound) other than
+    /// `except` is still playing
+    fn others_playing(&mut self, except: SoundId) -> bool {
+        let (mixer, channels) = (active(&mut self.mixer), &self.channels);
+        self.playing
+            .retain(|playing| lookup(mixer, channels, *playing).is_some());
+        self.playing.iter().any(|id| *id != except)
+    }
+
+    /// Stop a playing channel
+    ///
+    /// Looping channels stop right away too, without finishing their pass. Does nothing
+    /// if the channel has already finished.
+    pub fn stop_channel(&mut self, id: SoundId) {
+        if let Some(channel) = self.channel(id) {
+            channel.stop();
+        }
+        self.positions.retain(|(playing, _)| *playing != id);
+        self.loop_ends.retain(|(ending, _)| *ending != id);
+    }
+
+    /// Stop every channel started with [`play_sound()`](Self::play_sound)
+    ///
+    /// Sounds started directly on the underlying [`mixer()`](Self::mixer) are not affected.
+    pub fn stop_all(&mut self) {
+        for &id in &self.playing {
+            if let Some(channel) = lookup(active(&mut self.mixer), &self.channels, id) {
+                channel.stop();
+            }
+        }
+        let playing = &self.playing;
+        self.loop_ends
+            .retain(|(ending, _)| !playing.contains(ending));
+        self.playing.clear();
+        self.positions.clear();
+    }
+
+    /// Fade a channel out to silence over `over`, then stop it
+    ///
+    /// The volume is lowered once per frame with [`ramp_volume()`](Self::ramp_volume),
+    /// starting from the channel's current volume. Like
+    /// [`wait_for_channel()`](Self::wait_for_channel), this calls [`frame()`](Self::frame)
+    /// once per VBlank while it runs. If the channel finishes or is replaced mid-fade,
+    /// this returns early.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use embassy_agb::Duration;
+    /// # async fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>, bgm: embassy_agb::sound::SoundId) {
+    /// mixer.fade_out(bgm, Duration::from_millis(500)).await;
+    /// # }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn fade_out(&mut self, id: SoundId, over: embassy_time::Duration) {
+        self.ramp_volume(id, agb::fixnum::Num::new(0), over).await;
+        self.stop_channel(id);
     }
 
     /// Get a reference to a playing channel
     ///
     /// Returns `Some(&mut channel)` if the channel is still playing, or `None`
     /// if it has finished or been replaced.
-    pub fn channel(
-        &mut self,
-        id: &agb::sound::mixer::ChannelId,
-    ) -> Option<&mut agb::sound::mixer::SoundChannel> {
-        self.mixer.channel(id)
+    pub fn channel(&mut self, id: SoundId) -> Option<&mut SoundChannel> {
+        lookup(active(&mut self.mixer), &self.channels, id)
+    }
+
+    /// Wait until a channel has finished playing
+    ///
+    /// Resolves once [`channel()`](Self::channel) returns `None` for `id`, which happens
+    /// when the sound ends, is stopped, or its channel is taken by another sound. If the
+    /// channel is already gone this resolves immediately.
+    ///
+    /// While waiting, this calls [`frame()`](Self::frame) once per VBlank so playback keeps
+    /// advancing. Don't wait for VBlank or call `frame()` elsewhere until it resolves.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use agb::sound::mixer::SoundChannel;
+    /// # static VOICE: agb::sound::mixer::SoundData = agb::include_wav!("voice.wav");
+    /// # async fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>) {
+    /// if let Ok(id) = mixer.play_sound(SoundChannel::new(VOICE)) {
+    ///     mixer.wait_for_channel(id).await;
+    ///     // Voice line finished, start the scene transition
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_channel(&mut self, id: SoundId) {
+        while self.channel(id).is_some() {
+            self.frame_and_wait().await;
+        }
+    }
+
+    /// Process one frame of audio and wait for the following VBlank
+    pub(crate) async fn frame_and_wait(&mut self) {
+        self.frame();
+        crate::display::wait_for_vblank().await;
     }
 
     /// Get access to the underlying mixer for synchronous operations
+    ///
+    /// Sounds started on it return agb's own `ChannelId` and aren't known to the
+    /// [`SoundId`] methods of this wrapper.
     pub fn mixer(&mut self) -> &mut agb::sound::mixer::Mixer<'a> {
-        &mut self.mixer
+        active(&mut self.mixer)
+    }
+}
+
+/// Background music player with looping and crossfading
+///
+/// Plays one looping track at a time on a high-priority channel. High-priority channels
+/// are never taken over by low-priority sound effects, and the BGM channel is started on
+/// the mixer without counting it as a [`play_sound()`](AsyncMixer::play_sound) sound, so
+/// [`AsyncMixer::stop_all()`] leaves it playing.
+///
+/// The player only holds the current channel, so it can live across scenes while the
+/// mixer is passed in on each call.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use embassy_agb::sound::BgmPlayer;
+/// # use embassy_agb::Duration;
+/// # static TITLE: agb::sound::mixer::SoundData = agb::include_wav!("title.wav");
+/// # static LEVEL: agb::sound::mixer::SoundData = agb::include_wav!("level.wav");
+/// # async fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>) {
+/// let mut bgm = BgmPlayer::new();
+/// bgm.play(mixer, &TITLE);
+///
+/// // Start pressed
+/// bgm.crossfade_to(mixer, &LEVEL, Duration::from_secs(1)).await;
+/// # }
+/// ```
+#[derive(Default)]
+pub struct BgmPlayer {
+    current: Option<SoundId>,
+}
+
+impl BgmPlayer {
+    /// Create a player with no track playing
+    pub fn new() -> Self {
+        Self { current: None }
+    }
+
+    /// Start looping `track`, replacing the current one
+    pub fn play(&mut self, mixer: &mut AsyncMixer<'_>, track: &SoundData) {
+        self.stop(mixer);
+        self.current = Some(Self::start(mixer, track));
+    }
+
+    /// Stop the current track
+    pub fn stop(&mut self, mixer: &mut AsyncMixer<'_>) {
+        if let Some(id) = self.current.take() {
+            mixer.stop_channel(id);
+        }
+    }
+
+    /// Check if a track is playing
+    pub fn is_playing(&self, mixer: &mut AsyncMixer<'_>) -> bool {
+        self.current.is_some_and(|id| mixer.channel(id).is_some())
+    }
+
+    /// Get the sound ID of the current track, if any
+    pub fn channel_id(&self) -> Option<SoundId> {
+        self.current
+    }
+
+    /// Crossfade from the current track to `track` over `over`
+    ///
+    /// The old track ramps down while the new one ramps up, one step per frame, and the
+    /// old track is stopped at the end. Like [`AsyncMixer::wait_for_channel()`], this
+    /// calls [`AsyncMixer::frame()`] once per VBlank while it runs. With nothing playing,
+    /// this fades the new track in.
+    #[cfg(feature = "time")]
+    pub async fn crossfade_to(
+        &mut self,
+        mixer: &mut AsyncMixer<'_>,
+        track: &SoundData,
+        over: embassy_time::Duration,
+    ) {
+        let old = self.current.take();
+        let old_volume = old.and_then(|id| mixer.channel_volume(id));
+        let new = Self::start(mixer, track);
+        let _ = mixer.set_channel_volume(new, agb::fixnum::Num::new(0));
+        self.current = Some(new);
+
+        let (silent, full) = (agb::fixnum::Num::new(0), agb::fixnum::Num::new(1));
+        let frames = fade_frames(over);
+        for step in 1..=frames {
+            if let (Some(id), Some(from)) = (old, old_volume) {
+                let _ = mixer.set_channel_volume(id, lerp_volume(from, silent, step, frames));
+            }
+            let _ = mixer.set_channel_volume(new, lerp_volume(silent, full, step, frames));
+
+            mixer.frame_and_wait().await;
+        }
+
+        if let Some(id) = old {
+            mixer.stop_channel(id);
+        }
+    }
+
+    fn start(mixer: &mut AsyncMixer<'_>, track: &SoundData) -> SoundId {
+        let mut channel = SoundChannel::new_high_priority(*track);
+        channel.should_loop();
+        mixer
+            .start(channel)
+            .expect("high priority sounds always get a channel")
+    }
+}
+
+/// Lowers background music while sound effects or dialogue play
+///
+/// Call [`update()`](Self::update) once per frame, before [`AsyncMixer::frame()`] (or
+/// [`GbaPeripherals::wait_frame()`](crate::GbaPeripherals::wait_frame)). Each update moves
+/// the music volume one step towards its target, so both ducking and restoring are
+/// smooth ramps.
+///
+/// The music is ducked while:
+/// - in automatic mode (the default), any sound started with
+///   [`AsyncMixer::play_sound()`] (or the helpers built on it, such as [`SfxPool`]) is
+///   playing, and
+/// - a hold set by [`duck_for()`](Self::duck_for) or
+///   [`duck_frames()`](Self::duck_frames) hasn't run out.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use embassy_agb::sound::{BgmPlayer, Ducker};
+/// # static MUSIC: agb::sound::mixer::SoundData = agb::include_wav!("music.wav");
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+/// let mut bgm = BgmPlayer::new();
+/// bgm.play(&mut peripherals.mixer, &MUSIC);
+/// let mut ducker = Ducker::new(bgm.channel_id().unwrap());
+///
+/// loop {
+///     // Sound effects played here lower the music until they finish
+///     ducker.update(&mut peripherals.mixer);
+///     peripherals.wait_frame().await;
+/// }
+/// # }
+/// ```
+pub struct Ducker {
+    bgm: SoundId,
+    normal: agb::fixnum::Num<i16, 8>,
+    ducked: agb::fixnum::Num<i16, 8>,
+    ramp_frames: u16,
+    automatic: bool,
+    /// VBlank count the current hold runs until
+    hold_until: Option<usize>,
+    /// Volume applied on the last update
+    level: agb::fixnum::Num<i16, 8>,
+}
+
+impl Ducker {
+    /// Frames a full duck or restore takes by default (~130ms)
+    pub const DEFAULT_RAMP_FRAMES: u16 = 8;
+
+    /// Duck `bgm` to 40% volume in automatic mode
+    pub fn new(bgm: SoundId) -> Self {
+        Self {
+            bgm,
+            normal: agb::fixnum::Num::new(1),
+            ducked: agb::fixnum::Num::from_raw(102),
+            ramp_frames: Self::DEFAULT_RAMP_FRAMES,
+            automatic: true,
+            hold_until: None,
+            level: agb::fixnum::Num::new(1),
+        }
+    }
+
+    /// Follow a different music channel, e.g. after [`BgmPlayer::crossfade_to()`]
+    pub fn set_bgm(&mut self, bgm: SoundId) {
+        self.bgm = bgm;
+    }
+
+    /// Set the music volume when not ducked and while ducked
+    pub fn set_levels(
+        &mut self,
+        normal: agb::fixnum::Num<i16, 8>,
+        ducked: agb::fixnum::Num<i16, 8>,
+    ) {
+        self.normal = normal;
+        self.ducked = ducked;
+    }
+
+    /// Set how many frames a full duck or restore takes (at least 1)
+    pub fn set_ramp_frames(&mut self, frames: u16) {
+        self.ramp_frames = frames.max(1);
+    }
+
+    /// Turn ducking under [`AsyncMixer::play_sound()`] sounds on or off
+    ///
+    /// With automatic mode off, only [`duck_for()`](Self::duck_for) and
+    /// [`duck_frames()`](Self::duck_frames) duck the music.
+    pub fn set_automatic(&mut self, automatic: bool) {
+        self.automatic = automatic;
+    }
+
+    /// Keep the music ducked for at least `duration`, e.g. for a line of dialogue
+    #[cfg(feature = "time")]
+    pub fn duck_for(&mut self, duration: embassy_time::Duration) {
+        self.duck_frames(fade_frames(duration) as u32);
+    }
+
+    /// Keep the music ducked for at least `frames` frames
+    pub fn duck_frames(&mut self, frames: u32) {
+        let until = crate::display::vblank_count().wrapping_add(frames as usize);
+        let later = match self.hold_until {
+            Some(current) if (current.wrapping_sub(until) as isize) > 0 => current,
+            _ => until,
+        };
+        self.hold_until = Some(later);
+    }
+
+    /// Whether the music is currently below its normal volume
+    pub fn is_ducked(&self) -> bool {
+        self.level < self.normal
+    }
+
+    /// Move the music volume one step towards its target
+    ///
+    /// Does nothing once the music channel has finished.
+    pub fn update(&mut self, mixer: &mut AsyncMixer<'_>) {
+        if let Some(until) = self.hold_until {
+            if (until.wrapping_sub(crate::display::vblank_count()) as isize) <= 0 {
+                self.hold_until = None;
+            }
+        }
+
+        let duck = self.hold_until.is_some() || (self.automatic && mixer.others_playing(self.bgm));
+        let target = if duck { self.ducked } else { self.normal };
+
+        let span = (self.normal.to_raw() as i32 - self.ducked.to_raw() as i32).abs();
+        let step = (span / self.ramp_frames as i32).max(1);
+        let (level, target) = (self.level.to_raw() as i32, target.to_raw() as i32);
+        let next = level + (target - level).clamp(-step, step);
+
+        self.level = agb::fixnum::Num::from_raw(next as i16);
+        let _ = mixer.set_channel_volume(self.bgm, self.level);
+    }
+}
+
+/// Pool of sound effect channels that recycles the oldest effect when full
+///
+/// [`AsyncMixer::play_sound()`] fails when every channel is busy. `SfxPool` instead limits
+/// sound effects to a fixed number of channels and, once that budget is used up, stops
+/// the oldest effect it started to make room for the new one. Only channels started by
+/// the pool are ever recycled, so high-priority sounds such as [`BgmPlayer`] music keep
+/// playing.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use embassy_agb::sound::SfxPool;
+/// # static ROCKET: agb::sound::mixer::SoundData = agb::include_wav!("rocket.wav");
+/// # fn example(mixer: &mut embassy_agb::sound::AsyncMixer<'_>) {
+/// // Leave the other channels free for music and voices
+/// let mut sfx = SfxPool::new(4);
+///
+/// // Rapid fire never fails, the oldest rocket sound is cut off instead
+/// let _ = sfx.play(mixer, &ROCKET);
+/// # }
+/// ```
+pub struct SfxPool {
+    max_channels: usize,
+    /// Channels started by the pool with the VBlank count they started on, oldest first
+    playing: heapless::Vec<(SoundId, usize), 8>,
+}
+
+impl SfxPool {
+    /// Create a pool that uses at most `max_channels` channels (clamped to 1-8)
+    pub fn new(max_channels: usize) -> Self {
+        Self {
+            max_channels: max_channels.clamp(1, 8),
+            playing: heapless::Vec::new(),
+        }
+    }
+
+    /// Play a sound effect, recycling the oldest one if the pool is full
+    ///
+    /// Returns `Err(SoundError)` only if no channel could be freed, which happens when
+    /// the pool has nothing playing and every channel is in use by other sounds.
+    pub fn play(
+        &mut self,
+        mixer: &mut AsyncMixer<'_>,
+        sound: &SoundData,
+    ) -> Result<SoundId, SoundError> {
+        self.playing.retain(|(id, _)| mixer.channel(*id).is_some());
+
+        if self.playing.len() >= self.max_channels {
+            self.recycle_oldest(mixer);
+        }
+
+        let id = match mixer.play_sound(SoundChannel::new(*sound)) {
+            Ok(id) => id,
+            // Channels outside the pool are busy, make room in our own budget instead
+            Err(_) if self.recycle_oldest(mixer) => mixer.play_sound(SoundChannel::new(*sound))?,
+            Err(error) => return Err(error),
+        };
+
+        let started = crate::display::vblank_count();
+        let _ = self.playing.push((id, started));
+        Ok(id)
+    }
+
+    /// Get the number of sound effects the pool currently has playing
+    pub fn playing(&mut self, mixer: &mut AsyncMixer<'_>) -> usize {
+        self.playing.retain(|(id, _)| mixer.channel(*id).is_some());
+        self.playing.len()
+    }
+
+    /// Stop every sound effect started by the pool
+    pub fn stop_all(&mut self, mixer: &mut AsyncMixer<'_>) {
+        for &(id, _) in &self.playing {
+            mixer.stop_channel(id);
+        }
+        self.playing.clear();
+    }
+
+    /// Stop the oldest sound effect, returning false if nothing was playing
+    fn recycle_oldest(&mut self, mixer: &mut AsyncMixer<'_>) -> bool {
+        let Some(oldest) = self
+            .playing
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, started))| *started)
+            .map(|(index, _)| index)
+        else {
+            return false;
+        };
+
+        let (id, _) = self.playing.remove(oldest);
+        mixer.stop_channel(id);
+        true
     }
 }
@@ -0,0 +1,110 @@
+//! Synchronization primitives for sharing data between tasks
+//!
+//! Everything from [`embassy_sync`] is re-exported here, so existing paths such as
+//! `embassy_agb::sync::signal::Signal` keep working. [`Shared`] covers the most common
+//! case, a value read and written from several tasks, and [`Signal`] and [`Channel`]
+//! are embassy's types with the mutex already chosen.
+//!
+//! All of these use [`CriticalSectionRawMutex`], which is correct for the single-core
+//! GBA and also works from interrupt handlers.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+
+pub use embassy_sync::*;
+
+/// [`signal::Signal`] for the GBA: the latest value, handed to one waiting task
+///
+/// Suits one-shot notifications such as "the player died", where only the newest event
+/// matters.
+pub type Signal<T> = signal::Signal<CriticalSectionRawMutex, T>;
+
+/// [`channel::Channel`] for the GBA: a queue of up to `N` values between tasks
+///
+/// Unlike [`Signal`], no value is lost while the queue has room.
+pub type Channel<T, const N: usize> = channel::Channel<CriticalSectionRawMutex, T, N>;
+
+/// A value shared between tasks and interrupt handlers
+///
+/// Backed by a blocking [`Mutex`] over [`CriticalSectionRawMutex`], the right choice on
+/// the single-core GBA: each access runs with interrupts disabled, so tasks and
+/// interrupt handlers always see a consistent value, and there is no lock to hold
+/// across an `.await`. Keep the closures passed to [`update()`](Self::update) short, as
+/// interrupts wait while they run.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::sync::Shared;
+///
+/// #[derive(Clone, Copy, Default)]
+/// struct ButtonState {
+///     movement: (i32, i32),
+///     fire: bool,
+/// }
+///
+/// static BUTTONS: Shared<ButtonState> = Shared::new(ButtonState {
+///     movement: (0, 0),
+///     fire: false,
+/// });
+///
+/// // Input task
+/// BUTTONS.update(|state| state.fire = true);
+///
+/// // Game loop: read and clear the one-shot flag together
+/// let fire = BUTTONS.update(|state| core::mem::take(&mut state.fire));
+/// let movement = BUTTONS.get().movement;
+/// ```
+pub struct Shared<T> {
+    inner: Mutex<CriticalSectionRawMutex, RefCell<T>>,
+}
+
+impl<T> Shared<T> {
+    /// Share `value`, usable in a `static`
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(value)),
+        }
+    }
+
+    /// Replace the value
+    pub fn set(&self, value: T) {
+        self.replace(value);
+    }
+
+    /// Replace the value, returning the old one
+    pub fn replace(&self, value: T) -> T {
+        self.inner.lock(|cell| cell.replace(value))
+    }
+
+    /// Change the value in place, returning whatever `f` returns
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` accesses this same `Shared`.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.inner.lock(|cell| f(&mut cell.borrow_mut()))
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Copy of the current value
+    pub fn get(&self) -> T {
+        self.inner.lock(|cell| cell.borrow().clone())
+    }
+}
+
+impl<T: Default> Shared<T> {
+    /// Take the value, leaving the default in its place
+    pub fn take(&self) -> T {
+        self.replace(T::default())
+    }
+}
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
@@ -1,7 +1,8 @@
 //! Embassy time driver using GBA hardware timers
 //!
-//! Uses one of the four 16-bit GBA timers with Divider256 (65.536kHz) to provide
-//! embassy-time's 32.768kHz tick rate.
+//! Uses one of the four 16-bit GBA timers with Divider256 (65.536kHz), scaled to the
+//! embassy-time tick rate (`TICK_HZ`, 32.768kHz with the default `tick-hz-32_768`
+//! feature).
 //!
 //! ## Timer Registers (per timer n=0-3)
 //! - `TM{n}CNT_L` (0x4000100 + n*4): Counter/Reload
@@ -13,10 +14,20 @@
 //! - `IME` (0x4000208): Master Enable
 //!
 //! Default: Timer 2, 64-count overflow (~1ms ticks, ~1000 interrupts/sec)
+//!
+//! ## Cascade mode
+//! With [`TimerConfig::cascade`], timer N runs freely and cascades into timer N+1, giving
+//! a 32-bit counter that only interrupts when it wraps (every ~18 hours). `now()` keeps
+//! the full 65.536kHz precision, but with no periodic timer interrupt the alarm queue is
+//! serviced from the VBlank interrupt instead, so timers fire at frame granularity.
+//!
+//! The driver starts on the default [`TimerConfig`] when the executor starts, then
+//! [`crate::init()`] moves it to the timer chosen in [`crate::Config`]. Time accumulated
+//! on the previous timer is carried over so `now()` never goes backwards.
 
 use core::cell::{Cell, RefCell};
 use core::sync::atomic::{compiler_fence, Ordering};
-use portable_atomic::AtomicU32;
+use portable_atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8};
 
 use critical_section::CriticalSection;
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
@@ -27,101 +38,117 @@ use embassy_time_queue_utils::Queue;
 use agb::interrupt::{add_interrupt_handler, Interrupt};
 use agb::timer::{Divider, Timer};
 
-/// Timer selection via feature flags (default: Timer 2)
-/// Note: Timer 0-1 often used by sound system
-const TIMER_NUMBER: u16 = if cfg!(feature = "time-driver-timer0") {
-    0
-} else if cfg!(feature = "time-driver-timer1") {
-    1
-} else if cfg!(feature = "time-driver-timer2") {
-    2
-} else if cfg!(feature = "time-driver-timer3") {
-    3
-} else {
-    // This will be caught by the compile-time check below
-    0
-};
-
-/// Compile-time check to ensure exactly one timer is selected
-const _: () = {
-    let timer_count =
-        0 + if cfg!(feature = "time-driver-timer0") {
-            1
-        } else {
-            0
-        } + if cfg!(feature = "time-driver-timer1") {
-            1
-        } else {
-            0
-        } + if cfg!(feature = "time-driver-timer2") {
-            1
-        } else {
-            0
-        } + if cfg!(feature = "time-driver-timer3") {
-            1
-        } else {
-            0
-        };
-
-    if timer_count == 0 {
-        panic!(
-            "No timer selected for embassy-agb time driver. Enable exactly one of: time-driver-timer0, time-driver-timer1, time-driver-timer2, time-driver-timer3"
-        );
-    }
-    if timer_count > 1 {
-        panic!(
-            "Multiple timers selected for embassy-agb time driver. Enable exactly one of: time-driver-timer0, time-driver-timer1, time-driver-timer2, time-driver-timer3"
-        );
-    }
-};
-
-/// Get the appropriate timer interrupt based on selected timer
-const fn get_timer_interrupt() -> Interrupt {
-    match TIMER_NUMBER {
-        0 => Interrupt::Timer0,
-        1 => Interrupt::Timer1,
-        2 => Interrupt::Timer2,
-        3 => Interrupt::Timer3,
-        _ => unreachable!(),
+use crate::config::{TimerConfig, TimerNumber};
+
+// No timer feature is fine (Timer2 is used), but more than one is almost always a mistake
+#[cfg(any(
+    all(feature = "time-driver-timer0", feature = "time-driver-timer1"),
+    all(feature = "time-driver-timer0", feature = "time-driver-timer2"),
+    all(feature = "time-driver-timer0", feature = "time-driver-timer3"),
+    all(feature = "time-driver-timer1", feature = "time-driver-timer2"),
+    all(feature = "time-driver-timer1", feature = "time-driver-timer3"),
+    all(feature = "time-driver-timer2", feature = "time-driver-timer3"),
+))]
+compile_error!(
+    "embassy-agb: more than one `time-driver-timerN` feature is enabled. Keep at most one \
+     (or just `time-driver`, which defaults to Timer2), or pick the timer at runtime with \
+     `Config::timer.timer_number`."
+);
+
+/// IF register: pending interrupts, written with a 1 bit to acknowledge one
+const INTERRUPT_FLAGS: *mut u16 = 0x0400_0202 as *mut u16;
+
+/// Hardware timer index for a [`TimerNumber`]
+const fn timer_index(timer: TimerNumber) -> u8 {
+    match timer {
+        TimerNumber::Timer0 => 0,
+        TimerNumber::Timer1 => 1,
+        TimerNumber::Timer2 => 2,
+        TimerNumber::Timer3 => 3,
     }
 }
 
 /// Default overflow: 64 counts = ~1ms at 65.536kHz
 const DEFAULT_TIMER_OVERFLOW_AMOUNT: u16 = 64;
 
-/// Convert timer hardware ticks (65.536kHz) to embassy ticks (32.768kHz)
+/// Read a consistent `(period, counter)` pair
+///
+/// The counter can overflow between the two reads, and inside a critical section (where
+/// `now()` runs) the overflow interrupt stays pending, so `period` doesn't count it yet.
+/// Either way the pair would put time a whole period in the past. `period` is read on
+/// both sides of the counter, retrying if the interrupt ran in between, and an overflow
+/// that is still pending is counted here, with the counter read again so the value is
+/// certainly from after the wrap.
+fn snapshot(
+    mut read_period: impl FnMut() -> u32,
+    mut read_counter: impl FnMut() -> u32,
+    mut overflow_pending: impl FnMut() -> bool,
+) -> (u32, u32) {
+    loop {
+        let period = read_period();
+        compiler_fence(Ordering::SeqCst);
+        let counter = read_counter();
+        compiler_fence(Ordering::SeqCst);
+        let pending = overflow_pending();
+        compiler_fence(Ordering::SeqCst);
+
+        if read_period() != period {
+            continue;
+        }
+        if pending {
+            return (period.wrapping_add(1), read_counter());
+        }
+        return (period, counter);
+    }
+}
+
+/// Rate of the hardware timers with Divider256
+const HARDWARE_HZ: u64 = 65_536;
+
+/// Convert 65.536kHz hardware ticks to embassy ticks at `tick_hz`, rounding down
+///
+/// Whole seconds and the remainder are scaled separately, so nothing overflows for any
+/// tick rate embassy-time supports. At 32.768kHz this is a shift.
+const fn to_embassy_ticks(hardware_ticks: u64, tick_hz: u64) -> u64 {
+    let seconds = hardware_ticks / HARDWARE_HZ;
+    let rest = hardware_ticks % HARDWARE_HZ;
+    seconds * tick_hz + rest * tick_hz / HARDWARE_HZ
+}
+
+/// Convert a cascaded 32-bit count (65.536kHz) to embassy ticks
+fn calc_now_cascade(period: u32, counter: u32, initial_timer_value: u32) -> u64 {
+    let hardware_ticks_elapsed =
+        (((period as u64) << 32) | counter as u64).wrapping_sub(initial_timer_value as u64);
+
+    to_embassy_ticks(hardware_ticks_elapsed, embassy_time_driver::TICK_HZ)
+}
+
+/// Convert timer hardware ticks (65.536kHz) to embassy ticks
+///
+/// The timer counts from `65536 - timer_overflow_amount` (its reload value) up to 65535,
+/// then overflows back to the reload value, so the instant of an overflow reads as the
+/// reload value with `period` already counting it (see [`snapshot()`]). Every period,
+/// including the first, is measured from the reload value, so the count carries on
+/// without a gap from one period into the next.
 fn calc_now(
     period: u32,
     counter: u16,
     initial_timer_value: u32,
     timer_overflow_amount: u32,
 ) -> u64 {
-    let overflow_start = 65536 - timer_overflow_amount;
-
-    let hardware_ticks_elapsed = if period == 0 {
-        // No overflows yet - calculate ticks from initial timer value
-        if counter >= initial_timer_value as u16 {
-            (counter - initial_timer_value as u16) as u64
-        } else {
-            // Counter wrapped around
-            ((65536 - initial_timer_value) + counter as u32) as u64
-        }
-    } else {
-        // Calculate ticks from completed periods plus current period progress
-        let ticks_from_completed_periods = period as u64 * timer_overflow_amount as u64;
+    let reload = 65536 - timer_overflow_amount;
 
-        let ticks_in_current_period = if counter >= overflow_start as u16 {
-            (counter - overflow_start as u16) as u64
-        } else {
-            // Timer wrapped from 65535 to 0
-            ((65536 - overflow_start) + counter as u32) as u64
-        };
+    // A running timer never reads below its reload value, but never count such a read
+    // as more than the start of the period
+    let ticks_in_current_period = (counter as u32).saturating_sub(reload);
+    // The timer may have been a count or two past the reload value when first read
+    let ticks_before_start = initial_timer_value.saturating_sub(reload);
 
-        ticks_from_completed_periods + ticks_in_current_period
-    };
+    let hardware_ticks_elapsed = (period as u64 * timer_overflow_amount as u64
+        + ticks_in_current_period as u64)
+        .saturating_sub(ticks_before_start as u64);
 
-    // Convert 65.536kHz hardware ticks to 32.768kHz embassy ticks
-    hardware_ticks_elapsed >> 1
+    to_embassy_ticks(hardware_ticks_elapsed, embassy_time_driver::TICK_HZ)
 }
 
 struct AlarmState {
@@ -138,17 +165,80 @@ impl AlarmState {
     }
 }
 
+/// Hardware timers owned by the driver
+struct DriverTimers {
+    /// Counts at 65.536kHz
+    low: Timer,
+    /// Counts `low` overflows in cascade mode
+    high: Option<Timer>,
+}
+
+impl DriverTimers {
+    /// The timer whose overflow interrupt counts periods
+    fn irq_timer(&self) -> &Timer {
+        self.high.as_ref().unwrap_or(&self.low)
+    }
+
+    /// Whether the period counting timer has overflowed without its interrupt being handled
+    fn overflow_pending(&self) -> bool {
+        let bit = self.irq_timer().interrupt() as u16;
+        unsafe { INTERRUPT_FLAGS.read_volatile() & (1 << bit) != 0 }
+    }
+
+    /// Read the counter, combining both timers in cascade mode
+    fn value(&self) -> u32 {
+        match &self.high {
+            None => self.low.value() as u32,
+            Some(high) => {
+                let mut hi = high.value();
+                let mut lo = self.low.value();
+                let hi_again = high.value();
+                if hi != hi_again {
+                    // `low` wrapped between the reads, so take a fresh low half
+                    hi = hi_again;
+                    lo = self.low.value();
+                }
+                ((hi as u32) << 16) | lo as u32
+            }
+        }
+    }
+
+    /// Stop both timers, acknowledging any overflow that is already pending
+    fn stop(&mut self) {
+        for timer in core::iter::once(&mut self.low).chain(self.high.as_mut()) {
+            timer.set_interrupt(false).set_enabled(false);
+            let bit = timer.interrupt() as u16;
+            unsafe { INTERRUPT_FLAGS.write_volatile(1 << bit) };
+        }
+    }
+}
+
 /// Embassy time driver using GBA hardware timer
 struct GbaTimeDriver {
+    /// Embassy ticks accumulated before the timer was last restarted
+    base: AtomicU64,
+    /// Index of the hardware timer currently driving time
+    timer_number: AtomicU8,
+    /// Whether two timers are cascaded into a 32-bit counter
+    cascade: AtomicBool,
+    /// Bit per hardware timer whose interrupt handler has been installed (bit 4: VBlank)
+    installed_handlers: AtomicU8,
+    /// Timer interrupts handled since boot, across restarts
+    interrupts: AtomicU64,
     period: AtomicU32,
     initial_timer_value: AtomicU32,
     timer_overflow_amount: AtomicU32,
     alarms: Mutex<CriticalSectionRawMutex, AlarmState>,
     queue: Mutex<CriticalSectionRawMutex, RefCell<Queue>>,
-    timer: Mutex<CriticalSectionRawMutex, RefCell<Option<Timer>>>,
+    timer: Mutex<CriticalSectionRawMutex, RefCell<Option<DriverTimers>>>,
 }
 
 embassy_time_driver::time_driver_impl!(static DRIVER: GbaTimeDriver = GbaTimeDriver {
+    base: AtomicU64::new(0),
+    timer_number: AtomicU8::new(u8::MAX),
+    cascade: AtomicBool::new(false),
+    installed_handlers: AtomicU8::new(0),
+    interrupts: AtomicU64::new(0),
     period: AtomicU32::new(0),
     initial_timer_value: AtomicU32::new(0),
     timer_overflow_amount: AtomicU32::new(DEFAULT_TIMER_OVERFLOW_AMOUNT as u32),
@@ -158,58 +248,159 @@ embassy_time_driver::time_driver_impl!(static DRIVER: GbaTimeDriver = GbaTimeDri
 });
 
 impl GbaTimeDriver {
-    fn init(&'static self) {
-        self.init_timer();
-    }
-
-    /// Configure timer overflow (lower = better precision, more CPU overhead)
+    /// Start driving time from the timer in `config`, carrying over the elapsed time
     ///
-    /// At 65.536kHz: 4=~61μs, 16=~244μs, 64=~1ms (default), 256=~4ms, 1024=~16ms
-    pub fn set_timer_frequency(&self, overflow_amount: u16) {
-        self.timer_overflow_amount
-            .store(overflow_amount as u32, Ordering::Relaxed);
-    }
+    /// Does nothing if the timer is already running with this configuration.
+    fn start(&self, config: &TimerConfig) {
+        assert!(
+            config.overflow_amount > 0,
+            "timer overflow amount must be non-zero"
+        );
+        assert!(
+            !(config.cascade && config.timer_number == TimerNumber::Timer3),
+            "cascade mode needs two consecutive timers, so it can't start on Timer3"
+        );
+        let number = timer_index(config.timer_number);
 
-    fn init_timer(&self) {
         critical_section::with(|cs| {
             let mut timer_ref = self.timer.borrow(cs).borrow_mut();
 
+            if let Some(old) = timer_ref.as_mut() {
+                let cascade = self.cascade.load(Ordering::Relaxed);
+                if self.timer_number.load(Ordering::Relaxed) == number
+                    && cascade == config.cascade
+                    && (cascade
+                        || self.timer_overflow_amount.load(Ordering::Relaxed)
+                            == config.overflow_amount as u32)
+                {
+                    return;
+                }
+
+                // Fold the time so far into the base before the counters restart
+                let elapsed = self.elapsed(old);
+                self.base.fetch_add(elapsed, Ordering::Relaxed);
+
+                old.stop();
+            }
+
             let gba = unsafe { crate::_internal::get_agb_instance() };
             let all_timers = unsafe { gba.timers.all_timers() };
-            let mut timer = match TIMER_NUMBER {
-                0 => all_timers.timer0,
-                1 => all_timers.timer1,
-                2 => all_timers.timer2,
-                3 => all_timers.timer3,
-                _ => unreachable!(),
+            let mut timers = [
+                Some(all_timers.timer0),
+                Some(all_timers.timer1),
+                Some(all_timers.timer2),
+                Some(all_timers.timer3),
+            ];
+            let mut low = timers[number as usize].take().unwrap();
+            let mut high = if config.cascade {
+                timers[number as usize + 1].take()
+            } else {
+                None
+            };
+
+            self.period.store(0, Ordering::Relaxed);
+            self.timer_overflow_amount
+                .store(config.overflow_amount as u32, Ordering::Relaxed);
+            self.timer_number.store(number, Ordering::Relaxed);
+            self.cascade.store(config.cascade, Ordering::Relaxed);
+
+            // The timer whose overflow interrupt counts periods
+            let irq_timer = match high.as_mut() {
+                Some(high) => {
+                    // Free-running low half feeding a cascaded high half
+                    high.set_overflow_amount(0)
+                        .set_cascade(true)
+                        .set_interrupt(true)
+                        .set_enabled(true);
+                    low.set_divider(Divider::Divider256) // 65.536kHz
+                        .set_overflow_amount(0)
+                        .set_interrupt(false)
+                        .set_enabled(true);
+                    high
+                }
+                None => {
+                    low.set_divider(Divider::Divider256) // 65.536kHz
+                        .set_overflow_amount(config.overflow_amount)
+                        .set_interrupt(true)
+                        .set_enabled(true);
+                    &mut low
+                }
             };
+            let irq_interrupt = irq_timer.interrupt();
+            let irq_number = irq_interrupt as u8 - Interrupt::Timer0 as u8;
 
-            let overflow_amount = self.timer_overflow_amount.load(Ordering::Relaxed) as u16;
-            timer
-                .set_divider(Divider::Divider256) // 65.536kHz
-                .set_overflow_amount(overflow_amount)
-                .set_interrupt(true)
-                .set_enabled(true);
+            let timers = DriverTimers { low, high };
 
             // Capture initial timer value
-            let initial_value = timer.value();
             self.initial_timer_value
-                .store(initial_value as u32, Ordering::Relaxed);
+                .store(timers.value(), Ordering::Relaxed);
+
+            // Install the interrupt handler once per timer. Handlers for timers that are
+            // no longer in use stay installed but ignore their interrupts.
+            if self
+                .installed_handlers
+                .fetch_or(1 << irq_number, Ordering::Relaxed)
+                & (1 << irq_number)
+                == 0
+            {
+                let handler = unsafe {
+                    add_interrupt_handler(irq_interrupt, move |_| {
+                        DRIVER.on_interrupt(irq_number);
+                    })
+                };
+                core::mem::forget(handler);
+            }
 
-            // Install interrupt handler for selected timer
-            let handler = unsafe {
-                add_interrupt_handler(get_timer_interrupt(), |_| {
-                    DRIVER.on_interrupt();
-                })
-            };
-            core::mem::forget(handler);
+            // Cascade mode has no periodic timer interrupt, so service alarms every frame
+            if config.cascade
+                && self.installed_handlers.fetch_or(1 << 4, Ordering::Relaxed) & (1 << 4) == 0
+            {
+                let handler = unsafe {
+                    add_interrupt_handler(Interrupt::VBlank, |cs| {
+                        if DRIVER.cascade.load(Ordering::Relaxed) {
+                            crate::watchdog::check(DRIVER.now());
+                            DRIVER.trigger_alarm(cs);
+                        }
+                    })
+                };
+                core::mem::forget(handler);
+            }
 
-            *timer_ref = Some(timer);
+            *timer_ref = Some(timers);
         });
     }
 
-    fn on_interrupt(&self) {
+    /// Embassy ticks counted by `timers` since they were started
+    fn elapsed(&self, timers: &DriverTimers) -> u64 {
+        let initial_timer_value = self.initial_timer_value.load(Ordering::Relaxed);
+        let (period, counter) = snapshot(
+            || self.period.load(Ordering::Relaxed),
+            || timers.value(),
+            || timers.overflow_pending(),
+        );
+
+        if self.cascade.load(Ordering::Relaxed) {
+            calc_now_cascade(period, counter, initial_timer_value)
+        } else {
+            calc_now(
+                period,
+                counter as u16,
+                initial_timer_value,
+                self.timer_overflow_amount.load(Ordering::Relaxed),
+            )
+        }
+    }
+
+    fn on_interrupt(&self, number: u8) {
+        let irq_timer =
+            self.timer_number.load(Ordering::Relaxed) + self.cascade.load(Ordering::Relaxed) as u8;
+        if irq_timer != number {
+            return;
+        }
+
         self.period.fetch_add(1, Ordering::Relaxed);
+        self.interrupts.fetch_add(1, Ordering::Relaxed);
+        crate::watchdog::check(self.now());
         critical_section::with(|cs| {
             self.trigger_alarm(cs);
         });
@@ -245,27 +436,17 @@ impl GbaTimeDriver {
             true
         }
     }
-
-    fn read_timer_value(&self) -> u16 {
-        critical_section::with(|cs| {
-            let timer_ref = self.timer.borrow(cs).borrow();
-            if let Some(timer) = timer_ref.as_ref() {
-                timer.value()
-            } else {
-                0
-            }
-        })
-    }
 }
 
 impl Driver for GbaTimeDriver {
     fn now(&self) -> u64 {
-        let period = self.period.load(Ordering::Relaxed);
-        let initial_timer_value = self.initial_timer_value.load(Ordering::Relaxed);
-        let timer_overflow_amount = self.timer_overflow_amount.load(Ordering::Relaxed);
-        compiler_fence(Ordering::Acquire);
-        let counter = self.read_timer_value();
-        calc_now(period, counter, initial_timer_value, timer_overflow_amount)
+        critical_section::with(|cs| {
+            let base = self.base.load(Ordering::Relaxed);
+            match self.timer.borrow(cs).borrow().as_ref() {
+                Some(timers) => base + self.elapsed(timers),
+                None => base,
+            }
+        })
     }
 
     fn schedule_wake(&self, at: u64, waker: &core::task::Waker) {
@@ -281,15 +462,284 @@ impl Driver for GbaTimeDriver {
     }
 }
 
-/// Initialize the time driver
+/// Start the time driver on the default timer configuration
 pub(crate) fn init() {
-    DRIVER.init();
+    DRIVER.start(&TimerConfig::default());
 }
 
-/// Configure the timer interrupt frequency
+/// Move the time driver to the timer and overflow amount in `config`
 ///
-/// This must be called before using any embassy-time functionality.
-/// The configuration is typically set through the Config struct in init().
-pub(crate) fn configure_timer_frequency(overflow_amount: u16) {
-    DRIVER.set_timer_frequency(overflow_amount);
+/// Called from [`crate::init()`] to apply the user's [`crate::Config`].
+pub(crate) fn configure(config: &TimerConfig) {
+    DRIVER.start(config);
+}
+
+/// Current driver time in ticks
+pub(crate) fn now() -> u64 {
+    DRIVER.now()
+}
+
+/// Interrupt count and overflow amount of the running timer
+pub(crate) fn stats() -> crate::TimerStats {
+    let overflow_amount = if DRIVER.cascade.load(Ordering::Relaxed) {
+        0
+    } else {
+        DRIVER.timer_overflow_amount.load(Ordering::Relaxed) as u16
+    };
+
+    crate::TimerStats {
+        interrupts: DRIVER.interrupts.load(Ordering::Relaxed),
+        overflow_amount,
+    }
+}
+
+/// Change the overflow amount of the running timer, keeping `now()` monotonic
+pub(crate) fn reconfigure(overflow_amount: u16) {
+    let timer_number = match DRIVER.timer_number.load(Ordering::Relaxed) {
+        0 => TimerNumber::Timer0,
+        1 => TimerNumber::Timer1,
+        2 => TimerNumber::Timer2,
+        3 => TimerNumber::Timer3,
+        _ => TimerConfig::default().timer_number,
+    };
+
+    DRIVER.start(&TimerConfig {
+        timer_number,
+        overflow_amount,
+        cascade: DRIVER.cascade.load(Ordering::Relaxed),
+    });
+}
+
+/// Panic if the time driver runs on a timer the sound mixer needs
+///
+/// agb's mixer drives the sound FIFO from Timer0 and its buffer swaps from Timer1.
+pub(crate) fn assert_sound_timers_free() {
+    let number = DRIVER.timer_number.load(Ordering::Relaxed);
+    assert!(
+        number > 1,
+        "embassy-agb time driver is running on Timer{number}, which the sound mixer also uses. \
+         Select TimerNumber::Timer2 or TimerNumber::Timer3 in Config::timer to use sound."
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Timer reload value for the default overflow amount
+    const RELOAD: u32 = 65536 - DEFAULT_TIMER_OVERFLOW_AMOUNT as u32;
+
+    /// Hardware timer plus driver period, with the interrupt delivered by hand
+    struct SimulatedTimer {
+        counter: Cell<u32>,
+        period: Cell<u32>,
+        pending: Cell<bool>,
+        /// Counts the timer advances on every counter read
+        step: u32,
+    }
+
+    impl SimulatedTimer {
+        fn new(counter: u32, step: u32) -> Self {
+            Self {
+                counter: Cell::new(counter),
+                period: Cell::new(0),
+                pending: Cell::new(false),
+                step,
+            }
+        }
+
+        fn read_counter(&self) -> u32 {
+            let value = self.counter.get();
+            let next = value + self.step;
+            if next > 0xFFFF {
+                self.counter.set(RELOAD + (next - 0x10000));
+                self.pending.set(true);
+            } else {
+                self.counter.set(next);
+            }
+            value
+        }
+
+        fn run_interrupt(&self) {
+            if self.pending.replace(false) {
+                self.period.set(self.period.get() + 1);
+            }
+        }
+
+        fn now(&self) -> u64 {
+            let (period, counter) = snapshot(
+                || self.period.get(),
+                || self.read_counter(),
+                || self.pending.get(),
+            );
+            calc_now(
+                period,
+                counter as u16,
+                RELOAD,
+                DEFAULT_TIMER_OVERFLOW_AMOUNT as u32,
+            )
+        }
+    }
+
+    /// Embassy ticks for a hardware tick count at the compiled tick rate
+    fn ticks(hardware_ticks: u64) -> u64 {
+        to_embassy_ticks(hardware_ticks, embassy_time_driver::TICK_HZ)
+    }
+
+    /// calc_now() with the default overflow amount
+    fn now_at(period: u32, counter: u32, initial: u32) -> u64 {
+        calc_now(
+            period,
+            counter as u16,
+            initial,
+            DEFAULT_TIMER_OVERFLOW_AMOUNT as u32,
+        )
+    }
+
+    #[test_case]
+    fn calc_now_overflow_is_counted_once(_gba: &mut agb::Gba) {
+        // Last count of period 4, then the overflow instant at the reload value
+        assert_eq!(now_at(5, RELOAD, RELOAD), ticks(5 * 64));
+        assert_eq!(now_at(4, 0xFFFF, RELOAD), ticks(5 * 64 - 1));
+
+        // Reads below the reload value can't happen while running; they count as the
+        // start of the period rather than a whole extra one
+        assert_eq!(now_at(5, RELOAD - 1, RELOAD), now_at(5, RELOAD, RELOAD));
+        assert_eq!(now_at(5, 0, RELOAD), now_at(5, RELOAD, RELOAD));
+    }
+
+    #[test_case]
+    fn calc_now_is_gap_free_across_overflows(_gba: &mut agb::Gba) {
+        // Started a couple of counts past the reload value
+        for initial in [RELOAD, RELOAD + 2] {
+            let mut hardware_ticks = 0;
+            let mut last = now_at(0, initial, initial);
+            assert_eq!(last, 0);
+
+            let first_period = (initial..=0xFFFF).map(|counter| (0, counter));
+            let later_periods =
+                (1..4).flat_map(|period| (RELOAD..=0xFFFF).map(move |counter| (period, counter)));
+            for (period, counter) in first_period.chain(later_periods).skip(1) {
+                hardware_ticks += 1;
+                let now = now_at(period, counter, initial);
+                assert_eq!(
+                    now,
+                    ticks(hardware_ticks),
+                    "period {period} counter {counter}"
+                );
+                assert!(now >= last);
+                last = now;
+            }
+        }
+    }
+
+    /// Tick rates from embassy-time's `tick-hz-*` features, smallest to largest
+    const TICK_RATES: [u64; 6] = [1, 1_000, 32_768, 65_536, 1_000_000, 5_242_880_000];
+
+    #[test_case]
+    fn embassy_ticks_scale_to_tick_rate(_gba: &mut agb::Gba) {
+        for tick_hz in TICK_RATES {
+            assert_eq!(to_embassy_ticks(0, tick_hz), 0);
+            assert_eq!(to_embassy_ticks(HARDWARE_HZ, tick_hz), tick_hz);
+            assert_eq!(to_embassy_ticks(HARDWARE_HZ / 2, tick_hz), tick_hz / 2);
+            assert_eq!(
+                to_embassy_ticks(HARDWARE_HZ * 3 / 2, tick_hz),
+                tick_hz * 3 / 2
+            );
+
+            // A century of uptime doesn't overflow
+            let century = 100 * 365 * 24 * 60 * 60;
+            assert_eq!(
+                to_embassy_ticks(century * HARDWARE_HZ, tick_hz),
+                century * tick_hz
+            );
+        }
+
+        // The default rate is exactly half the hardware rate
+        for hardware_ticks in [1, 2, 3, 65_535, 65_537, 1 << 40] {
+            assert_eq!(
+                to_embassy_ticks(hardware_ticks, 32_768),
+                hardware_ticks >> 1
+            );
+        }
+    }
+
+    #[test_case]
+    fn embassy_ticks_are_monotonic_at_every_tick_rate(_gba: &mut agb::Gba) {
+        for tick_hz in TICK_RATES {
+            // Never more than a hardware tick's worth of embassy ticks apart
+            let max_step = tick_hz.div_ceil(HARDWARE_HZ);
+
+            let mut last = to_embassy_ticks(HARDWARE_HZ - 1000, tick_hz);
+            for hardware_ticks in HARDWARE_HZ - 999..HARDWARE_HZ + 1000 {
+                let now = to_embassy_ticks(hardware_ticks, tick_hz);
+                assert!(now >= last && now - last <= max_step, "{tick_hz}Hz");
+                last = now;
+            }
+        }
+    }
+
+    #[test_case]
+    fn snapshot_counts_overflow_still_pending(_gba: &mut agb::Gba) {
+        // The counter wraps while being read, inside a critical section
+        let timer = SimulatedTimer::new(0xFFFF, 2);
+        timer.period.set(5);
+
+        let before = calc_now(5, 0xFFFE, RELOAD, DEFAULT_TIMER_OVERFLOW_AMOUNT as u32);
+        assert_eq!(
+            snapshot(
+                || timer.period.get(),
+                || timer.read_counter(),
+                || timer.pending.get(),
+            ),
+            (6, RELOAD + 1)
+        );
+        assert!(timer.now() >= before);
+    }
+
+    #[test_case]
+    fn snapshot_retries_when_interrupt_runs_between_reads(_gba: &mut agb::Gba) {
+        let periods = [5, 6, 6, 6];
+        let counters = [0xFFFF, RELOAD + 1];
+        let (mut p, mut c) = (0, 0);
+
+        let pair = snapshot(
+            || {
+                p += 1;
+                periods[p - 1]
+            },
+            || {
+                c += 1;
+                counters[c - 1]
+            },
+            || false,
+        );
+        assert_eq!(pair, (6, RELOAD + 1));
+    }
+
+    #[test_case]
+    fn now_is_monotonic_with_interrupts_at_any_point(_gba: &mut agb::Gba) {
+        let timer = SimulatedTimer::new(RELOAD, 3);
+        let mut rng = 0x1234_5678_u32;
+        let mut held = 0;
+        let mut last = 0;
+
+        for _ in 0..20_000 {
+            // xorshift32 decides when the interrupt gets to run, as if critical
+            // sections held it back for a varying time. Never for a whole period
+            // though: a second overflow while one is pending is lost on hardware too.
+            rng ^= rng << 13;
+            rng ^= rng >> 17;
+            rng ^= rng << 5;
+            held = if timer.pending.get() { held + 1 } else { 0 };
+            if rng.is_multiple_of(4) || held == 8 {
+                timer.run_interrupt();
+            }
+
+            let now = timer.now();
+            assert!(now >= last, "time went backwards: {last} -> {now}");
+            last = now;
+        }
+        assert!(timer.period.get() > 100);
+    }
 }
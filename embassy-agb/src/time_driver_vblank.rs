@@ -0,0 +1,157 @@
+//! Embassy time driver counting VBlank interrupts
+//!
+//! Selected with the `time-driver-vblank` feature in place of the hardware timer driver,
+//! so all four timers stay free for sound and anything else.
+//!
+//! ## Precision: one frame (~16.7ms)
+//! Time only advances once per VBlank, 59.73 times a second. `Instant::now()` jumps a
+//! frame at a time, and every timer fires on the first VBlank at or after its deadline,
+//! so `Timer::after(Duration::from_millis(1))` takes up to a whole frame. Timeouts,
+//! tickers and delays all round up to frames. Frame-paced games rarely notice; anything
+//! that needs sub-frame timing (measuring code, audio sync, serial protocols) should use
+//! the timer driver instead.
+//!
+//! Ticks are still reported at embassy-time's `TICK_HZ`, scaled from the frame length
+//! of exactly 280896 CPU cycles, so durations convert the same as with the timer driver.
+//!
+//! [`crate::Config::timer`] and [`crate::reconfigure_timer()`] have no effect.
+
+use core::cell::RefCell;
+use portable_atomic::{AtomicBool, AtomicU64, Ordering};
+
+use critical_section::CriticalSection;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_time_driver::Driver;
+use embassy_time_queue_utils::Queue;
+
+use agb::interrupt::{add_interrupt_handler, Interrupt};
+
+use crate::config::TimerConfig;
+
+#[cfg(any(
+    feature = "time-driver",
+    feature = "time-driver-timer0",
+    feature = "time-driver-timer1",
+    feature = "time-driver-timer2",
+    feature = "time-driver-timer3",
+))]
+compile_error!(
+    "embassy-agb: `time-driver-vblank` replaces the hardware timer driver, so it can't be \
+     combined with `time-driver` or a `time-driver-timerN` feature. `time-driver` is a \
+     default feature: set `default-features = false` and list the features you need."
+);
+
+/// CPU cycles per frame: 228 scanlines of 1232 cycles
+const CYCLES_PER_FRAME: u64 = 280_896;
+/// CPU clock rate
+const CPU_HZ: u64 = 1 << 24;
+
+/// Convert a frame count to embassy ticks at `tick_hz`, rounding down
+///
+/// Whole seconds and the remainder are scaled separately, so nothing overflows for any
+/// tick rate embassy-time supports.
+const fn frames_to_ticks(frames: u64, tick_hz: u64) -> u64 {
+    let cycles = frames * CYCLES_PER_FRAME;
+    cycles / CPU_HZ * tick_hz + cycles % CPU_HZ * tick_hz / CPU_HZ
+}
+
+/// Embassy time driver advanced by the VBlank interrupt
+struct VBlankTimeDriver {
+    /// VBlanks since the driver started
+    frames: AtomicU64,
+    /// Whether the VBlank handler has been installed
+    installed: AtomicBool,
+    queue: Mutex<CriticalSectionRawMutex, RefCell<Queue>>,
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: VBlankTimeDriver = VBlankTimeDriver {
+    frames: AtomicU64::new(0),
+    installed: AtomicBool::new(false),
+    queue: Mutex::new(RefCell::new(Queue::new())),
+});
+
+impl VBlankTimeDriver {
+    /// Install the VBlank handler, once
+    fn start(&self) {
+        if self.installed.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let handler =
+            unsafe { add_interrupt_handler(Interrupt::VBlank, |cs| DRIVER.on_vblank(cs)) };
+        core::mem::forget(handler);
+    }
+
+    fn on_vblank(&self, cs: CriticalSection) {
+        self.frames.fetch_add(1, Ordering::Relaxed);
+        let now = self.now();
+        crate::watchdog::check(now);
+
+        // Wakes every expired timer; the rest are checked again next frame
+        self.queue.borrow(cs).borrow_mut().next_expiration(now);
+    }
+}
+
+impl Driver for VBlankTimeDriver {
+    fn now(&self) -> u64 {
+        frames_to_ticks(
+            self.frames.load(Ordering::Relaxed),
+            embassy_time_driver::TICK_HZ,
+        )
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &core::task::Waker) {
+        critical_section::with(|cs| {
+            // There is no alarm to program: the queue is checked every VBlank
+            self.queue.borrow(cs).borrow_mut().schedule_wake(at, waker);
+        })
+    }
+}
+
+/// Start counting VBlanks
+pub(crate) fn init() {
+    DRIVER.start();
+}
+
+/// Start counting VBlanks; there is no timer to configure
+pub(crate) fn configure(_config: &TimerConfig) {
+    DRIVER.start();
+}
+
+/// Current driver time in ticks
+pub(crate) fn now() -> u64 {
+    DRIVER.now()
+}
+
+/// VBlanks counted so far; there is no overflow amount
+pub(crate) fn stats() -> crate::TimerStats {
+    crate::TimerStats {
+        interrupts: DRIVER.frames.load(Ordering::Relaxed),
+        overflow_amount: 0,
+    }
+}
+
+/// Nothing to reconfigure: the precision is fixed at one frame
+pub(crate) fn reconfigure(_overflow_amount: u16) {}
+
+/// Every timer is free for the sound mixer
+pub(crate) fn assert_sound_timers_free() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn frames_scale_to_tick_rate(_gba: &mut agb::Gba) {
+        // 2^18 frames are exactly 4389 seconds
+        for tick_hz in [1_000, 32_768, 1_000_000] {
+            assert_eq!(frames_to_ticks(1 << 18, tick_hz), 4389 * tick_hz);
+        }
+
+        // 548.625 ticks per frame at the default rate
+        assert_eq!(frames_to_ticks(1, 32_768), 548);
+        assert_eq!(frames_to_ticks(8, 32_768), 4389);
+        assert_eq!(frames_to_ticks(60, 32_768), 32_917);
+    }
+}
@@ -0,0 +1,125 @@
+//! Axis-aligned bounding boxes for simple collision checks
+//!
+//! Boxes use integer pixel coordinates, matching sprite and background positions.
+//! `x` and `y` are the top-left corner and y grows downwards, as on screen.
+
+/// How far below a platform's top edge a falling box still lands on it, at low speeds
+const LANDING_MARGIN: i32 = 8;
+
+/// Axis-aligned bounding box
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::collision::Aabb;
+///
+/// let platform = Aabb::new(100, 130, 50, 20);
+/// # let (mut player_x, mut player_y, mut velocity_y) = (110, 120, 3);
+/// let player = Aabb::new(player_x, player_y + velocity_y, 8, 8);
+///
+/// if player.on_top_of(&platform, velocity_y) {
+///     // Land: stand on the platform's top edge
+///     player_y = platform.y - player.h;
+///     velocity_y = 0;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Aabb {
+    /// Left edge
+    pub x: i32,
+    /// Top edge
+    pub y: i32,
+    /// Width in pixels
+    pub w: i32,
+    /// Height in pixels
+    pub h: i32,
+}
+
+impl Aabb {
+    /// Box with its top-left corner at (`x`, `y`)
+    pub const fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Right edge (exclusive)
+    pub const fn right(&self) -> i32 {
+        self.x + self.w
+    }
+
+    /// Bottom edge (exclusive)
+    pub const fn bottom(&self) -> i32 {
+        self.y + self.h
+    }
+
+    /// The same box moved by (`dx`, `dy`)
+    pub const fn offset(&self, dx: i32, dy: i32) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.w, self.h)
+    }
+
+    /// Whether the point (`x`, `y`) is inside the box
+    pub const fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Whether the boxes overlap; boxes that only touch edges don't
+    pub const fn intersects(&self, other: &Aabb) -> bool {
+        self.right() > other.x
+            && self.x < other.right()
+            && self.bottom() > other.y
+            && self.y < other.bottom()
+    }
+
+    /// Whether this box, moving down at `vy` pixels per frame, lands on top of `other`
+    ///
+    /// True when the boxes overlap horizontally and this box's bottom edge is on or just
+    /// past `other`'s top edge: within `vy` pixels, and at least 8 so slow falls still
+    /// land. Always false while moving up (`vy < 0`), so jumps pass through platforms
+    /// from below.
+    pub const fn on_top_of(&self, other: &Aabb, vy: i32) -> bool {
+        let margin = if vy > LANDING_MARGIN {
+            vy
+        } else {
+            LANDING_MARGIN
+        };
+
+        vy >= 0
+            && self.right() > other.x
+            && self.x < other.right()
+            && self.bottom() >= other.y
+            && self.bottom() <= other.y + margin
+    }
+
+    /// Smallest move (`dx`, `dy`) that pushes this box out of `other`
+    ///
+    /// Pushes along whichever axis overlaps least, so a box landing on a floor is pushed
+    /// up and one walking into a wall is pushed back. Returns `(0, 0)` if the boxes
+    /// don't overlap.
+    pub const fn resolve(&self, other: &Aabb) -> (i32, i32) {
+        if !self.intersects(other) {
+            return (0, 0);
+        }
+
+        // Distance to move left/up or right/down to clear `other`
+        let push_left = other.x - self.right();
+        let push_right = other.right() - self.x;
+        let push_up = other.y - self.bottom();
+        let push_down = other.bottom() - self.y;
+
+        let dx = if -push_left < push_right {
+            push_left
+        } else {
+            push_right
+        };
+        let dy = if -push_up < push_down {
+            push_up
+        } else {
+            push_down
+        };
+
+        if dx.abs() < dy.abs() {
+            (dx, 0)
+        } else {
+            (0, dy)
+        }
+    }
+}
@@ -16,3 +16,33 @@ macro_rules! rgb15 {
         ((B5 << 10) | (G5 << 5) | R5) as u16
     }};
 }
+
+/// Blend two RGB15 colors channel by channel
+///
+/// `t` is clamped to `0..=1`, where 0 gives `a` and 1 gives `b`. Each 5-bit channel is
+/// rounded to the nearest value rather than truncated, so a blend halfway between two
+/// adjacent shades lands on the upper one instead of always the lower.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use agb::fixnum::num;
+/// use embassy_agb::rgb15;
+/// use embassy_agb::utils::color::lerp_rgb15;
+///
+/// let day = rgb15!(0x87CEEB);
+/// let night = rgb15!(0x191970);
+/// let dusk = lerp_rgb15(day, night, num!(0.5));
+/// ```
+pub fn lerp_rgb15(a: u16, b: u16, t: agb::fixnum::Num<i16, 8>) -> u16 {
+    let t = t.to_raw().clamp(0, 1 << 8) as u32;
+
+    let channel = |shift: u16| {
+        let from = ((a >> shift) & 0x1F) as u32;
+        let to = ((b >> shift) & 0x1F) as u32;
+        let mixed = (from * ((1 << 8) - t) + to * t + (1 << 7)) >> 8;
+        (mixed as u16) << shift
+    };
+
+    channel(0) | channel(5) | channel(10)
+}
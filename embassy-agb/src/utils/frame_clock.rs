@@ -0,0 +1,151 @@
+//! Frame-counted timing for builds without the `time` feature
+//!
+//! [`FrameEvents::frame_count`](crate::FrameEvents::frame_count) is a clock that needs no
+//! hardware timer: at ~59.73 frames per second, counting frames is enough for cooldowns,
+//! invulnerability windows and menu timeouts. [`Deadline`] marks a future frame and
+//! handles the counter wrapping around, and [`FrameClock`] keeps the current frame so
+//! deadlines can be set and checked without passing frame numbers around.
+
+/// Frames per second times 10000 (the GBA refreshes at ~59.7275Hz)
+const FRAMES_PER_10000_SECS: u64 = 597_275;
+
+/// A point in the future measured in frames
+///
+/// Comparisons treat frame numbers as a wrapping counter, so a deadline set just before
+/// `u32::MAX` is still reached just after the counter wraps. A deadline can be at most
+/// `i32::MAX` frames (about 13 months) away.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::frame_clock::Deadline;
+///
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+/// let events = peripherals.wait_frame().await;
+/// let cooldown = Deadline::in_frames(events.frame_count, 30);
+///
+/// loop {
+///     let events = peripherals.wait_frame().await;
+///     if cooldown.reached(events.frame_count) {
+///         break;
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Deadline {
+    frame: u32,
+}
+
+impl Deadline {
+    /// Deadline `frames` frames after `current_frame`
+    pub const fn in_frames(current_frame: u32, frames: u32) -> Self {
+        Self {
+            frame: current_frame.wrapping_add(frames),
+        }
+    }
+
+    /// Deadline roughly `secs` seconds after `current_frame`
+    pub const fn in_secs(current_frame: u32, secs: u32) -> Self {
+        Self::in_frames(current_frame, secs_to_frames(secs))
+    }
+
+    /// Deadline at exactly `frame`
+    pub const fn at(frame: u32) -> Self {
+        Self { frame }
+    }
+
+    /// Frame number the deadline falls on
+    pub const fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Whether `current_frame` is at or past the deadline
+    pub const fn reached(&self, current_frame: u32) -> bool {
+        current_frame.wrapping_sub(self.frame) as i32 >= 0
+    }
+
+    /// Frames left until the deadline, 0 once it has been reached
+    pub const fn remaining(&self, current_frame: u32) -> u32 {
+        if self.reached(current_frame) {
+            0
+        } else {
+            self.frame.wrapping_sub(current_frame)
+        }
+    }
+}
+
+/// Tracks the current frame so [`Deadline`]s can be used without passing frame numbers
+///
+/// Call [`update()`](Self::update) with each frame's events.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::frame_clock::FrameClock;
+///
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+/// let mut clock = FrameClock::new();
+/// let mut game_over = None;
+///
+/// loop {
+///     let events = peripherals.wait_frame().await;
+///     clock.update(&events);
+///
+///     // Player died: show the game over screen for 2 seconds
+///     # let player_died = false;
+///     if player_died {
+///         game_over = Some(clock.deadline_in_secs(2));
+///     }
+///     if game_over.is_some_and(|deadline| clock.reached(deadline)) {
+///         break;
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameClock {
+    frame: u32,
+}
+
+impl FrameClock {
+    /// Create a clock at frame 0
+    pub const fn new() -> Self {
+        Self { frame: 0 }
+    }
+
+    /// Move the clock to the frame in `events`
+    pub fn update(&mut self, events: &crate::FrameEvents) {
+        self.frame = events.frame_count;
+    }
+
+    /// Current frame number
+    pub const fn now(&self) -> u32 {
+        self.frame
+    }
+
+    /// Deadline `frames` frames from now
+    pub const fn deadline_in(&self, frames: u32) -> Deadline {
+        Deadline::in_frames(self.frame, frames)
+    }
+
+    /// Deadline roughly `secs` seconds from now
+    pub const fn deadline_in_secs(&self, secs: u32) -> Deadline {
+        Deadline::in_secs(self.frame, secs)
+    }
+
+    /// Whether `deadline` has been reached
+    pub const fn reached(&self, deadline: Deadline) -> bool {
+        deadline.reached(self.frame)
+    }
+
+    /// Frames since `frame`
+    pub const fn frames_since(&self, frame: u32) -> u32 {
+        self.frame.wrapping_sub(frame)
+    }
+}
+
+/// Number of frames in `secs` seconds, rounded to the nearest frame
+pub const fn secs_to_frames(secs: u32) -> u32 {
+    ((secs as u64 * FRAMES_PER_10000_SECS + 5_000) / 10_000) as u32
+}
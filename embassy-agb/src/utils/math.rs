@@ -0,0 +1,160 @@
+//! Fixed-point vectors for sub-pixel movement
+//!
+//! The GBA has no floating point unit, so positions and velocities are best kept in
+//! fixed point. [`Vec2`] stores both components as [`Fixed`] (agb's `Num<i32, 8>`, i.e.
+//! 1/256 pixel steps): a velocity of 0.5 pixels per frame moves a sprite one pixel every
+//! other frame instead of rounding to 0 or 1. Convert to whole pixels with
+//! [`Vec2::floor()`] when placing objects.
+
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use agb::fixnum::{Num, Vector2D};
+
+/// 24.8 fixed-point number, agb's usual choice for positions
+pub type Fixed = Num<i32, 8>;
+
+/// 2D vector of [`Fixed`] components
+///
+/// Converts into `Vector2D<i32>` by flooring, so it can be passed straight to
+/// `Object::set_pos`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use agb::fixnum::num;
+/// use embassy_agb::utils::math::Vec2;
+///
+/// # fn example(mut ship: agb::display::object::Object) {
+/// let mut position = Vec2::from_int(120, 80);
+/// let velocity = Vec2::new(num!(0.75), num!(-0.25));
+///
+/// // Once per frame
+/// position += velocity;
+/// ship.set_pos(position);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Vec2 {
+    /// Horizontal component
+    pub x: Fixed,
+    /// Vertical component (down is positive, as on screen)
+    pub y: Fixed,
+}
+
+impl Vec2 {
+    /// The zero vector
+    pub const ZERO: Self = Self {
+        x: Num::from_raw(0),
+        y: Num::from_raw(0),
+    };
+
+    /// Vector from fixed-point components
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Vector from whole-pixel components
+    pub fn from_int(x: i32, y: i32) -> Self {
+        Self::new(Fixed::new(x), Fixed::new(y))
+    }
+
+    /// Both components multiplied by `factor`
+    pub fn scale(self, factor: Fixed) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+
+    /// Whole-pixel position, rounding both components down
+    pub fn floor(self) -> Vector2D<i32> {
+        Vector2D::new(self.x.floor(), self.y.floor())
+    }
+
+    /// Dot product
+    pub fn dot(self, other: Self) -> Fixed {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Squared length, cheaper than [`length()`](Self::length) for comparing distances
+    ///
+    /// Overflows for vectors longer than about 180 pixels; compare
+    /// [`length()`](Self::length) for those.
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// Length of the vector
+    pub fn length(self) -> Fixed {
+        Vector2D::new(self.x, self.y).magnitude()
+    }
+
+    /// Vector of length 1 in the same direction, or zero for the zero vector
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length == Fixed::new(0) {
+            Self::ZERO
+        } else {
+            Self::new(self.x / length, self.y / length)
+        }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<Fixed> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fixed) -> Self {
+        self.scale(rhs)
+    }
+}
+
+impl From<Vector2D<Fixed>> for Vec2 {
+    fn from(value: Vector2D<Fixed>) -> Self {
+        Self::new(value.x, value.y)
+    }
+}
+
+impl From<Vec2> for Vector2D<Fixed> {
+    fn from(value: Vec2) -> Self {
+        Vector2D::new(value.x, value.y)
+    }
+}
+
+impl From<Vec2> for Vector2D<i32> {
+    fn from(value: Vec2) -> Self {
+        value.floor()
+    }
+}
@@ -1,4 +1,28 @@
 //! Utility functions and macros for embassy-agb
 
+/// Integer bounding boxes for collision checks
+pub mod collision;
+
 /// Color conversion utilities and macros
 pub mod color;
+
+/// Frame-counted deadlines that work without a hardware timer
+pub mod frame_clock;
+
+/// Fixed-point vectors for positions and velocities
+pub mod math;
+
+/// Per-label timings of hot code for optimizing on an emulator
+#[cfg(feature = "profiling")]
+pub mod profile;
+
+/// Pausable stopwatch and countdown timers for gameplay
+#[cfg(feature = "time")]
+pub mod stopwatch;
+
+/// Frame-aligned one-shot timers and tickers
+#[cfg(feature = "time")]
+pub mod timer;
+
+#[cfg(feature = "time")]
+pub use timer::{CancelHandle, CancellableTimer, Cancelled, FrameTicker};
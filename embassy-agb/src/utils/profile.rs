@@ -0,0 +1,147 @@
+//! Lightweight profiling scopes
+//!
+//! A [`Scope`] reads [`monotonic_ticks()`](crate::monotonic_ticks) when it starts and
+//! ends, and adds the difference to a running total for its label. Call [`dump()`] once
+//! per frame to print the totals through [`debug_print!`](crate::debug_print) and start
+//! the next frame from zero:
+//!
+//! ```text
+//! physics: 2.1ms (1 call)
+//! render: 4.8ms (3 calls)
+//! ```
+//!
+//! Starting or ending a scope is a timer read and a short critical section, so scopes
+//! can stay in release builds. Times are counted in time driver ticks (~30.5µs at the
+//! default 32.768kHz), so code that runs for less than a tick may be measured as 0 or 1
+//! tick each call; the totals still even out over many calls.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+
+/// Number of different labels recorded between two [`dump()`] or [`reset()`] calls
+pub const MAX_LABELS: usize = 16;
+
+/// Time accumulated for one label
+struct Entry {
+    label: &'static str,
+    ticks: u64,
+    calls: u32,
+}
+
+/// Totals since the last [`dump()`] or [`reset()`], in the order labels were first seen
+static ENTRIES: Mutex<CriticalSectionRawMutex, RefCell<heapless::Vec<Entry, MAX_LABELS>>> =
+    Mutex::new(RefCell::new(heapless::Vec::new()));
+
+/// A timed section of code
+///
+/// Ends when [`end()`](Self::end) is called or the scope is dropped, so it can also
+/// guard a block that returns early.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::profile::{self, Scope};
+///
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+/// loop {
+///     peripherals.wait_frame().await;
+///
+///     let physics = Scope::start("physics");
+///     // ... update positions
+///     physics.end();
+///
+///     let _render = Scope::start("render");
+///     // ... draw, ended when `_render` goes out of scope
+/// #   drop(_render);
+///
+///     profile::dump();
+/// }
+/// # }
+/// ```
+#[must_use = "a scope measures until it is ended or dropped"]
+pub struct Scope {
+    label: &'static str,
+    start: u64,
+}
+
+impl Scope {
+    /// Start timing a section of code under `label`
+    pub fn start(label: &'static str) -> Self {
+        Self {
+            label,
+            start: crate::monotonic_ticks(),
+        }
+    }
+
+    /// Stop timing and add the time to the label's total
+    pub fn end(self) {}
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let ticks = crate::monotonic_ticks().wrapping_sub(self.start);
+        record(self.label, ticks);
+    }
+}
+
+/// Add `ticks` to `label`'s total, dropping it if [`MAX_LABELS`] labels are in use
+fn record(label: &'static str, ticks: u64) {
+    ENTRIES.lock(|entries| {
+        let mut entries = entries.borrow_mut();
+        match entries.iter_mut().find(|entry| entry.label == label) {
+            Some(entry) => {
+                entry.ticks += ticks;
+                entry.calls += 1;
+            }
+            None => {
+                let _ = entries.push(Entry {
+                    label,
+                    ticks,
+                    calls: 1,
+                });
+            }
+        }
+    });
+}
+
+/// Total time recorded for `label` since the last [`dump()`] or [`reset()`], in
+/// microseconds
+pub fn total_micros(label: &str) -> Option<u64> {
+    ENTRIES.lock(|entries| {
+        entries
+            .borrow()
+            .iter()
+            .find(|entry| entry.label == label)
+            .map(|entry| ticks_to_micros(entry.ticks))
+    })
+}
+
+/// Print every label's total through [`debug_print!`](crate::debug_print), then clear them
+///
+/// Prints nothing on real hardware, where there is no debug output.
+pub fn dump() {
+    let entries = ENTRIES.lock(|entries| core::mem::take(&mut *entries.borrow_mut()));
+    for entry in &entries {
+        let micros = ticks_to_micros(entry.ticks);
+        crate::debug_print!(
+            "{}: {}.{}ms ({} call{})",
+            entry.label,
+            micros / 1000,
+            micros % 1000 / 100,
+            entry.calls,
+            if entry.calls == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Clear every label's total without printing
+pub fn reset() {
+    ENTRIES.lock(|entries| entries.borrow_mut().clear());
+}
+
+/// Convert time driver ticks to microseconds
+fn ticks_to_micros(ticks: u64) -> u64 {
+    ticks * 1_000_000 / embassy_time::TICK_HZ
+}
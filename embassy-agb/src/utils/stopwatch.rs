@@ -0,0 +1,142 @@
+//! Pausable gameplay timers built on [`Instant`]
+//!
+//! Unlike a raw `Instant`, a [`Stopwatch`] can be paused (e.g. while the pause menu is
+//! open) without the paused time counting towards the elapsed total.
+
+use embassy_time::{Duration, Instant};
+
+/// Measures elapsed time, excluding any time spent paused
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::stopwatch::Stopwatch;
+///
+/// let mut level_clock = Stopwatch::start();
+/// // Pause menu opened
+/// level_clock.pause();
+/// // Pause menu closed
+/// level_clock.resume();
+/// let time_taken = level_clock.elapsed();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stopwatch {
+    /// Time accumulated before the current run
+    accumulated: Duration,
+    /// When the current run started, `None` while paused
+    running_since: Option<Instant>,
+}
+
+impl Stopwatch {
+    /// Create a paused stopwatch reading zero
+    pub const fn new() -> Self {
+        Self {
+            accumulated: Duration::from_ticks(0),
+            running_since: None,
+        }
+    }
+
+    /// Create a stopwatch that starts counting from now
+    pub fn start() -> Self {
+        Self {
+            accumulated: Duration::from_ticks(0),
+            running_since: Some(Instant::now()),
+        }
+    }
+
+    /// Total time spent running
+    pub fn elapsed(&self) -> Duration {
+        match self.running_since {
+            Some(since) => self.accumulated + since.elapsed(),
+            None => self.accumulated,
+        }
+    }
+
+    /// Freeze the elapsed time. Does nothing if already paused.
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += since.elapsed();
+        }
+    }
+
+    /// Continue counting from the paused time. Does nothing if already running.
+    pub fn resume(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    /// Set the elapsed time back to zero, keeping the stopwatch running or paused
+    pub fn reset(&mut self) {
+        self.accumulated = Duration::from_ticks(0);
+        if self.running_since.is_some() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    /// Whether the stopwatch is counting
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+}
+
+/// Counts down from a duration, excluding any time spent paused
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::stopwatch::Countdown;
+/// use embassy_agb::Duration;
+///
+/// let combo_window = Countdown::start(Duration::from_millis(500));
+/// if !combo_window.is_expired() {
+///     // Still in time for the next hit
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Countdown {
+    duration: Duration,
+    stopwatch: Stopwatch,
+}
+
+impl Countdown {
+    /// Create a countdown of `duration` that starts now
+    pub fn start(duration: Duration) -> Self {
+        Self {
+            duration,
+            stopwatch: Stopwatch::start(),
+        }
+    }
+
+    /// Time left before the countdown expires, zero once expired
+    pub fn remaining(&self) -> Duration {
+        self.duration
+            .checked_sub(self.stopwatch.elapsed())
+            .unwrap_or(Duration::from_ticks(0))
+    }
+
+    /// Whether the full duration has run down
+    pub fn is_expired(&self) -> bool {
+        self.stopwatch.elapsed() >= self.duration
+    }
+
+    /// Freeze the countdown. Does nothing if already paused.
+    pub fn pause(&mut self) {
+        self.stopwatch.pause();
+    }
+
+    /// Continue counting down. Does nothing if already running.
+    pub fn resume(&mut self) {
+        self.stopwatch.resume();
+    }
+
+    /// Restart the full duration, keeping the countdown running or paused
+    pub fn reset(&mut self) {
+        self.stopwatch.reset();
+    }
+
+    /// Whether the countdown is running
+    pub fn is_running(&self) -> bool {
+        self.stopwatch.is_running()
+    }
+}
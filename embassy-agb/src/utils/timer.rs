@@ -0,0 +1,189 @@
+//! Frame-aligned timers for scheduling game events
+//!
+//! embassy-time's [`Timer`](embassy_time::Timer) and [`Ticker`](embassy_time::Ticker)
+//! fire whenever the hardware timer says so, which is usually partway through drawing a
+//! frame. A state change made then (a boss appearing, a stage switching) can show up in
+//! the bottom half of the screen a frame before the top half.
+//!
+//! [`CancellableTimer`] and [`FrameTicker`] wait for their duration and then for the
+//! next VBlank. They are named apart from the crate root's [`Timer`](crate::Timer) and
+//! [`Ticker`](crate::Ticker), which are embassy-time's.
+//!
+//! ## Alignment guarantee
+//! Each of these resolves on the first VBlank after its duration has passed, never
+//! before the duration and never mid-frame, so code run when it resolves gets the
+//! VBlank period to itself. At worst this adds one frame (~16.7ms) to the duration.
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use embassy_sync::waitqueue::AtomicWaker;
+use embassy_time::Duration;
+
+use crate::display::{VBlankCountFuture, VBLANK_INTERVAL_US};
+
+/// Error returned by a [`CancellableTimer`] that was cancelled through its [`CancelHandle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+/// State shared between a [`CancellableTimer`] and its [`CancelHandle`]
+struct CancelState {
+    cancelled: Cell<bool>,
+    waker: AtomicWaker,
+}
+
+/// Cancels the [`CancellableTimer`] it was created with
+///
+/// Can be kept in another task than the one awaiting the timer, as long as both run on
+/// the same [`Executor`](crate::Executor). The handle shares its state through an `Rc`,
+/// so it isn't `Send` and can't be passed to an
+/// [`InterruptExecutor`](crate::InterruptExecutor) task or through a `SendSpawner`.
+pub struct CancelHandle {
+    state: Rc<CancelState>,
+}
+
+impl CancelHandle {
+    /// Make the timer resolve with [`Cancelled`] instead of firing
+    ///
+    /// Does nothing if the timer already fired.
+    pub fn cancel(&self) {
+        self.state.cancelled.set(true);
+        self.state.waker.wake();
+    }
+
+    /// Whether [`cancel()`](Self::cancel) was called
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.get()
+    }
+}
+
+/// One-shot timer that fires on the first VBlank after its duration
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::CancellableTimer;
+/// use embassy_agb::Duration;
+///
+/// # async fn example() {
+/// let (boss_timer, cancel) = CancellableTimer::oneshot(Duration::from_secs(10));
+///
+/// // Elsewhere, e.g. when the player leaves the arena: cancel.cancel();
+///
+/// if boss_timer.await.is_ok() {
+///     // Spawn the boss, between frames
+/// }
+/// # }
+/// ```
+pub struct CancellableTimer {
+    deadline: embassy_time::Timer,
+    vblank: Option<VBlankCountFuture>,
+    cancel: Rc<CancelState>,
+}
+
+impl CancellableTimer {
+    /// Timer that fires on the first VBlank after `duration`, with a handle to cancel it
+    pub fn oneshot(duration: Duration) -> (Self, CancelHandle) {
+        let state = Rc::new(CancelState {
+            cancelled: Cell::new(false),
+            waker: AtomicWaker::new(),
+        });
+
+        let timer = Self {
+            deadline: embassy_time::Timer::after(duration),
+            vblank: None,
+            cancel: state.clone(),
+        };
+        (timer, CancelHandle { state })
+    }
+}
+
+impl Future for CancellableTimer {
+    type Output = Result<(), Cancelled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if this.cancel.cancelled.get() {
+            return Poll::Ready(Err(Cancelled));
+        }
+        this.cancel.waker.register(cx.waker());
+
+        if this.vblank.is_none() && Pin::new(&mut this.deadline).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        // Duration is up, finish on the next VBlank
+        let vblank = this.vblank.get_or_insert_with(VBlankCountFuture::next);
+        Pin::new(vblank).poll(cx).map(Ok)
+    }
+}
+
+/// Periodic timer that ticks on VBlank every whole number of frames
+///
+/// Wraps embassy-time's [`Ticker`](embassy_time::Ticker) with the period rounded to
+/// whole frames. Like embassy's ticker it keeps to its schedule when a tick is handled
+/// late, so ticks don't drift. Ticks are usually exactly the period apart in frames,
+/// now and then one frame more or less as the timer and display clocks drift apart.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::utils::FrameTicker;
+/// use embassy_agb::Duration;
+///
+/// # async fn example() {
+/// // Spawn an enemy wave every 5 seconds
+/// let mut waves = FrameTicker::every(Duration::from_secs(5));
+/// loop {
+///     waves.next().await;
+///     // Add the wave, between frames
+///     # break;
+/// }
+/// # }
+/// ```
+pub struct FrameTicker {
+    inner: embassy_time::Ticker,
+    frames: u32,
+}
+
+impl FrameTicker {
+    /// Tick every `period`, rounded to the nearest whole frame (at least one)
+    pub fn every(period: Duration) -> Self {
+        let frames = (period.as_micros() + VBLANK_INTERVAL_US / 2) / VBLANK_INTERVAL_US;
+        Self::every_frames(frames.clamp(1, u32::MAX as u64) as u32)
+    }
+
+    /// Tick every `frames` frames
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is 0.
+    pub fn every_frames(frames: u32) -> Self {
+        assert!(frames > 0, "Ticker period must be at least one frame");
+        let period = Duration::from_micros(frames as u64 * VBLANK_INTERVAL_US);
+        Self {
+            inner: embassy_time::Ticker::every(period),
+            frames,
+        }
+    }
+
+    /// Period in frames
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Restart the schedule from now
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Wait for the next tick, resolving on the VBlank after it
+    pub async fn next(&mut self) {
+        self.inner.next().await;
+        VBlankCountFuture::next().await;
+    }
+}
@@ -0,0 +1,151 @@
+//! Software watchdog that resets the game when the main loop hangs
+//!
+//! The watchdog is checked from the time driver's timer interrupt rather than from a
+//! task, so it still fires when the executor is stuck in a loop that never awaits. If
+//! it isn't fed within its timeout it runs its [`Action`]: a BIOS soft reset by default.
+//!
+//! ## Limitations
+//! - Hangs with interrupts disabled (e.g. inside a critical section) are not caught.
+//! - The check runs on every timer interrupt (~1ms by default). With
+//!   [`TimerConfig::cascade`](crate::config::TimerConfig::cascade) or the
+//!   `time-driver-vblank` driver it runs every VBlank instead, so a timeout can be up to
+//!   a frame late.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_time::Duration;
+
+use crate::time_driver;
+
+/// What the watchdog does when it isn't fed in time
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Action {
+    /// Restart the game with [`soft_reset()`](crate::soft_reset)
+    #[default]
+    SoftReset,
+    /// Jump to a recovery routine, e.g. one that shows an error screen
+    ///
+    /// The routine runs inside the timer interrupt with interrupts disabled, so it
+    /// can't await anything or rely on other interrupts. It can end with
+    /// [`soft_reset()`](crate::soft_reset) or [`reset_to_main()`](crate::reset_to_main).
+    Recover(fn() -> !),
+}
+
+/// State of the running watchdog
+#[derive(Clone, Copy)]
+struct Armed {
+    timeout: u64,
+    deadline: u64,
+    action: Action,
+}
+
+/// The running watchdog, if any
+static WATCHDOG: Mutex<CriticalSectionRawMutex, Cell<Option<Armed>>> = Mutex::new(Cell::new(None));
+
+/// Handle to the running watchdog, fed from the loop it guards
+///
+/// Only one watchdog can run at a time. Dropping the handle stops it, so keep it alive
+/// for as long as the loop runs.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use embassy_agb::watchdog::Watchdog;
+/// use embassy_agb::Duration;
+///
+/// # async fn example(mut peripherals: embassy_agb::GbaPeripherals<'_>) {
+/// // Reset if the game loop misses half a second of frames
+/// let watchdog = Watchdog::start(Duration::from_millis(500));
+/// loop {
+///     peripherals.wait_frame().await;
+///     watchdog.feed();
+///     // Update the game
+/// }
+/// # }
+/// ```
+#[must_use = "dropping the handle stops the watchdog"]
+pub struct Watchdog {
+    _private: (),
+}
+
+impl Watchdog {
+    /// Start a watchdog that soft resets if not fed for `timeout`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a watchdog is already running.
+    pub fn start(timeout: Duration) -> Self {
+        Self::start_with(timeout, Action::SoftReset)
+    }
+
+    /// Start a watchdog that runs `action` if not fed for `timeout`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a watchdog is already running.
+    pub fn start_with(timeout: Duration, action: Action) -> Self {
+        let timeout = timeout.as_ticks();
+        let started = WATCHDOG.lock(|watchdog| {
+            if watchdog.get().is_some() {
+                return false;
+            }
+            watchdog.set(Some(Armed {
+                timeout,
+                deadline: time_driver::now().saturating_add(timeout),
+                action,
+            }));
+            true
+        });
+        assert!(started, "a watchdog is already running");
+
+        Self { _private: () }
+    }
+
+    /// Restart the timeout
+    pub fn feed(&self) {
+        let now = time_driver::now();
+        WATCHDOG.lock(|watchdog| {
+            if let Some(mut armed) = watchdog.get() {
+                armed.deadline = now.saturating_add(armed.timeout);
+                watchdog.set(Some(armed));
+            }
+        });
+    }
+
+    /// Time left before the watchdog fires
+    pub fn remaining(&self) -> Duration {
+        let now = time_driver::now();
+        let deadline = WATCHDOG.lock(|watchdog| watchdog.get().map_or(now, |armed| armed.deadline));
+        Duration::from_ticks(deadline.saturating_sub(now))
+    }
+
+    /// Stop the watchdog
+    pub fn stop(self) {}
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        WATCHDOG.lock(|watchdog| watchdog.set(None));
+    }
+}
+
+/// Fire the watchdog if its deadline has passed
+///
+/// Called from the time driver's interrupt handler.
+pub(crate) fn check(now: u64) {
+    let expired = WATCHDOG.lock(|watchdog| match watchdog.get() {
+        Some(armed) if now >= armed.deadline => {
+            watchdog.set(None);
+            Some(armed.action)
+        }
+        _ => None,
+    });
+
+    match expired {
+        None => {}
+        Some(Action::SoftReset) => crate::power::soft_reset(),
+        Some(Action::Recover(routine)) => routine(),
+    }
+}
@@ -16,7 +16,8 @@
 //! - Hold buttons for continuous movement
 //! - Hold multiple buttons for diagonal movement
 //! - Ship shows FLAME animation when moving, IDLE when stationary
-//! Input polling: 60Hz (configurable from 30-120Hz)
+//!
+//! Input polling: 60Hz (configurable, see PollingRate)
 
 #![no_std]
 #![no_main]
@@ -30,8 +31,9 @@ use agb::{display::object::Object, include_aseprite};
 use alloc::vec::Vec;
 use embassy_agb::{
     agb::input::Button,
+    display::animation::Animator,
     input::{AsyncInput, InputConfig, PollingRate},
-    sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex},
+    sync::Shared,
     Spawner,
 };
 
@@ -44,39 +46,16 @@ include_aseprite!(mod rocket_sprites, "gfx/rocket.aseprite");
 // Shared button state between input task and main loop
 #[derive(Clone, Copy, Default)]
 struct ButtonState {
-    up: bool,
-    down: bool,
-    left: bool,
-    right: bool,
+    /// Net D-pad movement, each axis in -1..=1
+    movement: (i32, i32),
     a: bool,
     a_just_pressed: bool,
 }
 
 impl ButtonState {
-    /// Calculate net movement from current button state
-    fn net_movement(&self) -> (i32, i32) {
-        let mut x = 0;
-        let mut y = 0;
-
-        if self.left {
-            x -= 1;
-        }
-        if self.right {
-            x += 1;
-        }
-        if self.up {
-            y -= 1;
-        }
-        if self.down {
-            y += 1;
-        }
-
-        (x, y)
-    }
-
-    /// Check if any movement button is pressed
+    /// Check if the D-pad is moving the ship
     fn is_moving(&self) -> bool {
-        self.up || self.down || self.left || self.right
+        self.movement != (0, 0)
     }
 }
 
@@ -104,11 +83,8 @@ impl Rocket {
     }
 }
 
-static BUTTON_STATE: Mutex<CriticalSectionRawMutex, ButtonState> = Mutex::new(ButtonState {
-    up: false,
-    down: false,
-    left: false,
-    right: false,
+static BUTTON_STATE: Shared<ButtonState> = Shared::new(ButtonState {
+    movement: (0, 0),
     a: false,
     a_just_pressed: false,
 });
@@ -116,30 +92,21 @@ static BUTTON_STATE: Mutex<CriticalSectionRawMutex, ButtonState> = Mutex::new(Bu
 // Input task: continuously poll button state and update shared state
 #[embassy_executor::task]
 async fn input_task(mut input: AsyncInput) {
-    let mut prev_a_pressed = false;
-
     loop {
-        // Poll current button state (non-blocking)
-        let up_pressed = input.is_pressed(Button::UP);
-        let down_pressed = input.is_pressed(Button::DOWN);
-        let left_pressed = input.is_pressed(Button::LEFT);
-        let right_pressed = input.is_pressed(Button::RIGHT);
-        let a_pressed = input.is_pressed(Button::A);
+        // Latch current button state (non-blocking)
+        input.update();
+        let movement = input.direction();
+        let a_pressed = input.is_pressed_polling(Button::A);
 
-        // Detect A button just pressed (edge detection)
-        let a_just_pressed = a_pressed && !prev_a_pressed;
-        prev_a_pressed = a_pressed;
+        // Edge detection against the state from the previous update()
+        let a_just_pressed = input.is_just_pressed_polling(Button::A);
 
         // Update shared state
-        {
-            let mut state = BUTTON_STATE.lock().await;
-            state.up = up_pressed;
-            state.down = down_pressed;
-            state.left = left_pressed;
-            state.right = right_pressed;
-            state.a = a_pressed;
-            state.a_just_pressed = a_just_pressed;
-        }
+        BUTTON_STATE.set(ButtonState {
+            movement,
+            a: a_pressed,
+            a_just_pressed,
+        });
 
         // Wait for any button press or release (non-blocking)
         input.wait_for_any_button_press().await;
@@ -151,9 +118,7 @@ async fn main(spawner: Spawner) -> ! {
     let mut gba = embassy_agb::init(Default::default());
 
     // Configure input polling at 60Hz
-    let input_config = InputConfig {
-        poll_rate: PollingRate::Hz60,
-    };
+    let input_config = InputConfig::new(PollingRate::Hz60);
     spawner.must_spawn(embassy_agb::input::input_polling_task(input_config));
 
     let input = gba.input_with_config(input_config);
@@ -172,9 +137,9 @@ async fn main(spawner: Spawner) -> ! {
     const MAX_Y: i32 = agb::display::HEIGHT - SPRITE_SIZE;
 
     // Animation timing
-    let mut frame_count = 0u32;
     const IDLE_ANIMATION_RATE: u32 = 15; // slower animation for idle
     const FLAME_ANIMATION_RATE: u32 = 8; // faster animation for flame
+    let mut ship_animation = Animator::looping(&ship_sprites::IDLE, IDLE_ANIMATION_RATE);
 
     // Rocket management
     let mut rockets: Vec<Rocket> = Vec::new();
@@ -190,15 +155,17 @@ async fn main(spawner: Spawner) -> ! {
         display.wait_for_vblank().await;
 
         // Get current button state and calculate net movement
-        let (move_x, move_y, is_moving, a_pressed, fire_rocket) = {
-            let mut state = BUTTON_STATE.lock().await;
-            let movement = state.net_movement();
-            let fire = state.a_just_pressed;
-            let a_held = state.a;
+        let (move_x, move_y, is_moving, a_pressed, fire_rocket) = BUTTON_STATE.update(|state| {
             // Reset the just_pressed flag after reading it
-            state.a_just_pressed = false;
-            (movement.0, movement.1, state.is_moving(), a_held, fire)
-        };
+            let fire = core::mem::take(&mut state.a_just_pressed);
+            (
+                state.movement.0,
+                state.movement.1,
+                state.is_moving(),
+                state.a,
+                fire,
+            )
+        });
 
         // Apply movement if any buttons are pressed
         if move_x != 0 || move_y != 0 {
@@ -212,9 +179,7 @@ async fn main(spawner: Spawner) -> ! {
         }
 
         // Update fire cooldown
-        if fire_cooldown > 0 {
-            fire_cooldown -= 1;
-        }
+        fire_cooldown = fire_cooldown.saturating_sub(1);
 
         // Fire rocket if A button was just pressed or if A is held and cooldown is ready
         if (fire_rocket || (a_pressed && fire_cooldown == 0)) && rockets.len() < MAX_ROCKETS {
@@ -240,11 +205,11 @@ async fn main(spawner: Spawner) -> ! {
             (&ship_sprites::IDLE, IDLE_ANIMATION_RATE)
         };
 
-        // Calculate current animation frame based on the chosen rate
-        let animation_frame = (frame_count / animation_rate) as usize;
+        // Keeps the animation going while the state stays the same
+        ship_animation.set_animation(animation_tag, animation_rate);
 
         // Create sprite object with current animation frame and position
-        let mut ship = Object::new(animation_tag.animation_sprite(animation_frame));
+        let mut ship = Object::new(ship_animation.tick());
         ship.set_pos((ship_x, ship_y));
 
         // Create rocket objects
@@ -267,7 +232,5 @@ async fn main(spawner: Spawner) -> ! {
         }
 
         frame.commit();
-
-        frame_count = frame_count.wrapping_add(1);
     }
 }
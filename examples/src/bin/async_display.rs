@@ -103,7 +103,7 @@ async fn main(_spawner: Spawner) -> ! {
         frame.commit();
 
         // Animation: change color every 60 frames (1 second at 60fps)
-        if frame_count % FRAMES_PER_COLOR_CHANGE == 0 && frame_count > 0 {
+        if frame_count.is_multiple_of(FRAMES_PER_COLOR_CHANGE) && frame_count > 0 {
             color_index = (color_index + 1) % colors.len();
             let new_color = colors[color_index];
 
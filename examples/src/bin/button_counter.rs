@@ -15,8 +15,7 @@
 
 use embassy_agb::agb::input::Button;
 use embassy_agb::input::{AsyncInput, PollingRate};
-use embassy_agb::sync::blocking_mutex::raw::CriticalSectionRawMutex;
-use embassy_agb::sync::signal::Signal;
+use embassy_agb::sync::Signal;
 use embassy_agb::{input::ButtonEvent, Duration, Spawner, Ticker};
 
 #[derive(Clone, Copy)]
@@ -26,7 +25,7 @@ enum CounterAction {
 }
 
 // Signal to communicate counter actions from button task to main
-static COUNTER_SIGNAL: Signal<CriticalSectionRawMutex, CounterAction> = Signal::new();
+static COUNTER_SIGNAL: Signal<CounterAction> = Signal::new();
 
 // Task that awaits button inputs and sends signals to main
 #[embassy_executor::task]
@@ -19,7 +19,7 @@
 //! ```
 //!
 //! Controls: D-pad moves the sprite, clamped to screen edges
-//! Input polling: 60Hz (configurable from 30-120Hz)
+//! Input polling: 60Hz (configurable, see PollingRate)
 
 #![no_std]
 #![no_main]
@@ -34,7 +34,7 @@ use embassy_agb::{
         input::Button,
     },
     input::{AsyncInput, ButtonEvent, InputConfig, PollingRate},
-    sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal},
+    sync::Signal,
     Spawner,
 };
 
@@ -47,7 +47,7 @@ enum Movement {
     Right,
 }
 
-static MOVEMENT_SIGNAL: Signal<CriticalSectionRawMutex, Movement> = Signal::new();
+static MOVEMENT_SIGNAL: Signal<Movement> = Signal::new();
 
 // Input task: detect button presses and signal movement
 #[embassy_executor::task]
@@ -72,9 +72,7 @@ async fn main(spawner: Spawner) -> ! {
     let mut gba = embassy_agb::init(Default::default());
 
     // Configure input polling at 60Hz (matches VBlank rate)
-    let input_config = InputConfig {
-        poll_rate: PollingRate::Hz60,
-    };
+    let input_config = InputConfig::new(PollingRate::Hz60);
 
     // Spawn the input polling task
     spawner.must_spawn(embassy_agb::input::input_polling_task(input_config));
@@ -22,7 +22,8 @@
 //! Controls: D-pad moves the sprite, clamped to screen edges
 //! - Hold buttons for continuous movement
 //! - Hold multiple buttons for diagonal movement (net vector calculated)
-//! Input polling: 60Hz (configurable from 30-120Hz)
+//!
+//! Input polling: 60Hz (configurable, see PollingRate)
 
 #![no_std]
 #![no_main]
@@ -109,9 +110,7 @@ async fn main(spawner: Spawner) -> ! {
     let mut gba = embassy_agb::init(Default::default());
 
     // Configure input polling at 60Hz (matches VBlank rate)
-    let input_config = InputConfig {
-        poll_rate: PollingRate::Hz60,
-    };
+    let input_config = InputConfig::new(PollingRate::Hz60);
 
     // Spawn the input polling task
     spawner.must_spawn(embassy_agb::input::input_polling_task(input_config));
@@ -26,6 +26,8 @@
 extern crate alloc;
 
 use agb::{display::object::Object, include_aseprite, include_wav};
+use embassy_agb::display::animation::Animator;
+use embassy_agb::utils::collision::Aabb;
 use embassy_agb::{agb::input::Button, agb::sound::mixer::Frequency, Spawner};
 
 include_aseprite!(mod goof_sprites, "gfx/goof.aseprite");
@@ -35,39 +37,7 @@ include_aseprite!(mod coin_sprites, "gfx/coin.aseprite");
 /// Jump sound effect
 static JUMP_SOUND: agb::sound::mixer::SoundData = include_wav!("sfx/jump.wav");
 
-#[derive(Clone, Copy)]
-struct Platform {
-    x: i32,
-    y: i32,
-    width: i32,
-    height: i32,
-}
-
-impl Platform {
-    fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
-        Self {
-            x,
-            y,
-            width,
-            height,
-        }
-    }
-
-    fn collides_with(&self, px: i32, py: i32, pw: i32, ph: i32) -> bool {
-        px + pw > self.x
-            && px < self.x + self.width
-            && py + ph > self.y
-            && py < self.y + self.height
-    }
-
-    fn is_on_top(&self, px: i32, py: i32, pw: i32, ph: i32, vy: i32) -> bool {
-        vy >= 0
-            && px + pw > self.x
-            && px < self.x + self.width
-            && py + ph >= self.y
-            && py + ph <= self.y + 8
-    }
-}
+type Platform = Aabb;
 
 #[derive(Clone, Copy)]
 struct Coin {
@@ -85,12 +55,9 @@ impl Coin {
         }
     }
 
-    fn collides_with(&self, px: i32, py: i32, pw: i32, ph: i32) -> bool {
-        if self.collected {
-            return false;
-        }
+    fn collides_with(&self, player: &Aabb) -> bool {
         const COIN_SIZE: i32 = 8;
-        px + pw > self.x && px < self.x + COIN_SIZE && py + ph > self.y && py < self.y + COIN_SIZE
+        !self.collected && Aabb::new(self.x, self.y, COIN_SIZE, COIN_SIZE).intersects(player)
     }
 }
 
@@ -140,15 +107,19 @@ async fn main(_spawner: Spawner) -> ! {
     let mut collected_coins = 0;
 
     const IDLE_ANIMATION_RATE: u32 = 15;
+    const WIN_ANIMATION_RATE: u32 = 5;
+    let mut goof_animation = Animator::looping(&goof_sprites::RIGHT, IDLE_ANIMATION_RATE);
+    let mut goal_animation = Animator::looping(&goof_sprites::RIGHT, WIN_ANIMATION_RATE);
+    let mut coin_animation = Animator::looping(&coin_sprites::IDLE, 8);
 
     loop {
         // Wait for frame and get events (button presses, frame counter, etc.)
         let events = peripherals.wait_frame().await;
 
         if !game_won {
-            // Check for continuous button states (movement)
-            let move_left = peripherals.input.is_pressed(Button::LEFT);
-            let move_right = peripherals.input.is_pressed(Button::RIGHT);
+            // Check for continuous button states (movement), captured with the frame events
+            let move_left = events.is_held(Button::LEFT);
+            let move_right = events.is_held(Button::RIGHT);
 
             if move_left {
                 goof_x -= MOVE_SPEED;
@@ -171,7 +142,8 @@ async fn main(_spawner: Spawner) -> ! {
             let was_on_ground = on_ground;
             on_ground = false;
             for platform in &platforms {
-                if platform.is_on_top(goof_x, next_y, SPRITE_SIZE, SPRITE_SIZE, velocity_y) {
+                let next_bounds = Aabb::new(goof_x, next_y, SPRITE_SIZE, SPRITE_SIZE);
+                if next_bounds.on_top_of(platform, velocity_y) {
                     goof_y = platform.y - SPRITE_SIZE;
                     velocity_y = 0;
                     on_ground = true;
@@ -204,8 +176,9 @@ async fn main(_spawner: Spawner) -> ! {
                 velocity_y = 0;
             }
 
+            let goof_bounds = Aabb::new(goof_x, goof_y, SPRITE_SIZE, SPRITE_SIZE);
             for coin in &mut coins {
-                if coin.collides_with(goof_x, goof_y, SPRITE_SIZE, SPRITE_SIZE) {
+                if coin.collides_with(&goof_bounds) {
                     coin.collected = true;
                     collected_coins += 1;
                 }
@@ -218,43 +191,41 @@ async fn main(_spawner: Spawner) -> ! {
                 collected_coins = 0;
             }
 
-            if goal_platform.collides_with(goof_x, goof_y, SPRITE_SIZE, SPRITE_SIZE) {
+            if goal_platform.intersects(&goof_bounds) {
                 game_won = true;
             }
 
-            let animation_frame = (events.frame_count / IDLE_ANIMATION_RATE) as usize;
-
             let animation_tag = if facing_right {
                 &goof_sprites::RIGHT
             } else {
                 &goof_sprites::LEFT
             };
+            goof_animation.set_animation(animation_tag, IDLE_ANIMATION_RATE);
 
-            let mut goof = Object::new(animation_tag.animation_sprite(animation_frame));
+            let mut goof = Object::new(goof_animation.tick());
             goof.set_pos((goof_x, goof_y));
 
             let mut frame = peripherals.display.frame().await;
             goof.show(&mut frame);
 
             for platform in &platforms {
-                for i in 0..(platform.width / 8) {
+                for i in 0..(platform.w / 8) {
                     let mut platform_obj = Object::new(grass_sprites::IDLE.animation_sprite(0));
                     platform_obj.set_pos((platform.x + i * 8, platform.y));
                     platform_obj.show(&mut frame);
                 }
             }
 
-            for i in 0..(goal_platform.width / 8) {
+            for i in 0..(goal_platform.w / 8) {
                 let mut goal_obj = Object::new(goof_sprites::RIGHT.animation_sprite(0));
                 goal_obj.set_pos((goal_platform.x + i * 8, goal_platform.y));
                 goal_obj.show(&mut frame);
             }
 
-            let coin_animation_frame = (events.frame_count / 8) as usize;
+            let coin_sprite = coin_animation.tick();
             for coin in &coins {
                 if !coin.collected {
-                    let mut coin_obj =
-                        Object::new(coin_sprites::IDLE.animation_sprite(coin_animation_frame));
+                    let mut coin_obj = Object::new(coin_sprite);
                     coin_obj.set_pos((coin.x, coin.y));
                     coin_obj.show(&mut frame);
                 }
@@ -262,40 +233,39 @@ async fn main(_spawner: Spawner) -> ! {
 
             frame.commit();
         } else {
-            let animation_frame = (events.frame_count / 5) as usize;
-
             let animation_tag = if facing_right {
                 &goof_sprites::RIGHT
             } else {
                 &goof_sprites::LEFT
             };
+            // Celebrate with a faster animation
+            goof_animation.set_animation(animation_tag, WIN_ANIMATION_RATE);
 
-            let mut goof = Object::new(animation_tag.animation_sprite(animation_frame));
+            let mut goof = Object::new(goof_animation.tick());
             goof.set_pos((goof_x, goof_y));
 
             let mut frame = peripherals.display.frame().await;
             goof.show(&mut frame);
 
             for platform in &platforms {
-                for i in 0..(platform.width / 8) {
+                for i in 0..(platform.w / 8) {
                     let mut platform_obj = Object::new(grass_sprites::IDLE.animation_sprite(0));
                     platform_obj.set_pos((platform.x + i * 8, platform.y));
                     platform_obj.show(&mut frame);
                 }
             }
 
-            for i in 0..(goal_platform.width / 8) {
-                let mut goal_obj =
-                    Object::new(goof_sprites::RIGHT.animation_sprite(animation_frame));
+            let goal_sprite = goal_animation.tick();
+            for i in 0..(goal_platform.w / 8) {
+                let mut goal_obj = Object::new(goal_sprite);
                 goal_obj.set_pos((goal_platform.x + i * 8, goal_platform.y));
                 goal_obj.show(&mut frame);
             }
 
-            let coin_animation_frame = (events.frame_count / 8) as usize;
+            let coin_sprite = coin_animation.tick();
             for coin in &coins {
                 if !coin.collected {
-                    let mut coin_obj =
-                        Object::new(coin_sprites::IDLE.animation_sprite(coin_animation_frame));
+                    let mut coin_obj = Object::new(coin_sprite);
                     coin_obj.set_pos((coin.x, coin.y));
                     coin_obj.show(&mut frame);
                 }